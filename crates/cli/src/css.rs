@@ -0,0 +1,75 @@
+//! `css transform` and `css minify`.
+
+use std::{fs, path::Path};
+
+use anyhow::Context;
+use clap::Subcommand;
+use css_core::{parse_options, MinifyOptions, TransformOptions, TransformOutput};
+
+use crate::files::{output_paths, read_config, run_all, warn, write, Args, Message};
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Transforms stylesheets to `{name}.out.css`. The mapping of CSS Modules
+    /// is written to `{output}.json`.
+    Transform(Args),
+    /// Minifies stylesheets to `{name}.min.css`.
+    Minify(Args),
+}
+
+pub fn run(command: Command) -> anyhow::Result<bool> {
+    match command {
+        Command::Transform(args) => {
+            let (opts, warnings): (TransformOptions, _) =
+                parse_options(&read_config(args.config.as_deref())?)
+                    .context("failed to deserialize transform options")?;
+            warn(&warnings);
+            opts.validate()?;
+
+            let inputs = args.expand("out")?;
+            let outputs = output_paths(&inputs, args.out_dir.as_deref(), "out")?;
+
+            Ok(run_all(&inputs, |input, code| {
+                let filename = input.to_string_lossy();
+                let output = css_core::transform(code, Some(&filename), &opts)?;
+
+                emit(input, &outputs[input], output)
+            }))
+        }
+        Command::Minify(args) => {
+            let (opts, warnings): (MinifyOptions, _) =
+                parse_options(&read_config(args.config.as_deref())?)
+                    .context("failed to deserialize minifier options")?;
+            warn(&warnings);
+
+            let inputs = args.expand("min")?;
+            let outputs = output_paths(&inputs, args.out_dir.as_deref(), "min")?;
+
+            Ok(run_all(&inputs, |input, code| {
+                let filename = input.to_string_lossy();
+                let output = css_core::minify(code, Some(&filename), &opts)?;
+
+                emit(input, &outputs[input], output)
+            }))
+        }
+    }
+}
+
+fn emit(input: &Path, path: &Path, output: TransformOutput) -> anyhow::Result<Vec<Message>> {
+    write(path, &output.code, output.map.as_deref())?;
+
+    if let Some(mapping) = &output.modules_mapping {
+        let mut json_path = path.as_os_str().to_os_string();
+        json_path.push(".json");
+
+        fs::write(&json_path, mapping)
+            .with_context(|| format!("failed to write `{}`", Path::new(&json_path).display()))?;
+    }
+
+    Ok(output
+        .errors
+        .unwrap_or_default()
+        .iter()
+        .map(|d| Message::new(input, &d.level, &d.message, d.code_frame.as_deref()).fails(d.fatal))
+        .collect())
+}
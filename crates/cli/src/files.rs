@@ -0,0 +1,288 @@
+//! Finding the inputs, writing the outputs and printing the errors.
+
+use std::{
+    collections::{HashMap, HashSet},
+    env,
+    ffi::OsStr,
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use anyhow::{bail, Context};
+use rayon::prelude::*;
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// Files or glob patterns, like `src/**/*.css`.
+    #[arg(required = true)]
+    pub inputs: Vec<String>,
+
+    /// A JSON file with the options.
+    #[arg(short, long)]
+    pub config: Option<PathBuf>,
+
+    /// Write the outputs to this directory instead of next to the inputs,
+    /// keeping the paths of the inputs relative to the current directory, or
+    /// to their common directory if any is outside of it.
+    #[arg(short, long)]
+    pub out_dir: Option<PathBuf>,
+}
+
+impl Args {
+    /// Expands the inputs, skipping the outputs of a previous run which match
+    /// a glob pattern, like `a.min.css` for `*.css`.
+    pub fn expand(&self, suffix: &str) -> anyhow::Result<Vec<PathBuf>> {
+        expand(&self.inputs, |path| match &self.out_dir {
+            Some(out_dir) => without_cur_dir(path).starts_with(without_cur_dir(out_dir)),
+            None => {
+                let stem = Path::new(path.file_stem().unwrap_or_default());
+
+                stem.extension() == Some(OsStr::new(suffix))
+            }
+        })
+    }
+}
+
+fn without_cur_dir(path: &Path) -> &Path {
+    path.strip_prefix(".").unwrap_or(path)
+}
+
+/// A diagnostic of a file, printed to stderr.
+pub struct Message {
+    pub text: String,
+    /// Whether the exit code is `1`.
+    pub fails: bool,
+}
+
+impl Message {
+    /// Prefers the code frame, which contains the location.
+    pub fn new(input: &Path, level: &str, message: &str, code_frame: Option<&str>) -> Self {
+        Message {
+            text: match code_frame {
+                Some(frame) => frame.to_string(),
+                None => format!("{}: {}: {}", input.display(), level, message),
+            },
+            fails: false,
+        }
+    }
+
+    pub fn fails(mut self, fails: bool) -> Self {
+        self.fails = fails;
+        self
+    }
+}
+
+/// Expands the glob patterns of `inputs`, skipping the matches which are
+/// `is_output`. Files which are not patterns are kept, and each file is only
+/// listed once.
+pub fn expand<F>(inputs: &[String], is_output: F) -> anyhow::Result<Vec<PathBuf>>
+where
+    F: Fn(&Path) -> bool,
+{
+    let mut files = vec![];
+    let mut seen = HashSet::new();
+    let mut push = |path: PathBuf| {
+        if seen.insert(path.clone()) {
+            files.push(path);
+        }
+    };
+
+    for input in inputs {
+        if !input.contains(['*', '?', '[']) {
+            push(PathBuf::from(input));
+            continue;
+        }
+
+        let mut matched = false;
+        for entry in glob::glob(input).with_context(|| format!("invalid pattern `{}`", input))? {
+            let path = entry.with_context(|| format!("failed to expand `{}`", input))?;
+            if path.is_file() && !is_output(&path) {
+                matched = true;
+                push(path);
+            }
+        }
+
+        if !matched {
+            bail!("`{}` matches no files", input)
+        }
+    }
+
+    Ok(files)
+}
+
+/// Reads the options, or returns `{}`.
+pub fn read_config(config: Option<&Path>) -> anyhow::Result<String> {
+    match config {
+        Some(path) => {
+            fs::read_to_string(path).with_context(|| format!("failed to read `{}`", path.display()))
+        }
+        None => Ok("{}".into()),
+    }
+}
+
+/// Prints the unknown keys of the options, if `unknownOptions` is `"warn"`.
+pub fn warn(warnings: &[String]) {
+    for warning in warnings {
+        eprintln!("warning: {}", warning);
+    }
+}
+
+/// Maps each input to its output, like `a.{suffix}.css` next to `a.css`, or
+/// `{out_dir}/a.css`.
+///
+/// Under `out_dir`, the inputs keep their paths relative to the current
+/// directory, or relative to their common directory if any of them is outside
+/// of it. Fails if two inputs have the same output, before anything is
+/// written.
+pub fn output_paths(
+    inputs: &[PathBuf],
+    out_dir: Option<&Path>,
+    suffix: &str,
+) -> anyhow::Result<HashMap<PathBuf, PathBuf>> {
+    let relative = |input: &Path| {
+        input.is_relative()
+            && input
+                .components()
+                .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+    };
+
+    let base = match out_dir {
+        Some(_) if !inputs.iter().all(|input| relative(input)) => {
+            let cwd = env::current_dir().context("failed to get the current directory")?;
+            let dirs = inputs
+                .iter()
+                .map(|input| normalize(&cwd.join(input)))
+                .map(|path| path.parent().map(Path::to_path_buf).unwrap_or_default())
+                .collect::<Vec<_>>();
+
+            Some((cwd, common_ancestor(&dirs)))
+        }
+        _ => None,
+    };
+
+    let mut outputs = HashMap::<PathBuf, PathBuf>::new();
+    let mut inputs_of = HashMap::<PathBuf, &Path>::new();
+
+    for input in inputs {
+        let output = match (out_dir, &base) {
+            (Some(out_dir), Some((cwd, base))) => {
+                let path = normalize(&cwd.join(input));
+
+                out_dir.join(path.strip_prefix(base).unwrap_or(&path))
+            }
+            (Some(out_dir), None) => out_dir.join(input),
+            (None, _) => {
+                let mut name = input.file_stem().unwrap_or_default().to_os_string();
+                name.push(".");
+                name.push(suffix);
+                if let Some(ext) = input.extension() {
+                    name.push(".");
+                    name.push(ext);
+                }
+
+                input.with_file_name(name)
+            }
+        };
+
+        if let Some(other) = inputs_of.insert(normalize(&output), input) {
+            bail!(
+                "`{}` and `{}` would both be written to `{}`",
+                other.display(),
+                input.display(),
+                output.display()
+            )
+        }
+
+        outputs.insert(input.clone(), output);
+    }
+
+    Ok(outputs)
+}
+
+/// Removes `.` and resolves `..` without reading the file system.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+
+    for c in path.components() {
+        match c {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            c => normalized.push(c),
+        }
+    }
+
+    normalized
+}
+
+fn common_ancestor(dirs: &[PathBuf]) -> PathBuf {
+    let mut common = dirs.first().cloned().unwrap_or_default();
+
+    for dir in dirs {
+        while !dir.starts_with(&common) {
+            if !common.pop() {
+                break;
+            }
+        }
+    }
+
+    common
+}
+
+/// Writes `code`, and `map` as `{path}.map`.
+pub fn write(path: &Path, code: &str, map: Option<&str>) -> anyhow::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("failed to create `{}`", dir.display()))?;
+    }
+
+    fs::write(path, code).with_context(|| format!("failed to write `{}`", path.display()))?;
+
+    if let Some(map) = map {
+        let mut map_path = path.as_os_str().to_os_string();
+        map_path.push(".map");
+
+        fs::write(&map_path, map)
+            .with_context(|| format!("failed to write `{}`", Path::new(&map_path).display()))?;
+    }
+
+    Ok(())
+}
+
+/// Processes `inputs` in parallel with `op`, which returns the diagnostics of
+/// a file, and prints them in the order of `inputs`.
+///
+/// Returns false if any file failed.
+pub fn run_all<F>(inputs: &[PathBuf], op: F) -> bool
+where
+    F: Fn(&Path, &[u8]) -> anyhow::Result<Vec<Message>> + Sync,
+{
+    let results = inputs
+        .par_iter()
+        .map(|input| {
+            let code =
+                fs::read(input).with_context(|| format!("failed to read `{}`", input.display()))?;
+
+            op(input, &code).with_context(|| format!("failed to process `{}`", input.display()))
+        })
+        .collect::<Vec<_>>();
+
+    let mut ok = true;
+
+    for result in results {
+        match result {
+            Ok(messages) => {
+                for message in messages {
+                    eprintln!("{}", message.text.trim_end());
+                    ok &= !message.fails;
+                }
+            }
+            Err(err) => {
+                eprintln!("error: {:?}", err);
+                ok = false;
+            }
+        }
+    }
+
+    ok
+}
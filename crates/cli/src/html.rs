@@ -0,0 +1,54 @@
+//! `html minify` and `html minify-fragment`.
+
+use std::path::Path;
+
+use anyhow::Context;
+use clap::Subcommand;
+use html_core::{parse_options, MinifyOptions, TransformOutput};
+
+use crate::files::{output_paths, read_config, run_all, warn, write, Args, Message};
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Minifies documents to `{name}.min.html`.
+    Minify(Args),
+    /// Minifies document fragments to `{name}.min.html`, parsed in the
+    /// `contextElement` of the options.
+    MinifyFragment(Args),
+}
+
+pub fn run(command: Command) -> anyhow::Result<bool> {
+    let (args, is_fragment) = match command {
+        Command::Minify(args) => (args, false),
+        Command::MinifyFragment(args) => (args, true),
+    };
+
+    let (opts, warnings): (MinifyOptions, _) = parse_options(&read_config(args.config.as_deref())?)
+        .context("failed to deserialize minifier options")?;
+    warn(&warnings);
+
+    let inputs = args.expand("min")?;
+    let outputs = output_paths(&inputs, args.out_dir.as_deref(), "min")?;
+
+    Ok(run_all(&inputs, |input, code| {
+        let filename = input.to_string_lossy();
+        let output = if is_fragment {
+            html_core::minify_fragment(code, Some(&filename), &opts)?
+        } else {
+            html_core::minify(code, Some(&filename), &opts)?
+        };
+
+        emit(input, &outputs[input], output)
+    }))
+}
+
+fn emit(input: &Path, path: &Path, output: TransformOutput) -> anyhow::Result<Vec<Message>> {
+    write(path, &output.code, None)?;
+
+    Ok(output
+        .errors
+        .unwrap_or_default()
+        .iter()
+        .map(|d| Message::new(input, &d.level, &d.message, d.code_frame.as_deref()).fails(d.fatal))
+        .collect())
+}
@@ -0,0 +1,42 @@
+//! `lint`, which fails if any rule reports an error.
+
+use std::path::PathBuf;
+
+use anyhow::Context;
+use linter_core::{parse_options, LintOptions};
+
+use crate::files::{expand, read_config, run_all, warn, Message};
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// Files or glob patterns, like `src/**/*.js`.
+    #[arg(required = true)]
+    inputs: Vec<String>,
+
+    /// A JSON file with the options.
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+}
+
+pub fn run(args: Args) -> anyhow::Result<bool> {
+    let (opts, warnings): (LintOptions, _) = parse_options(&read_config(args.config.as_deref())?)
+        .context("failed to deserialize linter options")?;
+    warn(&warnings);
+
+    let inputs = expand(&args.inputs, |_| false)?;
+
+    Ok(run_all(&inputs, |input, code| {
+        let filename = input.to_string_lossy();
+        let output = linter_core::lint(code, Some(&filename), &opts)?;
+
+        Ok(output
+            .errors
+            .unwrap_or_default()
+            .iter()
+            .map(|d| {
+                Message::new(input, &d.level, &d.message, d.code_frame.as_deref())
+                    .fails(d.level == "error")
+            })
+            .collect())
+    }))
+}
@@ -0,0 +1,51 @@
+//! `swc-extra`, which runs the tools of the Node.js packages on files.
+//!
+//! The options are read from a JSON file in the format of the packages. The
+//! exit code is `1` if any file failed, and `2` for invalid arguments or
+//! options.
+
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+
+mod css;
+mod files;
+mod html;
+mod lint;
+
+/// Minifies and transforms CSS and HTML, and lints JavaScript.
+#[derive(Parser)]
+#[command(name = "swc-extra", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Transforms or minifies stylesheets.
+    #[command(subcommand)]
+    Css(css::Command),
+    /// Minifies documents or fragments.
+    #[command(subcommand)]
+    Html(html::Command),
+    /// Lints JavaScript modules.
+    Lint(lint::Args),
+}
+
+fn main() -> ExitCode {
+    let result = match Cli::parse().command {
+        Command::Css(command) => css::run(command),
+        Command::Html(command) => html::run(command),
+        Command::Lint(args) => lint::run(args),
+    };
+
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("error: {:?}", err);
+            ExitCode::from(2)
+        }
+    }
+}
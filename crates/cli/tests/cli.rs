@@ -0,0 +1,166 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("swc-extra-cli-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn run(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_swc-extra"))
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn css_minify_writes_next_to_the_inputs() {
+    let dir = temp_dir("minify");
+    fs::write(dir.join("a.css"), "a { color: red }").unwrap();
+
+    let output = run(&dir, &["css", "minify", "a.css"]);
+
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(
+        fs::read_to_string(dir.join("a.min.css")).unwrap(),
+        "a{color:red}"
+    );
+}
+
+#[test]
+fn css_minify_skips_previous_outputs_of_globs() {
+    let dir = temp_dir("rerun");
+    fs::write(dir.join("a.css"), "a { color: red }").unwrap();
+
+    for _ in 0..2 {
+        let output = run(&dir, &["css", "minify", "*.css"]);
+        assert!(output.status.success(), "{:?}", output);
+    }
+
+    assert!(dir.join("a.min.css").exists());
+    assert!(!dir.join("a.min.min.css").exists());
+}
+
+#[test]
+fn css_transform_skips_the_out_dir() {
+    let dir = temp_dir("out-dir");
+    fs::create_dir(dir.join("src")).unwrap();
+    fs::write(dir.join("src/a.css"), "a { color: red }").unwrap();
+
+    for _ in 0..2 {
+        let output = run(
+            &dir,
+            &["css", "transform", "**/*.css", "--out-dir", "./dist"],
+        );
+        assert!(output.status.success(), "{:?}", output);
+    }
+
+    assert!(dir.join("dist/src/a.css").exists());
+    assert!(!dir.join("dist/dist").exists());
+}
+
+#[test]
+fn html_minify_skips_previous_outputs_of_globs() {
+    let dir = temp_dir("html");
+    fs::write(dir.join("a.html"), "<p>  a  </p>").unwrap();
+
+    for _ in 0..2 {
+        let output = run(&dir, &["html", "minify-fragment", "*.html"]);
+        assert!(output.status.success(), "{:?}", output);
+    }
+
+    assert!(dir.join("a.min.html").exists());
+    assert!(!dir.join("a.min.min.html").exists());
+}
+
+#[test]
+fn explicit_inputs_are_not_skipped() {
+    let dir = temp_dir("explicit");
+    fs::write(dir.join("a.min.css"), "a { color: red }").unwrap();
+
+    let output = run(&dir, &["css", "minify", "a.min.css"]);
+
+    assert!(output.status.success(), "{:?}", output);
+    assert!(dir.join("a.min.min.css").exists());
+}
+
+#[test]
+fn fatal_errors_fail_with_1() {
+    let dir = temp_dir("fatal");
+    fs::write(dir.join("a.css"), "a { color: red").unwrap();
+    fs::write(dir.join("config.json"), r#"{ "strict": true }"#).unwrap();
+
+    let output = run(&dir, &["css", "minify", "a.css", "--config", "config.json"]);
+
+    assert_eq!(output.status.code(), Some(1), "{:?}", output);
+}
+
+#[test]
+fn invalid_options_fail_with_2() {
+    let dir = temp_dir("options");
+    fs::write(dir.join("a.css"), "a {}").unwrap();
+    fs::write(dir.join("config.json"), r#"{ "minfy": true }"#).unwrap();
+
+    let output = run(
+        &dir,
+        &["css", "transform", "a.css", "--config", "config.json"],
+    );
+
+    assert_eq!(output.status.code(), Some(2), "{:?}", output);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("minfy"));
+}
+
+#[test]
+fn globs_matching_nothing_fail() {
+    let dir = temp_dir("nothing");
+
+    let output = run(&dir, &["lint", "*.js"]);
+
+    assert_eq!(output.status.code(), Some(2), "{:?}", output);
+}
+
+#[test]
+fn out_dir_keeps_inputs_with_the_same_name_apart() {
+    let dir = temp_dir("same-name");
+    for sub in ["a", "b"] {
+        fs::create_dir_all(dir.join("x").join(sub)).unwrap();
+        fs::write(dir.join("x").join(sub).join("x.css"), "a { color: red }").unwrap();
+    }
+
+    let output = run(
+        &dir.join("x/a"),
+        &[
+            "css",
+            "minify",
+            "x.css",
+            "../b/x.css",
+            "--out-dir",
+            "../dist",
+        ],
+    );
+
+    assert!(output.status.success(), "{:?}", output);
+    assert!(dir.join("x/dist/a/x.css").exists());
+    assert!(dir.join("x/dist/b/x.css").exists());
+}
+
+#[test]
+fn inputs_with_the_same_output_fail_before_writing() {
+    let dir = temp_dir("same-output");
+    fs::write(dir.join("a.css"), "a { color: red }").unwrap();
+
+    let output = run(
+        &dir,
+        &["css", "minify", "a.css", "./a.css", "--out-dir", "dist"],
+    );
+
+    assert_eq!(output.status.code(), Some(2), "{:?}", output);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("would both be written"));
+    assert!(!dir.join("dist").exists());
+}
@@ -0,0 +1,278 @@
+use std::{fmt, iter::Peekable, vec};
+
+use anyhow::{bail, Context};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use swc_common::{
+    comments::{Comment, CommentKind, SingleThreadedComments},
+    BytePos, LineCol, Span, Spanned,
+};
+use swc_css_ast::Stylesheet;
+use swc_css_codegen::{
+    writer::{
+        basic::{BasicCssWriter, BasicCssWriterConfig, IndentType, LineFeed},
+        CssWriter,
+    },
+    CodeGenerator, CodegenConfig, Emit,
+};
+
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[schemars(deny_unknown_fields)]
+pub struct CodegenOptions {
+    /// Defaults to `space`.
+    #[serde(default)]
+    pub indent_type: Option<IndentKind>,
+
+    /// Between 0 and 16. Defaults to 2 for `space` and 1 for `tab`.
+    #[serde(default)]
+    #[schemars(range(min = 0, max = 16))]
+    pub indent_width: Option<i32>,
+
+    /// Defaults to `lf`.
+    #[serde(default)]
+    pub line_feed: Option<LineFeedKind>,
+
+    /// Keep comments, including the ones nested in rules and values. A comment
+    /// is printed before the token following it.
+    #[serde(default)]
+    pub preserve_comments: bool,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum IndentKind {
+    Tab,
+    Space,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum LineFeedKind {
+    Lf,
+    Crlf,
+}
+
+/// The largest `indentWidth`.
+const MAX_INDENT_WIDTH: i32 = 16;
+
+impl CodegenOptions {
+    fn writer_config(&self, minify: bool) -> anyhow::Result<BasicCssWriterConfig> {
+        if let Some(width) = self.indent_width {
+            if !(0..=MAX_INDENT_WIDTH).contains(&width) {
+                bail!(
+                    "`indentWidth` must be between 0 and {}, but it is {}",
+                    MAX_INDENT_WIDTH,
+                    width
+                )
+            }
+        }
+
+        if minify {
+            return Ok(BasicCssWriterConfig {
+                indent_type: IndentType::Space,
+                indent_width: 0,
+                linefeed: LineFeed::LF,
+            });
+        }
+
+        let default = BasicCssWriterConfig::default();
+
+        Ok(BasicCssWriterConfig {
+            indent_type: match self.indent_type {
+                Some(IndentKind::Tab) => IndentType::Tab,
+                Some(IndentKind::Space) => IndentType::Space,
+                None => default.indent_type,
+            },
+            indent_width: match (self.indent_width, self.indent_type) {
+                (Some(width), _) => width,
+                // A tab is a single indentation unit.
+                (None, Some(IndentKind::Tab)) => 1,
+                (None, _) => default.indent_width,
+            },
+            linefeed: match self.line_feed {
+                Some(LineFeedKind::Lf) => LineFeed::LF,
+                Some(LineFeedKind::Crlf) => LineFeed::CRLF,
+                None => default.linefeed,
+            },
+        })
+    }
+}
+
+/// Prints `ss`, with the comments collected by the parser if `comments` is
+/// given.
+pub fn print_stylesheet(
+    ss: &Stylesheet,
+    minify: bool,
+    opts: &CodegenOptions,
+    comments: Option<&SingleThreadedComments>,
+    src_map: Option<&mut Vec<(BytePos, LineCol)>>,
+) -> anyhow::Result<String> {
+    let mut buf = String::new();
+
+    {
+        let wr = BasicCssWriter::new(&mut buf, src_map, opts.writer_config(minify)?);
+
+        match comments {
+            Some(comments) => {
+                let mut wr = CommentWriter {
+                    inner: wr,
+                    comments: sorted_comments(comments).into_iter().peekable(),
+                    minify,
+                    at_line_start: true,
+                };
+
+                let mut gen = CodeGenerator::new(&mut wr, CodegenConfig { minify });
+
+                gen.emit(ss).context("failed to emit")?;
+
+                wr.finish().context("failed to emit")?;
+            }
+            None => {
+                let mut gen = CodeGenerator::new(wr, CodegenConfig { minify });
+
+                gen.emit(ss).context("failed to emit")?;
+            }
+        }
+    }
+
+    Ok(buf)
+}
+
+/// Writes each comment before the first token following it, so the comments
+/// nested in rules and values are kept too.
+///
+/// A comment starting a line is followed by a line feed, and others by a
+/// space, unless minifying.
+struct CommentWriter<W> {
+    inner: W,
+    comments: Peekable<vec::IntoIter<Comment>>,
+    minify: bool,
+    at_line_start: bool,
+}
+
+impl<W> CommentWriter<W>
+where
+    W: CssWriter,
+{
+    fn write_comments_before(&mut self, span: Option<Span>) -> fmt::Result {
+        let lo = match span {
+            Some(span) if !span.is_dummy() => span.lo,
+            _ => return Ok(()),
+        };
+
+        while let Some(comment) = self.comments.next_if(|c| c.span.hi <= lo) {
+            self.inner
+                .write_raw(Some(comment.span), &comment_text(&comment))?;
+
+            if !self.minify {
+                if self.at_line_start {
+                    self.inner.write_newline()?;
+                } else {
+                    self.inner.write_space()?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes the comments after the last token, each on its own line.
+    fn finish(mut self) -> fmt::Result {
+        for comment in self.comments {
+            if !self.minify && !self.at_line_start {
+                self.inner.write_newline()?;
+            }
+
+            self.inner
+                .write_raw(Some(comment.span), &comment_text(&comment))?;
+            self.at_line_start = false;
+        }
+
+        Ok(())
+    }
+}
+
+impl<W> CssWriter for CommentWriter<W>
+where
+    W: CssWriter,
+{
+    fn write_space(&mut self) -> fmt::Result {
+        self.inner.write_space()
+    }
+
+    fn write_newline(&mut self) -> fmt::Result {
+        self.at_line_start = true;
+        self.inner.write_newline()
+    }
+
+    fn write_raw(&mut self, span: Option<Span>, text: &str) -> fmt::Result {
+        self.write_comments_before(span)?;
+        self.at_line_start = false;
+        self.inner.write_raw(span, text)
+    }
+
+    fn write_str(&mut self, span: Span, s: &str) -> fmt::Result {
+        self.write_comments_before(Some(span))?;
+        self.at_line_start = false;
+        self.inner.write_str(span, s)
+    }
+
+    fn increase_indent(&mut self) {
+        self.inner.increase_indent()
+    }
+
+    fn decrease_indent(&mut self) {
+        self.inner.decrease_indent()
+    }
+}
+
+/// Prints `n` in the minified form.
+pub(crate) fn print_node<N>(n: N) -> String
+where
+    N: Spanned,
+    for<'a> CodeGenerator<BasicCssWriter<'a, &'a mut String>>: Emit<N>,
+{
+    let mut buf = String::new();
+    let wr = BasicCssWriter::new(
+        &mut buf,
+        None,
+        BasicCssWriterConfig {
+            indent_type: IndentType::Space,
+            indent_width: 0,
+            linefeed: LineFeed::LF,
+        },
+    );
+    let mut gen = CodeGenerator::new(wr, CodegenConfig { minify: true });
+
+    gen.emit(&n).expect("failed to print node");
+
+    buf
+}
+
+fn sorted_comments(comments: &SingleThreadedComments) -> Vec<Comment> {
+    let (leading, trailing) = comments.borrow_all();
+
+    let mut v = leading
+        .values()
+        .chain(trailing.values())
+        .flatten()
+        .cloned()
+        .collect::<Vec<_>>();
+
+    // A comment may be attached both as a trailing comment of one token and as
+    // a leading comment of the next one.
+    v.sort_by_key(|c| c.span.lo);
+    v.dedup_by_key(|c| c.span.lo);
+
+    v
+}
+
+/// The parser keeps the delimiters in the text of block comments, but not in
+/// the one of line comments, which are printed as block comments.
+fn comment_text(comment: &Comment) -> String {
+    match comment.kind {
+        CommentKind::Block => comment.text.to_string(),
+        CommentKind::Line => format!("/*{}*/", comment.text.trim_end()),
+    }
+}
@@ -0,0 +1,407 @@
+use schemars::JsonSchema;
+use serde::Deserialize;
+use swc_common::{collections::AHashMap, sync::Lrc, FileName, SourceMap, Span};
+use swc_css_ast::{
+    ComplexSelectorChildren, ComponentValue, Declaration, DeclarationName, QualifiedRule,
+    QualifiedRulePrelude, Rule, SimpleBlock, Stylesheet, SubclassSelector,
+};
+use swc_css_visit::{VisitMut, VisitMutWith};
+
+use crate::{abort::Ticker, codegen::print_node};
+
+/// Resolved values longer than this are rejected, as each `var()` may repeat a
+/// value which is itself repeated.
+const MAX_RESOLVED_BYTES: usize = 64 * 1024;
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[schemars(deny_unknown_fields)]
+pub struct CustomPropertiesOptions {
+    /// Keep the declarations using `var()` after the static fallback. If
+    /// false, they are replaced. Defaults to true.
+    #[serde(default = "crate::true_by_default")]
+    pub preserve: bool,
+}
+
+/// Computes the values of custom properties declared on `:root` and adds a
+/// static fallback for each declaration using them.
+///
+/// Returns warnings for the usages which could not be resolved.
+pub fn resolve_custom_properties(
+    ss: &mut Stylesheet,
+    cm: &Lrc<SourceMap>,
+    opts: &CustomPropertiesOptions,
+) -> Vec<(Span, String)> {
+    let mut vars = AHashMap::default();
+
+    for rule in &ss.rules {
+        if let Rule::QualifiedRule(rule) = rule {
+            if is_root(rule) {
+                for value in &rule.block.value {
+                    if let ComponentValue::Declaration(decl) = value {
+                        if let DeclarationName::DashedIdent(name) = &decl.name {
+                            let name = name.value.trim_start_matches("--").to_string();
+
+                            if let Some(value) = declaration_value(decl) {
+                                vars.insert(name, value);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let mut v = StaticFallback {
+        cm,
+        preserve: opts.preserve,
+        resolver: Resolver {
+            vars: &vars,
+            resolved: Default::default(),
+            cycles: 0,
+            messages: vec![],
+        },
+        warnings: vec![],
+        ticker: Ticker::default(),
+    };
+
+    ss.visit_mut_with(&mut v);
+
+    v.warnings
+}
+
+fn is_root(rule: &QualifiedRule) -> bool {
+    let list = match &rule.prelude {
+        QualifiedRulePrelude::SelectorList(list) => list,
+        _ => return false,
+    };
+
+    list.children.len() == 1
+        && matches!(
+            &*list.children[0].children,
+            [ComplexSelectorChildren::CompoundSelector(compound)]
+                if compound.type_selector.is_none()
+                    && matches!(
+                        &*compound.subclass_selectors,
+                        [SubclassSelector::PseudoClass(pseudo)]
+                            if pseudo.name.value.eq_ignore_ascii_case("root")
+                                && pseudo.children.is_none()
+                    )
+        )
+}
+
+/// Returns the text after the colon, without `!important`.
+fn declaration_value(decl: &Declaration) -> Option<String> {
+    let text = print_node(decl);
+    let (_, value) = text.split_once(':')?;
+
+    let value = if decl.important.is_some() {
+        &value[..value.rfind('!')?]
+    } else {
+        value
+    };
+
+    Some(value.trim().to_string())
+}
+
+struct StaticFallback<'a> {
+    cm: &'a Lrc<SourceMap>,
+    preserve: bool,
+    resolver: Resolver<'a>,
+    warnings: Vec<(Span, String)>,
+    ticker: Ticker,
+}
+
+impl StaticFallback<'_> {
+    fn resolve(&mut self, decl: &Declaration) -> Option<Declaration> {
+        if !matches!(decl.name, DeclarationName::Ident(..)) {
+            return None;
+        }
+
+        let text = print_node(decl);
+        if !text.to_ascii_lowercase().contains("var(") {
+            return None;
+        }
+
+        let resolved = self.resolver.substitute(&text, &mut vec![]);
+
+        self.warnings.extend(
+            self.resolver
+                .messages
+                .drain(..)
+                .map(|message| (decl.span, message)),
+        );
+
+        match self.parse_declaration(&resolved.ok()?, decl.span) {
+            Some(resolved) => Some(resolved),
+            None => {
+                self.warnings.push((
+                    decl.span,
+                    "failed to parse the statically resolved value of custom properties"
+                        .to_string(),
+                ));
+                None
+            }
+        }
+    }
+
+    fn parse_declaration(&self, text: &str, span: Span) -> Option<Declaration> {
+        let fm = self.cm.new_source_file(
+            FileName::Custom("custom-properties".into()),
+            format!("a{{{}}}", text),
+        );
+
+        let mut errors = vec![];
+        let ss =
+            swc_css_parser::parse_file::<Stylesheet>(&fm, None, Default::default(), &mut errors)
+                .ok()?;
+
+        if !errors.is_empty() {
+            return None;
+        }
+
+        let mut decl = match ss.rules.into_iter().next()? {
+            Rule::QualifiedRule(rule) => {
+                rule.block.value.into_iter().find_map(|value| match value {
+                    ComponentValue::Declaration(decl) => Some(*decl),
+                    _ => None,
+                })?
+            }
+            _ => return None,
+        };
+
+        decl.visit_mut_with(&mut SpanReplacer(span));
+
+        Some(decl)
+    }
+}
+
+impl VisitMut for StaticFallback<'_> {
+    fn visit_mut_simple_block(&mut self, n: &mut SimpleBlock) {
+        if !self.ticker.tick() {
+            return;
+        }
+
+        n.visit_mut_children_with(self);
+
+        let mut value = Vec::with_capacity(n.value.len());
+
+        for item in n.value.drain(..) {
+            if let ComponentValue::Declaration(decl) = &item {
+                if let Some(resolved) = self.resolve(decl) {
+                    value.push(ComponentValue::Declaration(Box::new(resolved)));
+
+                    if !self.preserve {
+                        continue;
+                    }
+                }
+            }
+
+            value.push(item);
+        }
+
+        n.value = value;
+    }
+}
+
+struct SpanReplacer(Span);
+
+impl VisitMut for SpanReplacer {
+    fn visit_mut_span(&mut self, span: &mut Span) {
+        *span = self.0;
+    }
+}
+
+struct Resolver<'a> {
+    /// Raw values of custom properties, keyed by the name without `--`.
+    vars: &'a AHashMap<String, String>,
+    /// The resolved values of custom properties, or the messages explaining
+    /// why they could not be resolved.
+    resolved: AHashMap<String, Result<String, Vec<String>>>,
+    /// How many cycles were found.
+    cycles: usize,
+    messages: Vec<String>,
+}
+
+impl Resolver<'_> {
+    /// Replaces every `var()` in `text`. `stack` contains the custom properties
+    /// being resolved, to detect cycles.
+    fn substitute(&mut self, text: &str, stack: &mut Vec<String>) -> Result<String, ()> {
+        let mut buf = String::with_capacity(text.len());
+        let mut rest = text;
+
+        while let Some(start) = find_var_call(rest) {
+            buf.push_str(&rest[..start]);
+
+            let args_start = start + "var(".len();
+            let end = match find_top_level(&rest[args_start..], b')') {
+                Some(end) => args_start + end,
+                None => {
+                    // Unbalanced parens. The parser would have reported it.
+                    buf.push_str(&rest[start..]);
+                    return Ok(buf);
+                }
+            };
+
+            let args = &rest[args_start..end];
+            let (name, fallback) = match find_top_level(args, b',') {
+                Some(comma) => (&args[..comma], Some(&args[comma + 1..])),
+                None => (args, None),
+            };
+
+            buf.push_str(&self.resolve_var(name.trim(), fallback, stack)?);
+
+            if buf.len() > MAX_RESOLVED_BYTES {
+                self.messages.push(format!(
+                    "the resolved value of custom properties is longer than {} bytes",
+                    MAX_RESOLVED_BYTES
+                ));
+                return Err(());
+            }
+
+            rest = &rest[end + 1..];
+        }
+
+        buf.push_str(rest);
+
+        Ok(buf)
+    }
+
+    fn resolve_var(
+        &mut self,
+        name: &str,
+        fallback: Option<&str>,
+        stack: &mut Vec<String>,
+    ) -> Result<String, ()> {
+        let name = name.trim_start_matches("--");
+        let vars = self.vars;
+
+        let resolved = match vars.get(name) {
+            Some(..) if stack.iter().any(|n| n == name) => {
+                self.cycles += 1;
+                self.messages.push(format!(
+                    "custom property `--{}` has a cyclic reference: --{} -> --{}",
+                    name,
+                    stack.join(" -> --"),
+                    name
+                ));
+                Err(())
+            }
+            Some(value) => self.resolve_defined(name, value, stack),
+            None if fallback.is_some() => Err(()),
+            None => {
+                self.messages
+                    .push(format!("custom property `--{}` is not defined", name));
+                Err(())
+            }
+        };
+
+        match (resolved, fallback) {
+            (Ok(v), _) => Ok(v),
+            (Err(()), Some(fallback)) => self.substitute(fallback.trim(), stack),
+            (Err(()), None) => Err(()),
+        }
+    }
+
+    /// Resolves the custom property `name`, whose raw value is `value`.
+    ///
+    /// The result is reused for the other usages, unless a cycle through
+    /// `stack` was found, as it would depend on `stack`.
+    fn resolve_defined(
+        &mut self,
+        name: &str,
+        value: &str,
+        stack: &mut Vec<String>,
+    ) -> Result<String, ()> {
+        match self.resolved.get(name) {
+            Some(Ok(resolved)) => return Ok(resolved.clone()),
+            Some(Err(messages)) => {
+                self.messages.extend(messages.iter().cloned());
+                return Err(());
+            }
+            None => {}
+        }
+
+        let cycles = self.cycles;
+        let messages = self.messages.len();
+
+        stack.push(name.to_string());
+        let resolved = self.substitute(value, stack);
+        stack.pop();
+
+        if self.cycles == cycles {
+            self.resolved.insert(
+                name.to_string(),
+                resolved
+                    .clone()
+                    .map_err(|()| self.messages[messages..].to_vec()),
+            );
+        }
+
+        resolved
+    }
+}
+
+/// Returns the byte offset of the first `var(` outside of strings.
+fn find_var_call(s: &str) -> Option<usize> {
+    let bytes = s.as_bytes();
+    let mut quote = None;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let b = bytes[i];
+
+        match quote {
+            Some(..) if b == b'\\' => i += 1,
+            Some(q) if b == q => quote = None,
+            Some(..) => {}
+            None if b == b'"' || b == b'\'' => quote = Some(b),
+            None => {
+                if bytes[i..].len() >= 4
+                    && bytes[i..i + 4].eq_ignore_ascii_case(b"var(")
+                    && (i == 0 || !is_ident_byte(bytes[i - 1]))
+                {
+                    return Some(i);
+                }
+            }
+        }
+
+        i += 1;
+    }
+
+    None
+}
+
+/// Returns the byte offset of the first `target` which is not nested in
+/// parens or strings.
+fn find_top_level(s: &str, target: u8) -> Option<usize> {
+    let bytes = s.as_bytes();
+    let mut quote = None;
+    let mut depth = 0usize;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let b = bytes[i];
+
+        match quote {
+            Some(..) if b == b'\\' => i += 1,
+            Some(q) if b == q => quote = None,
+            Some(..) => {}
+            None => match b {
+                _ if b == target && depth == 0 => return Some(i),
+                b'"' | b'\'' => quote = Some(b),
+                b'(' => depth += 1,
+                b')' => depth = depth.saturating_sub(1),
+                _ => {}
+            },
+        }
+
+        i += 1;
+    }
+
+    None
+}
+
+fn is_ident_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'-' || b == b'_' || b >= 0x80
+}
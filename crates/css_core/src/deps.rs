@@ -1,13 +1,19 @@
 use serde::Serialize;
 use swc_atoms::JsWord;
-use swc_common::Spanned;
-use swc_css_ast::{ImportHref, ImportPrelude, Url, UrlValue};
-use swc_css_codegen::{
-    writer::basic::{BasicCssWriter, BasicCssWriterConfig, IndentType, LineFeed},
-    CodeGenerator, CodegenConfig, Emit,
-};
+use swc_css_ast::{ImportHref, ImportPrelude, Stylesheet, Url, UrlValue};
 use swc_css_visit::{Visit, VisitWith};
 
+use crate::codegen::print_node;
+
+/// Returns the URLs of `@import` and `url()` in `ss`.
+pub fn analyze(ss: &Stylesheet) -> Dependencies {
+    let mut v = Analyzer::default();
+
+    ss.visit_with(&mut v);
+
+    v.deps
+}
+
 #[derive(Default)]
 pub struct Analyzer {
     pub deps: Dependencies,
@@ -84,26 +90,3 @@ fn normalize_url(n: &Url) -> Option<CssUrl> {
 fn parse_url(s: &JsWord) -> CssUrl {
     CssUrl { value: s.clone() }
 }
-
-fn print_node<N>(n: N) -> String
-where
-    N: Spanned,
-    for<'a> CodeGenerator<BasicCssWriter<'a, &'a mut String>>: Emit<N>,
-{
-    let mut buf = String::new();
-    let wr = BasicCssWriter::new(
-        &mut buf,
-        None,
-        BasicCssWriterConfig {
-            indent_type: IndentType::Space,
-            indent_width: 0,
-            linefeed: LineFeed::LF,
-        },
-    );
-    let mut gen = CodeGenerator::new(wr, CodegenConfig { minify: true });
-
-    gen.emit(&n)
-        .expect("failed to print node for dependency analysis");
-
-    buf
-}
@@ -0,0 +1,132 @@
+//! Decoding of the input and escaping of the output, for stylesheets which
+//! are not UTF-8.
+
+use std::{borrow::Cow, fmt::Write};
+
+use anyhow::{bail, Context};
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8};
+
+use crate::diagnostic::Diagnostic;
+
+/// The input decoded to UTF-8.
+pub struct Decoded<'a> {
+    pub code: Cow<'a, str>,
+    encoding: &'static Encoding,
+    /// True if invalid bytes were replaced with U+FFFD.
+    lossy: bool,
+}
+
+impl Decoded<'_> {
+    /// Returns a warning if invalid bytes were replaced.
+    pub fn diagnostic(&self) -> Option<Diagnostic> {
+        if !self.lossy {
+            return None;
+        }
+
+        Some(Diagnostic {
+            level: "warning".into(),
+            message: format!(
+                "the input contains bytes which are invalid in {} and were replaced with U+FFFD",
+                self.encoding.name()
+            ),
+            code: Some("lossy-decoding".into()),
+            span: None,
+            code_frame: None,
+            fatal: false,
+        })
+    }
+}
+
+/// Decodes `bytes` with the encoding of, in order of precedence, the byte
+/// order mark, `label`, the `@charset` rule and UTF-8.
+pub fn decode<'a>(bytes: &'a [u8], label: Option<&str>) -> anyhow::Result<Decoded<'a>> {
+    let (encoding, bom_len) = match Encoding::for_bom(bytes) {
+        Some(v) => v,
+        None => {
+            let encoding = match label {
+                Some(label) => for_label(label)?,
+                None => charset_rule(bytes).unwrap_or(UTF_8),
+            };
+
+            (encoding, 0)
+        }
+    };
+
+    let (code, lossy) = encoding.decode_without_bom_handling(&bytes[bom_len..]);
+
+    Ok(Decoded {
+        code,
+        encoding,
+        lossy,
+    })
+}
+
+/// Escapes the characters of `code` which cannot be encoded with
+/// `output_encoding`, or all non-ASCII characters if `ascii_only`.
+pub fn escape_output(
+    code: String,
+    output_encoding: Option<&str>,
+    ascii_only: bool,
+) -> anyhow::Result<String> {
+    let encoding = match output_encoding {
+        Some(label) => for_label(label)?.output_encoding(),
+        None => UTF_8,
+    };
+
+    if code.is_ascii() || (!ascii_only && encoding == UTF_8) {
+        return Ok(code);
+    }
+
+    let mut buf = String::with_capacity(code.len());
+
+    for c in code.chars() {
+        if c.is_ascii() || (!ascii_only && can_encode(encoding, c)) {
+            buf.push(c);
+        } else {
+            // The space ends the escape, and is consumed by it.
+            write!(buf, "\\{:x} ", c as u32).unwrap();
+        }
+    }
+
+    Ok(buf)
+}
+
+/// Rejects the output options which would make `map` wrong.
+pub fn check_source_map(
+    source_map: bool,
+    output_encoding: Option<&str>,
+    ascii_only: bool,
+) -> anyhow::Result<()> {
+    if source_map && (ascii_only || output_encoding.is_some()) {
+        bail!("`asciiOnly` and `outputEncoding` cannot be used with `sourceMap`")
+    }
+
+    Ok(())
+}
+
+fn for_label(label: &str) -> anyhow::Result<&'static Encoding> {
+    Encoding::for_label(label.as_bytes()).with_context(|| format!("unknown encoding `{}`", label))
+}
+
+fn can_encode(encoding: &'static Encoding, c: char) -> bool {
+    let mut buf = [0; 4];
+    let (_, _, had_errors) = encoding.encode(c.encode_utf8(&mut buf));
+
+    !had_errors
+}
+
+/// Returns the encoding of the `@charset` rule at the start of `bytes`.
+///
+/// See https://www.w3.org/TR/css-syntax-3/#determine-the-fallback-encoding
+fn charset_rule(bytes: &[u8]) -> Option<&'static Encoding> {
+    const PREFIX: &[u8] = b"@charset \"";
+
+    let bytes = &bytes[..bytes.len().min(1024)];
+    let rest = bytes.strip_prefix(PREFIX)?;
+    let end = rest.windows(2).position(|w| w == b"\";")?;
+
+    match Encoding::for_label(&rest[..end])? {
+        e if e == UTF_16BE || e == UTF_16LE => Some(UTF_8),
+        e => Some(e),
+    }
+}
@@ -0,0 +1,17 @@
+//! The optional parts, which can be left out with cargo features. Their
+//! options fail if they are not compiled in.
+
+use anyhow::bail;
+
+/// Fails if `option` is used but `feature` is not compiled in.
+pub fn check(used: bool, compiled: bool, option: &str, feature: &str) -> anyhow::Result<()> {
+    if used && !compiled {
+        bail!(
+            "`{}` is not supported, as the `{}` feature is not compiled in",
+            option,
+            feature
+        )
+    }
+
+    Ok(())
+}
@@ -0,0 +1,1199 @@
+//! The CSS tools of `@swc/css`, without the bindings for Node.js.
+//!
+//! Each function takes the input as bytes, which are decoded with the
+//! `encoding` of the options, and returns the output with the recoverable
+//! errors. Options can be deserialized from JSON with [parse_options].
+
+#[cfg(feature = "modules")]
+use std::{collections::HashMap, fmt::Write};
+use std::{iter::once, sync::Arc};
+
+use anyhow::{anyhow, bail, Context};
+/// An aborted call stops while parsing, between the phases, while walking
+/// the tree or between lint rules.
+pub use extra_common::abort;
+use extra_common::util::try_with;
+pub use extra_common::{
+    diagnostic,
+    options::{parse_options, parse_options_value},
+    panic,
+};
+use preset_env_base::query::{targets_to_versions, Targets};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use swc_atoms::JsWord;
+use swc_common::{
+    comments::SingleThreadedComments,
+    errors::{Diagnostic as SwcDiagnostic, Handler, Level},
+    input::StringInput,
+    sync::Lrc,
+    BytePos, FileName, LineCol, SourceFile, SourceMap,
+};
+use swc_css_ast::Stylesheet;
+use swc_css_codegen::{
+    writer::basic::{BasicCssWriter, BasicCssWriterConfig, IndentType, LineFeed},
+    CodeGenerator, CodegenConfig, Emit,
+};
+#[cfg(feature = "compat")]
+use swc_css_compat::compiler::{Compiler, Config};
+use swc_css_parser::{
+    lexer::Lexer,
+    parser::{Parser, ParserConfig},
+};
+use swc_css_prefixer::options::Options as PrefixerOptions;
+use swc_css_visit::VisitMutWith;
+use tracing::{info_span, instrument};
+
+use crate::{
+    codegen::{print_stylesheet, CodegenOptions, IndentKind, LineFeedKind},
+    custom_properties::{resolve_custom_properties, CustomPropertiesOptions},
+    diagnostic::{to_diagnostic, Diagnostic},
+    encoding::{check_source_map, decode, escape_output, Decoded},
+    limits::{Checked, Limiter, Limits},
+    lint::lint_stylesheet,
+    prefix::PrefixRemover,
+    purge::{purge, PurgeOptions},
+    rewrite_url::{rewrite_urls, UrlRewriter},
+    stats::{Phase, Stats, StatsRecorder},
+};
+
+pub mod codegen;
+pub mod custom_properties;
+pub mod deps;
+mod encoding;
+mod features;
+pub mod limits;
+mod lint;
+mod prefix;
+pub mod purge;
+pub mod rewrite_url;
+pub mod schema;
+pub mod stats;
+#[cfg(feature = "compat")]
+mod targets;
+
+#[cfg_attr(
+    all(feature = "napi", not(feature = "namespace")),
+    napi_derive::napi(object)
+)]
+#[cfg_attr(
+    all(feature = "napi", feature = "namespace"),
+    napi_derive::napi(object, namespace = "css")
+)]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransformOutput {
+    /// The input as-is if it could not be parsed with `recover`.
+    pub code: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub map: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errors: Option<Vec<Diagnostic>>,
+    /// Unknown keys of the options, if `unknownOptions` is `"warn"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warnings: Option<Vec<String>>,
+
+    /// JSON string.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deps: Option<String>,
+
+    /// JSON string.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modules_mapping: Option<String>,
+
+    /// If `stats` is requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stats: Option<Stats>,
+}
+
+#[cfg_attr(
+    all(feature = "napi", not(feature = "namespace")),
+    napi_derive::napi(object)
+)]
+#[cfg_attr(
+    all(feature = "napi", feature = "namespace"),
+    napi_derive::napi(object, namespace = "css")
+)]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintOutput {
+    pub diagnostics: Vec<Diagnostic>,
+    /// Recoverable errors of the parser.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errors: Option<Vec<Diagnostic>>,
+    /// Unknown keys of the options, if `unknownOptions` is `"warn"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warnings: Option<Vec<String>>,
+}
+
+impl TransformOutput {
+    /// Reports the unknown keys of the options, returned by [parse_options].
+    pub fn with_warnings(mut self, warnings: &[String]) -> Self {
+        if !warnings.is_empty() {
+            self.warnings = Some(warnings.to_vec());
+        }
+        self
+    }
+
+    /// Reports the lossy decoding of `input`, and escapes `code` for the
+    /// output encoding.
+    fn encoded(
+        mut self,
+        input: &Decoded,
+        output_encoding: Option<&str>,
+        ascii_only: bool,
+    ) -> anyhow::Result<Self> {
+        if let Some(d) = input.diagnostic() {
+            self.errors.get_or_insert_with(Vec::new).push(d);
+        }
+        self.code = escape_output(self.code, output_encoding, ascii_only)?;
+        if let Some(stats) = &mut self.stats {
+            stats.output_size = self.code.len() as u32;
+        }
+        Ok(self)
+    }
+}
+
+impl LintOutput {
+    /// Reports the unknown keys of the options, returned by [parse_options].
+    pub fn with_warnings(mut self, warnings: &[String]) -> Self {
+        if !warnings.is_empty() {
+            self.warnings = Some(warnings.to_vec());
+        }
+        self
+    }
+
+    /// Reports the lossy decoding of `input`.
+    fn decoded(mut self, input: &Decoded) -> Self {
+        if let Some(d) = input.diagnostic() {
+            self.errors.get_or_insert_with(Vec::new).push(d);
+        }
+        self
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[schemars(deny_unknown_fields)]
+pub struct MinifyOptions {
+    #[serde(default)]
+    pub filename: Option<String>,
+
+    /// The encoding of the input, if it has no byte order mark, like
+    /// `windows-1252`. Defaults to the one of `@charset`, or UTF-8. Invalid
+    /// bytes are replaced and reported as a `lossy-decoding` warning in
+    /// `errors`.
+    #[serde(default)]
+    pub encoding: Option<String>,
+
+    /// Characters which cannot be encoded with this encoding are escaped. The
+    /// output is still returned as a string. Cannot be used with `sourceMap`.
+    #[serde(default)]
+    pub output_encoding: Option<String>,
+
+    /// Escape all non-ASCII characters. Cannot be used with `sourceMap`.
+    #[serde(default)]
+    pub ascii_only: bool,
+
+    #[serde(default)]
+    pub source_map: bool,
+
+    /// Return `stats` in the output, with the size of the input and the output,
+    /// the duration of each phase and the number of nodes before and after.
+    #[serde(default)]
+    pub stats: bool,
+
+    #[serde(default)]
+    pub purge: Option<PurgeOptions>,
+
+    /// Return the input as-is with the errors instead of throwing, if the input
+    /// cannot be parsed. The recoverable errors are limited by `maxErrors`.
+    #[serde(default)]
+    pub recover: bool,
+
+    /// Fail if the parser reports any recoverable error.
+    #[serde(default)]
+    pub strict: bool,
+
+    /// The maximum number of recoverable errors to report.
+    #[serde(default)]
+    pub max_errors: Option<usize>,
+
+    /// Return `codeFrame` in the diagnostics. Defaults to true, but rendering
+    /// them is slow for many diagnostics.
+    #[serde(default = "true_by_default")]
+    pub code_frame: bool,
+
+    /// Fail on untrusted input which is too large or too deep.
+    #[serde(default)]
+    pub limits: Limits,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[schemars(deny_unknown_fields)]
+pub struct TransformOptions {
+    #[serde(default)]
+    pub filename: Option<String>,
+
+    /// The encoding of the input, if it has no byte order mark, like
+    /// `windows-1252`. Defaults to the one of `@charset`, or UTF-8. Invalid
+    /// bytes are replaced and reported as a `lossy-decoding` warning in
+    /// `errors`.
+    #[serde(default)]
+    pub encoding: Option<String>,
+
+    /// Characters which cannot be encoded with this encoding are escaped. The
+    /// output is still returned as a string. Cannot be used with `sourceMap`.
+    #[serde(default)]
+    pub output_encoding: Option<String>,
+
+    /// Escape all non-ASCII characters. Cannot be used with `sourceMap`.
+    #[serde(default)]
+    pub ascii_only: bool,
+
+    #[serde(default)]
+    pub source_map: bool,
+
+    /// Return `stats` in the output, with the size of the input and the output,
+    /// the duration of each phase and the number of nodes before and after.
+    #[serde(default)]
+    pub stats: bool,
+
+    #[serde(default)]
+    pub css_modules: Option<CssModulesConfig>,
+
+    #[serde(default)]
+    pub minify: bool,
+
+    /// Return the URLs of `@import` and `url()` in `deps` of the output.
+    #[serde(default)]
+    pub analyze_dependencies: bool,
+
+    /// Remove rules which cannot match the used names.
+    #[serde(default)]
+    pub purge: Option<PurgeOptions>,
+
+    /// Return the input as-is with the errors instead of throwing, if the input
+    /// cannot be parsed. The recoverable errors are limited by `maxErrors`.
+    #[serde(default)]
+    pub recover: bool,
+
+    /// Fail if the parser reports any recoverable error.
+    #[serde(default)]
+    pub strict: bool,
+
+    /// The maximum number of recoverable errors to report.
+    #[serde(default)]
+    pub max_errors: Option<usize>,
+
+    /// Return `codeFrame` in the diagnostics. Defaults to true, but rendering
+    /// them is slow for many diagnostics.
+    #[serde(default = "true_by_default")]
+    pub code_frame: bool,
+
+    /// Fail on untrusted input which is too large or too deep.
+    #[serde(default)]
+    pub limits: Limits,
+
+    /// Statically resolve custom properties declared on `:root`. Usages which
+    /// cannot be resolved, like cycles or values longer than 64 KiB once
+    /// resolved, are reported as warnings in `errors`.
+    #[serde(default)]
+    pub custom_properties: Option<CustomPropertiesOptions>,
+
+    /// Browser targets, in the format of browserslist. The syntax they do not
+    /// support is lowered, or all of it without targets.
+    #[serde(default)]
+    #[schemars(schema_with = "schema::targets")]
+    pub targets: Option<Targets>,
+
+    /// Add vendor prefixes required by `targets` and remove the ones which are
+    /// not required anymore.
+    #[serde(default)]
+    pub prefix: bool,
+
+    /// Used only if `minify` is false, except for `preserveComments`.
+    #[serde(default)]
+    pub codegen: CodegenOptions,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[schemars(deny_unknown_fields)]
+pub struct FormatOptions {
+    #[serde(default)]
+    pub filename: Option<String>,
+
+    /// The encoding of the input, if it has no byte order mark, like
+    /// `windows-1252`. Defaults to the one of `@charset`, or UTF-8. Invalid
+    /// bytes are replaced and reported as a `lossy-decoding` warning in
+    /// `errors`.
+    #[serde(default)]
+    pub encoding: Option<String>,
+
+    /// Characters which cannot be encoded with this encoding are escaped. The
+    /// output is still returned as a string. Cannot be used with `sourceMap`.
+    #[serde(default)]
+    pub output_encoding: Option<String>,
+
+    /// Escape all non-ASCII characters. Cannot be used with `sourceMap`.
+    #[serde(default)]
+    pub ascii_only: bool,
+
+    #[serde(default)]
+    pub source_map: bool,
+
+    /// Return `stats` in the output, with the size of the input and the output,
+    /// the duration of each phase and the number of nodes before and after.
+    #[serde(default)]
+    pub stats: bool,
+
+    /// Return `codeFrame` in the diagnostics. Defaults to true, but rendering
+    /// them is slow for many diagnostics.
+    #[serde(default = "true_by_default")]
+    pub code_frame: bool,
+
+    /// Fail on untrusted input which is too large or too deep.
+    #[serde(default)]
+    pub limits: Limits,
+
+    #[serde(default)]
+    pub indent_type: Option<IndentKind>,
+
+    #[serde(default)]
+    #[schemars(range(min = 0, max = 16))]
+    pub indent_width: Option<i32>,
+
+    #[serde(default)]
+    pub line_feed: Option<LineFeedKind>,
+
+    /// Keep comments, which are printed before the token following them.
+    #[serde(default)]
+    pub preserve_comments: bool,
+}
+
+impl FormatOptions {
+    /// The [CodegenOptions], whose fields are the ones of the format options
+    /// instead of `codegen`.
+    fn codegen(&self) -> CodegenOptions {
+        CodegenOptions {
+            indent_type: self.indent_type,
+            indent_width: self.indent_width,
+            line_feed: self.line_feed,
+            preserve_comments: self.preserve_comments,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[schemars(deny_unknown_fields)]
+pub struct LintOptions {
+    #[serde(default)]
+    pub filename: Option<String>,
+
+    /// The encoding of the input, if it has no byte order mark. Defaults to
+    /// the one of `@charset`, or UTF-8.
+    #[serde(default)]
+    pub encoding: Option<String>,
+
+    /// Maps a rule id like `block-no-empty` to its level, optionally followed
+    /// by the options of the rule.
+    #[serde(default)]
+    #[schemars(schema_with = "schema::lint_rules")]
+    pub rules: serde_json::Map<String, serde_json::Value>,
+
+    /// Return `codeFrame` in the diagnostics. Defaults to true, but rendering
+    /// them is slow for many diagnostics.
+    #[serde(default = "true_by_default")]
+    pub code_frame: bool,
+
+    /// Fail on untrusted input which is too large or too deep.
+    #[serde(default)]
+    pub limits: Limits,
+}
+
+/// Implements `Default` with the options of an empty object, so the defaults
+/// of the fields match the deserialized ones.
+macro_rules! default_from_empty_object {
+    ($($ty:ty),*) => {
+        $(
+            impl Default for $ty {
+                fn default() -> Self {
+                    parse_options("{}")
+                        .expect("the options of an empty object")
+                        .0
+                }
+            }
+        )*
+    };
+}
+
+default_from_empty_object!(MinifyOptions, TransformOptions, FormatOptions, LintOptions);
+
+const fn true_by_default() -> bool {
+    true
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[schemars(deny_unknown_fields)]
+pub struct CssModulesConfig {
+    pub pattern: String,
+}
+
+/// Renames the classes of a file with the pattern of [CssModulesConfig].
+#[derive(Debug)]
+pub struct CssModuleTransformConfig {
+    pub file_name: Arc<FileName>,
+    pub file_name_hash: u8,
+    pub pattern: Vec<CssClassNameSegment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CssClassNameSegment {
+    /// A literal string segment.
+    Literal(JsWord),
+    /// The base file name.
+    Name,
+    /// The original class name.
+    Local,
+    /// A hash of the file name.
+    Hash,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type")]
+pub enum CssClassName {
+    Local { name: JsWord },
+    Global { name: JsWord },
+    Import { name: JsWord, from: JsWord },
+}
+
+#[cfg(feature = "modules")]
+impl swc_css_modules::TransformConfig for CssModuleTransformConfig {
+    fn new_name_for(&self, local: &JsWord) -> JsWord {
+        let mut buf = String::new();
+
+        for segment in &self.pattern {
+            match segment {
+                CssClassNameSegment::Literal(s) => buf.push_str(s),
+                CssClassNameSegment::Name => match &*self.file_name {
+                    FileName::Real(f) => {
+                        write!(buf, "{}", f.file_stem().unwrap().to_str().unwrap()).unwrap();
+                    }
+                    FileName::Anon => buf.push_str("[anon]"),
+                    _ => {
+                        unreachable!("CssModuleTransformConfig::new_name_for: invalid file name")
+                    }
+                },
+                CssClassNameSegment::Local => buf.push_str(local),
+                CssClassNameSegment::Hash => {
+                    write!(buf, "{:x}", self.file_name_hash).unwrap();
+                }
+            }
+        }
+
+        buf.into()
+    }
+}
+
+impl TransformOptions {
+    /// Reports invalid options before processing any input.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        self.check_features()?;
+
+        if let Some(config) = &self.css_modules {
+            config
+                .parse_pattern()
+                .context("failed to parse the pattern for CSS Modules")?;
+        }
+
+        targets_to_versions(self.targets.clone()).context("failed to resolve browser targets")?;
+
+        Ok(())
+    }
+
+    fn check_features(&self) -> anyhow::Result<()> {
+        features::check(
+            self.source_map,
+            cfg!(feature = "sourcemap"),
+            "sourceMap",
+            "sourcemap",
+        )?;
+        features::check(
+            self.css_modules.is_some(),
+            cfg!(feature = "modules"),
+            "cssModules",
+            "modules",
+        )
+    }
+}
+
+impl CssModulesConfig {
+    /// Adapted from lightningcss
+    pub fn parse_pattern(&self) -> anyhow::Result<Vec<CssClassNameSegment>> {
+        let mut res = Vec::with_capacity(2);
+
+        let mut idx = 0;
+
+        let mut s = &*self.pattern;
+
+        while !s.is_empty() {
+            if s.starts_with('[') {
+                if let Some(end_idx) = s.find(']') {
+                    let segment = match &s[0..=end_idx] {
+                        "[name]" => CssClassNameSegment::Name,
+                        "[local]" => CssClassNameSegment::Local,
+                        "[hash]" => CssClassNameSegment::Hash,
+                        s => {
+                            bail!(
+                                "Unknown placeholder {} at {} in CSS Modules pattern: {}",
+                                s,
+                                idx,
+                                self.pattern
+                            )
+                        }
+                    };
+                    res.push(segment);
+                    idx += end_idx + 1;
+                    s = &s[end_idx + 1..];
+                } else {
+                    bail!(
+                        "Unclosed brackets at {} in CSS Modules pattern: {}",
+                        idx,
+                        self.pattern
+                    )
+                }
+            } else {
+                let end_idx = s.find('[').unwrap_or(s.len());
+                res.push(CssClassNameSegment::Literal(s[0..end_idx].into()));
+                idx += end_idx;
+                s = &s[end_idx..];
+            }
+        }
+
+        Ok(res)
+    }
+}
+
+/// Applies `strict` and `maxErrors` to the recoverable errors of the parser.
+fn check_recoverable_errors(
+    handler: &Handler,
+    errors: &mut Vec<swc_css_parser::error::Error>,
+    strict: bool,
+    max_errors: Option<usize>,
+) -> anyhow::Result<()> {
+    // `strict` fails on any error, even if none is reported.
+    let fails = strict && !errors.is_empty();
+
+    if let Some(max_errors) = max_errors {
+        errors.truncate(max_errors);
+    }
+
+    if fails {
+        for err in errors.drain(..) {
+            err.to_diagnostics(handler).emit();
+        }
+
+        bail!("found recoverable errors in strict mode")
+    }
+
+    Ok(())
+}
+
+/// Serializes the mappings collected by the writer.
+#[cfg(feature = "sourcemap")]
+fn build_source_map(cm: &Lrc<SourceMap>, src_map: &[(BytePos, LineCol)]) -> anyhow::Result<String> {
+    let map = cm.build_source_map(src_map);
+    let mut buf = vec![];
+    map.to_writer(&mut buf)
+        .context("failed to generate sourcemap")?;
+    String::from_utf8(buf).context("the generated source map is not utf8")
+}
+
+/// Unreachable, as `sourceMap` is rejected by [features::check].
+#[cfg(not(feature = "sourcemap"))]
+fn build_source_map(_: &Lrc<SourceMap>, _: &[(BytePos, LineCol)]) -> anyhow::Result<String> {
+    bail!("the `sourcemap` feature is not compiled in")
+}
+
+/// An input which could not be parsed by [parse_stylesheet].
+struct ParseFailure {
+    err: swc_css_parser::error::Error,
+    /// The recoverable errors found before `err`.
+    errors: Vec<swc_css_parser::error::Error>,
+}
+
+impl ParseFailure {
+    /// Emits the errors to `handler`, so they are thrown.
+    fn emit(self, handler: &Handler) -> anyhow::Error {
+        self.err.to_diagnostics(handler).emit();
+
+        for err in self.errors {
+            err.to_diagnostics(handler).emit();
+        }
+
+        anyhow!("failed to parse input as stylesheet")
+    }
+}
+
+/// Parses `fm` in the `parse` span, with the parser config of all the
+/// functions.
+///
+/// Returns the recoverable errors with the stylesheet, as they are returned
+/// in the output unless `strict` is set. Fails if the call was aborted or
+/// timed out while parsing, as the parser then stopped early.
+fn parse_stylesheet(
+    fm: &SourceFile,
+    comments: Option<&SingleThreadedComments>,
+    css_modules: bool,
+) -> anyhow::Result<Result<(Stylesheet, Vec<swc_css_parser::error::Error>), ParseFailure>> {
+    let _span = info_span!("parse").entered();
+
+    let config = ParserConfig {
+        allow_wrong_line_comments: false,
+        css_modules,
+        legacy_nesting: false,
+        legacy_ie: false,
+    };
+    let lexer = Lexer::new(StringInput::from(fm), comments.map(|c| c as _), config);
+    let mut parser = Parser::new(Checked::new(lexer), config);
+
+    let ss = parser.parse_all();
+    let errors = parser.take_errors();
+
+    abort::check()?;
+
+    Ok(match ss {
+        Ok(ss) => Ok((ss, errors)),
+        Err(err) => Err(ParseFailure { err, errors }),
+    })
+}
+
+/// Converts the recoverable errors of the parser, which are returned in
+/// `errors` of the output.
+fn recoverable_diagnostics(
+    cm: &Lrc<SourceMap>,
+    handler: &Handler,
+    errors: Vec<swc_css_parser::error::Error>,
+    code_frame: bool,
+) -> Option<Vec<Diagnostic>> {
+    if errors.is_empty() {
+        return None;
+    }
+
+    let mut diagnostics = Vec::with_capacity(errors.len());
+
+    for err in errors {
+        let mut buf = vec![];
+
+        err.to_diagnostics(handler).buffer(&mut buf);
+
+        for i in buf {
+            diagnostics.push(to_diagnostic(cm, &i, code_frame));
+        }
+    }
+
+    Some(diagnostics)
+}
+
+/// Used for `recover: true`, when the parser failed. The output is the input
+/// as-is, as there is no tree to print.
+fn recovered_output(
+    cm: &Lrc<SourceMap>,
+    handler: &Handler,
+    code: &str,
+    failure: ParseFailure,
+    max_errors: Option<usize>,
+    code_frame: bool,
+) -> TransformOutput {
+    let ParseFailure { err, mut errors } = failure;
+
+    if let Some(max_errors) = max_errors {
+        errors.truncate(max_errors);
+    }
+
+    let mut diagnostics = vec![];
+
+    for (err, fatal) in once((err, true)).chain(errors.into_iter().map(|err| (err, false))) {
+        let mut buf = vec![];
+
+        err.to_diagnostics(handler).buffer(&mut buf);
+
+        for i in buf {
+            let mut d = to_diagnostic(cm, &i, code_frame);
+            d.fatal = fatal;
+            diagnostics.push(d);
+        }
+    }
+
+    TransformOutput {
+        code: code.into(),
+        map: None,
+        errors: Some(diagnostics),
+        warnings: None,
+        deps: None,
+        modules_mapping: None,
+        stats: None,
+    }
+}
+
+/// Minifies a stylesheet.
+#[instrument(skip_all)]
+pub fn minify(
+    code: &[u8],
+    filename: Option<&str>,
+    opts: &MinifyOptions,
+) -> anyhow::Result<TransformOutput> {
+    features::check(
+        opts.source_map,
+        cfg!(feature = "sourcemap"),
+        "sourceMap",
+        "sourcemap",
+    )?;
+    check_source_map(
+        opts.source_map,
+        opts.output_encoding.as_deref(),
+        opts.ascii_only,
+    )?;
+
+    let limiter = Limiter::new(&opts.limits, code.len())?;
+    let mut stats = StatsRecorder::new(opts.stats, code.len());
+
+    let input = decode(code, opts.encoding.as_deref())?;
+    let code = &*input.code;
+    limiter.check_source(code)?;
+
+    let output = swc_common::GLOBALS.set(&swc_common::Globals::new(), || {
+        try_with(|cm, handler| {
+            let filename = match filename.or(opts.filename.as_deref()) {
+                Some(v) => FileName::Real(v.into()),
+                None => FileName::Anon,
+            };
+
+            let fm = cm.new_source_file(filename, code.into());
+
+            let (mut ss, mut errors) = match parse_stylesheet(&fm, None, false)? {
+                Ok(parsed) => parsed,
+                Err(failure) if opts.recover => {
+                    return Ok(recovered_output(
+                        cm,
+                        handler,
+                        code,
+                        failure,
+                        opts.max_errors,
+                        opts.code_frame,
+                    ));
+                }
+                Err(failure) => return Err(failure.emit(handler)),
+            };
+
+            stats.lap(Phase::Parse);
+            limiter.check_stylesheet(&ss)?;
+            stats.count_before(&ss);
+
+            check_recoverable_errors(handler, &mut errors, opts.strict, opts.max_errors)?;
+
+            let returned_errors = recoverable_diagnostics(cm, handler, errors, opts.code_frame);
+
+            if let Some(purge_opts) = &opts.purge {
+                purge(&mut ss, purge_opts);
+            }
+
+            stats.lap(Phase::Transforms);
+            limiter.checkpoint()?;
+
+            info_span!("minify").in_scope(|| swc_css_minifier::minify(&mut ss, Default::default()));
+
+            stats.lap(Phase::Minify);
+            stats.count_after(&ss);
+            limiter.checkpoint()?;
+
+            let _span = info_span!("codegen").entered();
+
+            let mut src_map = vec![];
+            let code = {
+                let mut buf = String::new();
+                {
+                    let wr = BasicCssWriter::new(
+                        &mut buf,
+                        if opts.source_map {
+                            Some(&mut src_map)
+                        } else {
+                            None
+                        },
+                        BasicCssWriterConfig {
+                            indent_type: IndentType::Space,
+                            indent_width: 0,
+                            linefeed: LineFeed::LF,
+                        },
+                    );
+                    let mut gen = CodeGenerator::new(wr, CodegenConfig { minify: true });
+
+                    gen.emit(&ss).context("failed to emit")?;
+                }
+
+                buf
+            };
+
+            stats.lap(Phase::Codegen);
+
+            let map = if opts.source_map {
+                let map = build_source_map(cm, &src_map)?;
+                stats.lap(Phase::SourceMap);
+                Some(map)
+            } else {
+                None
+            };
+
+            Ok(TransformOutput {
+                code,
+                map,
+                errors: returned_errors,
+                warnings: None,
+                deps: Default::default(),
+                modules_mapping: Default::default(),
+                stats: stats.finish(),
+            })
+        })
+    })?;
+
+    output.encoded(&input, opts.output_encoding.as_deref(), opts.ascii_only)
+}
+
+/// Transforms a stylesheet, which is also minified if `opts.minify`.
+pub fn transform(
+    code: &[u8],
+    filename: Option<&str>,
+    opts: &TransformOptions,
+) -> anyhow::Result<TransformOutput> {
+    transform_with(code, filename, opts, None)
+}
+
+/// [transform], replacing the URLs of `url()` and `@import` with
+/// `rewrite_url`.
+#[instrument(skip_all)]
+pub fn transform_with(
+    code: &[u8],
+    filename: Option<&str>,
+    opts: &TransformOptions,
+    rewrite_url: Option<UrlRewriter>,
+) -> anyhow::Result<TransformOutput> {
+    opts.check_features()?;
+    check_source_map(
+        opts.source_map,
+        opts.output_encoding.as_deref(),
+        opts.ascii_only,
+    )?;
+
+    let limiter = Limiter::new(&opts.limits, code.len())?;
+    let mut stats = StatsRecorder::new(opts.stats, code.len());
+
+    let input = decode(code, opts.encoding.as_deref())?;
+    let code = &*input.code;
+    limiter.check_source(code)?;
+
+    let output = try_with(|cm, handler| {
+        let filename = match filename.or(opts.filename.as_deref()) {
+            Some(v) => FileName::Real(v.into()),
+            None => FileName::Anon,
+        };
+
+        let fm = cm.new_source_file(filename, code.into());
+
+        let comments = SingleThreadedComments::default();
+        let comments = opts.codegen.preserve_comments.then_some(&comments);
+
+        let (mut ss, mut errors) =
+            match parse_stylesheet(&fm, comments, opts.css_modules.is_some())? {
+                Ok(parsed) => parsed,
+                Err(failure) if opts.recover => {
+                    return Ok(recovered_output(
+                        cm,
+                        handler,
+                        code,
+                        failure,
+                        opts.max_errors,
+                        opts.code_frame,
+                    ));
+                }
+                Err(failure) => return Err(failure.emit(handler)),
+            };
+
+        stats.lap(Phase::Parse);
+        limiter.check_stylesheet(&ss)?;
+        stats.count_before(&ss);
+
+        if let Some(purge_opts) = &opts.purge {
+            purge(&mut ss, purge_opts);
+        }
+
+        let deps = if opts.analyze_dependencies {
+            Some(deps::analyze(&ss))
+        } else {
+            None
+        };
+
+        // After the analysis, so `deps` contains the URLs of the input.
+        if let Some(rewrite_url) = rewrite_url {
+            rewrite_urls(&mut ss, rewrite_url)?;
+        }
+
+        check_recoverable_errors(handler, &mut errors, opts.strict, opts.max_errors)?;
+
+        let mut returned_errors = recoverable_diagnostics(cm, handler, errors, opts.code_frame);
+
+        #[cfg(feature = "modules")]
+        let modules_mapping = if let Some(config) = &opts.css_modules {
+            let _span = info_span!("modules").entered();
+
+            let result = swc_css_modules::compile(
+                &mut ss,
+                CssModuleTransformConfig {
+                    file_name: Arc::new(fm.name.clone()),
+                    file_name_hash: fm.name_hash as _,
+                    pattern: config
+                        .parse_pattern()
+                        .context("failed to parse the pattern for CSS Modules")?,
+                },
+            );
+            let map: HashMap<_, _> = result
+                .renamed
+                .into_iter()
+                .map(|(k, v)| {
+                    (
+                        k,
+                        v.into_iter()
+                            .map(|v| match v {
+                                swc_css_modules::CssClassName::Local { name } => {
+                                    CssClassName::Local { name: name.value }
+                                }
+                                swc_css_modules::CssClassName::Global { name } => {
+                                    CssClassName::Global { name: name.value }
+                                }
+                                swc_css_modules::CssClassName::Import { name, from } => {
+                                    CssClassName::Import {
+                                        name: name.value,
+                                        from,
+                                    }
+                                }
+                            })
+                            .collect::<Vec<_>>(),
+                    )
+                })
+                .collect();
+            Some(
+                serde_json::to_string(&map)
+                    .context("failed to serialize the mapping for CSS Modules")?,
+            )
+        } else {
+            None
+        };
+        // `cssModules` is rejected by `check_features`.
+        #[cfg(not(feature = "modules"))]
+        let modules_mapping = None;
+
+        if let Some(custom_properties) = &opts.custom_properties {
+            let warnings = resolve_custom_properties(&mut ss, cm, custom_properties);
+
+            for (span, message) in warnings {
+                let mut d = SwcDiagnostic::new(Level::Warning, &message);
+                d.set_span(span);
+
+                returned_errors
+                    .get_or_insert_with(Vec::new)
+                    .push(to_diagnostic(cm, &d, opts.code_frame));
+            }
+        }
+
+        limiter.checkpoint()?;
+
+        #[cfg(feature = "compat")]
+        {
+            let process = targets::compat_features(opts.targets.as_ref())
+                .context("failed to resolve browser targets")?;
+
+            info_span!("compat").in_scope(|| {
+                ss.visit_mut_with(&mut Compiler::new(Config { process }));
+            });
+        }
+
+        if opts.prefix {
+            let _span = info_span!("prefix").entered();
+
+            let env = targets_to_versions(opts.targets.clone())
+                .context("failed to resolve browser targets")?;
+
+            ss.visit_mut_with(&mut PrefixRemover::default());
+            ss.visit_mut_with(&mut swc_css_prefixer::prefixer(PrefixerOptions {
+                env: Some(Targets::Versions(env)),
+            }));
+        }
+
+        stats.lap(Phase::Transforms);
+        stats.count_after(&ss);
+        limiter.checkpoint()?;
+
+        let _span = info_span!("codegen").entered();
+
+        let mut src_map = vec![];
+        let code = print_stylesheet(
+            &ss,
+            opts.minify,
+            &opts.codegen,
+            comments,
+            if opts.source_map {
+                Some(&mut src_map)
+            } else {
+                None
+            },
+        )?;
+
+        stats.lap(Phase::Codegen);
+
+        let map = if opts.source_map {
+            let map = build_source_map(cm, &src_map)?;
+            stats.lap(Phase::SourceMap);
+            Some(map)
+        } else {
+            None
+        };
+
+        Ok(TransformOutput {
+            code,
+            map,
+            errors: returned_errors,
+            warnings: None,
+            deps: deps.map(|v| serde_json::to_string(&v).unwrap()),
+            modules_mapping,
+            stats: stats.finish(),
+        })
+    })?;
+
+    output.encoded(&input, opts.output_encoding.as_deref(), opts.ascii_only)
+}
+
+/// Pretty-prints a stylesheet.
+#[instrument(skip_all)]
+pub fn format(
+    code: &[u8],
+    filename: Option<&str>,
+    opts: &FormatOptions,
+) -> anyhow::Result<TransformOutput> {
+    features::check(
+        opts.source_map,
+        cfg!(feature = "sourcemap"),
+        "sourceMap",
+        "sourcemap",
+    )?;
+    check_source_map(
+        opts.source_map,
+        opts.output_encoding.as_deref(),
+        opts.ascii_only,
+    )?;
+
+    let limiter = Limiter::new(&opts.limits, code.len())?;
+    let mut stats = StatsRecorder::new(opts.stats, code.len());
+
+    let input = decode(code, opts.encoding.as_deref())?;
+    let code = &*input.code;
+    limiter.check_source(code)?;
+
+    let output = try_with(|cm, handler| {
+        let filename = match filename.or(opts.filename.as_deref()) {
+            Some(v) => FileName::Real(v.into()),
+            None => FileName::Anon,
+        };
+
+        let fm = cm.new_source_file(filename, code.into());
+
+        let comments = SingleThreadedComments::default();
+        let comments = opts.preserve_comments.then_some(&comments);
+
+        let (ss, errors) =
+            parse_stylesheet(&fm, comments, false)?.map_err(|failure| failure.emit(handler))?;
+
+        stats.lap(Phase::Parse);
+        limiter.check_stylesheet(&ss)?;
+        stats.count_before(&ss);
+        stats.count_after(&ss);
+
+        let returned_errors = recoverable_diagnostics(cm, handler, errors, opts.code_frame);
+
+        let _span = info_span!("codegen").entered();
+
+        let mut src_map = vec![];
+        let code = print_stylesheet(
+            &ss,
+            false,
+            &opts.codegen(),
+            comments,
+            if opts.source_map {
+                Some(&mut src_map)
+            } else {
+                None
+            },
+        )?;
+
+        stats.lap(Phase::Codegen);
+
+        let map = if opts.source_map {
+            let map = build_source_map(cm, &src_map)?;
+            stats.lap(Phase::SourceMap);
+            Some(map)
+        } else {
+            None
+        };
+
+        Ok(TransformOutput {
+            code,
+            map,
+            errors: returned_errors,
+            warnings: None,
+            deps: Default::default(),
+            modules_mapping: Default::default(),
+            stats: stats.finish(),
+        })
+    })?;
+
+    output.encoded(&input, opts.output_encoding.as_deref(), opts.ascii_only)
+}
+
+/// Lints a stylesheet with the rules of `opts.rules`.
+#[instrument(skip_all)]
+pub fn lint(code: &[u8], filename: Option<&str>, opts: &LintOptions) -> anyhow::Result<LintOutput> {
+    let limiter = Limiter::new(&opts.limits, code.len())?;
+
+    let input = decode(code, opts.encoding.as_deref())?;
+    let code = &*input.code;
+    limiter.check_source(code)?;
+
+    let output = try_with(|cm, handler| {
+        let filename = match filename.or(opts.filename.as_deref()) {
+            Some(v) => FileName::Real(v.into()),
+            None => FileName::Anon,
+        };
+
+        let fm = cm.new_source_file(filename, code.into());
+
+        let (ss, errors) =
+            parse_stylesheet(&fm, None, false)?.map_err(|failure| failure.emit(handler))?;
+
+        let returned_errors = recoverable_diagnostics(cm, handler, errors, opts.code_frame);
+
+        limiter.check_stylesheet(&ss)?;
+
+        let diagnostics = info_span!("lint")
+            .in_scope(|| lint_stylesheet(&ss, cm, &opts.rules, opts.code_frame))?;
+
+        Ok(LintOutput {
+            diagnostics,
+            errors: returned_errors,
+            warnings: None,
+        })
+    })?;
+
+    Ok(output.decoded(&input))
+}
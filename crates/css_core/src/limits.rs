@@ -0,0 +1,267 @@
+//! `limits` of the options, for untrusted input.
+//!
+//! An exceeded limit fails with a [LimitExceeded] error, whose [Limit] is
+//! kept until it's taken with [take_exceeded], like the reports of
+//! [panic](crate::panic).
+
+use std::borrow::Cow;
+
+use extra_common::limits::exceeded;
+pub use extra_common::limits::{take_exceeded, Limit, LimitExceeded};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use swc_atoms::Atom;
+use swc_common::BytePos;
+use swc_css_ast::{AtRule, ComponentValue, Declaration, QualifiedRule, Stylesheet, TokenAndSpan};
+use swc_css_parser::{error::Error, parser::input::ParserInput};
+use swc_css_visit::{Visit, VisitWith};
+
+use crate::abort::{self, Ticker};
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[schemars(deny_unknown_fields)]
+pub struct Limits {
+    /// The maximum size of the input, in bytes.
+    #[serde(default)]
+    pub max_input_bytes: Option<usize>,
+
+    /// The maximum nesting of blocks, functions and brackets. It's checked
+    /// before parsing, as the parser recurses into them.
+    #[serde(default)]
+    pub max_depth: Option<usize>,
+
+    /// The maximum number of rules, declarations and component values of the
+    /// parsed stylesheet.
+    #[serde(default)]
+    pub max_nodes: Option<usize>,
+
+    /// Milliseconds after which processing fails.
+    ///
+    /// It's checked while parsing and walking the stylesheet, but the
+    /// minifier of swc runs to its end once started, so a call can take
+    /// longer. `maxInputBytes`, `maxDepth` and `maxNodes` bound its work.
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+}
+
+/// How many nodes are counted between [checkpoint](Limiter::checkpoint)s.
+const NODES_PER_CHECK: usize = 1024;
+
+/// Checks the [Limits] of a call.
+pub struct Limiter<'a> {
+    limits: &'a Limits,
+    _deadline: abort::Deadline,
+}
+
+impl<'a> Limiter<'a> {
+    /// Starts the clock of `timeoutMs`, and checks the size of the input and
+    /// whether the call was aborted before it started.
+    pub fn new(limits: &'a Limits, input_size: usize) -> anyhow::Result<Self> {
+        abort::check()?;
+
+        if let Some(max) = limits.max_input_bytes {
+            if input_size > max {
+                return Err(exceeded(Limit::InputBytes, max as u64));
+            }
+        }
+
+        Ok(Limiter {
+            limits,
+            _deadline: abort::deadline(limits.timeout_ms),
+        })
+    }
+
+    /// Checks `timeoutMs`, and whether the call was [aborted](crate::abort).
+    pub fn checkpoint(&self) -> anyhow::Result<()> {
+        abort::check()
+    }
+
+    /// Checks `maxDepth` on the source, skipping comments and strings.
+    pub fn check_source(&self, code: &str) -> anyhow::Result<()> {
+        let max = match self.limits.max_depth {
+            Some(max) => max,
+            None => return Ok(()),
+        };
+
+        let mut depth = 0usize;
+        let mut chars = code.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => {
+                    chars.next();
+                }
+                '/' if chars.peek() == Some(&'*') => {
+                    chars.next();
+                    let mut prev = ' ';
+                    for c in chars.by_ref() {
+                        if prev == '*' && c == '/' {
+                            break;
+                        }
+                        prev = c;
+                    }
+                }
+                '"' | '\'' => {
+                    while let Some(d) = chars.next() {
+                        match d {
+                            '\\' => {
+                                chars.next();
+                            }
+                            '\n' => break,
+                            _ if d == c => break,
+                            _ => {}
+                        }
+                    }
+                }
+                '{' | '(' | '[' => {
+                    depth += 1;
+                    if depth > max {
+                        return Err(exceeded(Limit::Depth, max as u64));
+                    }
+                }
+                '}' | ')' | ']' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks `maxNodes` on the parsed stylesheet, and [checkpoint]s
+    /// periodically while walking it.
+    ///
+    /// [checkpoint]: Limiter::checkpoint
+    pub fn check_stylesheet(&self, ss: &Stylesheet) -> anyhow::Result<()> {
+        self.checkpoint()?;
+
+        if self.limits.max_nodes.is_none() && !abort::is_scoped() {
+            return Ok(());
+        }
+
+        let mut v = NodeCounter {
+            limiter: self,
+            nodes: 0,
+            result: Ok(()),
+        };
+        ss.visit_with(&mut v);
+
+        v.result
+    }
+}
+
+struct NodeCounter<'a, 'b> {
+    limiter: &'a Limiter<'b>,
+    nodes: usize,
+    result: anyhow::Result<()>,
+}
+
+impl NodeCounter<'_, '_> {
+    /// Returns false to stop the visit.
+    fn count(&mut self) -> bool {
+        if self.result.is_err() {
+            return false;
+        }
+
+        self.nodes += 1;
+
+        if let Some(max) = self.limiter.limits.max_nodes {
+            if self.nodes > max {
+                self.result = Err(exceeded(Limit::Nodes, max as u64));
+                return false;
+            }
+        }
+
+        if self.nodes % NODES_PER_CHECK == 0 {
+            self.result = self.limiter.checkpoint();
+        }
+
+        self.result.is_ok()
+    }
+}
+
+impl Visit for NodeCounter<'_, '_> {
+    fn visit_qualified_rule(&mut self, n: &QualifiedRule) {
+        if self.count() {
+            n.visit_children_with(self);
+        }
+    }
+
+    fn visit_at_rule(&mut self, n: &AtRule) {
+        if self.count() {
+            n.visit_children_with(self);
+        }
+    }
+
+    fn visit_declaration(&mut self, n: &Declaration) {
+        if self.count() {
+            n.visit_children_with(self);
+        }
+    }
+
+    fn visit_component_value(&mut self, n: &ComponentValue) {
+        if self.count() {
+            n.visit_children_with(self);
+        }
+    }
+}
+
+/// The tokens of a lexer, which end early once the call must stop, so
+/// `timeoutMs` and the [abort flag](crate::abort) are checked while parsing.
+///
+/// The stylesheet parsed from truncated tokens must be discarded, by
+/// [checking](Limiter::checkpoint) again after parsing.
+#[derive(Debug, Clone)]
+pub struct Checked<I> {
+    input: I,
+    ticker: Ticker,
+}
+
+impl<I> Checked<I> {
+    pub fn new(input: I) -> Self {
+        Checked {
+            input,
+            ticker: Ticker::default(),
+        }
+    }
+}
+
+impl<I: ParserInput> Iterator for Checked<I> {
+    type Item = TokenAndSpan;
+
+    fn next(&mut self) -> Option<TokenAndSpan> {
+        if !self.ticker.tick() {
+            return None;
+        }
+
+        self.input.next()
+    }
+}
+
+impl<I: ParserInput> ParserInput for Checked<I> {
+    type State = I::State;
+
+    fn start_pos(&mut self) -> BytePos {
+        self.input.start_pos()
+    }
+
+    fn state(&mut self) -> Self::State {
+        self.input.state()
+    }
+
+    fn reset(&mut self, state: &Self::State) {
+        self.input.reset(state)
+    }
+
+    fn take_errors(&mut self) -> Vec<Error> {
+        self.input.take_errors()
+    }
+
+    fn skip_ws(&mut self) -> Option<BytePos> {
+        self.input.skip_ws()
+    }
+
+    fn atom(&self, s: Cow<'_, str>) -> Atom {
+        self.input.atom(s)
+    }
+}
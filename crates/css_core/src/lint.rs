@@ -0,0 +1,89 @@
+use anyhow::{anyhow, bail, Context};
+use serde_json::{json, Map, Value};
+use swc_common::{
+    errors::{Handler, HANDLER},
+    sync::Lrc,
+    SourceMap,
+};
+use swc_css_ast::Stylesheet;
+use swc_css_lints::{get_rules, LintConfig, LintParams};
+
+use crate::{
+    abort,
+    diagnostic::{to_diagnostic, Collector, Diagnostic},
+};
+
+/// Runs the rules configured by `rules`, which is the `rules` object of
+/// stylelint-like configuration.
+///
+/// Each rule is created and run separately, so the reported diagnostics can be
+/// attributed to the rule ids, and the call can be [aborted](crate::abort)
+/// between them.
+pub fn lint_stylesheet(
+    ss: &Stylesheet,
+    cm: &Lrc<SourceMap>,
+    rules: &Map<String, Value>,
+    code_frame: bool,
+) -> anyhow::Result<Vec<Diagnostic>> {
+    let known = known_rules()?;
+    let mut diagnostics = vec![];
+
+    for (rule, config) in rules {
+        abort::check()?;
+
+        // The config of swc ignores unknown rules.
+        if !known.contains_key(rule) {
+            bail!("unknown lint rule `{}`", rule)
+        }
+
+        // The config of a rule is `[level, options]`, whose options are optional.
+        let config = match config {
+            Value::Array(_) => config.clone(),
+            level => json!([level]),
+        };
+
+        let lint_config: LintConfig = serde_json::from_value(json!({
+            "rules": {
+                rule: config,
+            }
+        }))
+        .with_context(|| format!("failed to parse the config of lint rule `{}`", rule))?;
+
+        let mut lints = get_rules(&LintParams {
+            lint_config: &lint_config,
+        })
+        .map_err(|err| anyhow!("invalid config of lint rule `{}`: {:?}", rule, err))?;
+
+        let collector = Collector::default();
+        let handler = Handler::with_emitter(true, false, Box::new(collector.clone()));
+
+        HANDLER.set(&handler, || {
+            for lint in lints.iter_mut() {
+                lint.lint_stylesheet(ss);
+            }
+        });
+
+        diagnostics.extend(collector.take().iter().map(|d| Diagnostic {
+            code: Some(rule.clone()),
+            ..to_diagnostic(cm, d, code_frame)
+        }));
+    }
+
+    diagnostics.sort_by_key(|d| d.span.as_ref().map(|span| span.start));
+
+    Ok(diagnostics)
+}
+
+/// Returns the ids of the rules, which are the keys of the default config.
+fn known_rules() -> anyhow::Result<Map<String, Value>> {
+    let config = serde_json::to_value(LintConfig::default())
+        .context("failed to serialize the default lint config")?;
+
+    match config {
+        Value::Object(mut config) => match config.remove("rules") {
+            Some(Value::Object(rules)) => Ok(rules),
+            _ => bail!("the default lint config has no rules"),
+        },
+        _ => bail!("the default lint config is not an object"),
+    }
+}
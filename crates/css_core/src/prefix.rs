@@ -0,0 +1,256 @@
+use swc_atoms::JsWord;
+use swc_common::{collections::AHashSet, EqIgnoreSpan, DUMMY_SP};
+use swc_css_ast::{
+    AtRule, AtRuleName, ComponentValue, Declaration, DeclarationName, FunctionName, Ident,
+    PseudoClassSelector, PseudoElementSelector, QualifiedRule, QualifiedRulePrelude, Rule,
+    SimpleBlock, SubclassSelector,
+};
+use swc_css_visit::{VisitMut, VisitMutWith};
+
+use crate::abort::Ticker;
+
+const VENDOR_PREFIXES: &[&str] = &["-webkit-", "-moz-", "-ms-", "-o-"];
+
+/// A pseudo-class or a pseudo-element, if the `bool` is true.
+type Pseudo = (bool, &'static str);
+
+/// The prefixed pseudo-classes and pseudo-elements, whose unprefixed name is
+/// not only without the prefix.
+const PSEUDOS: &[(Pseudo, Pseudo)] = &[
+    ((false, "-moz-placeholder"), (true, "placeholder")),
+    ((false, "-ms-input-placeholder"), (true, "placeholder")),
+    ((true, "-ms-input-placeholder"), (true, "placeholder")),
+    ((true, "-webkit-input-placeholder"), (true, "placeholder")),
+    ((false, "-moz-full-screen"), (false, "fullscreen")),
+    ((false, "-webkit-full-screen"), (false, "fullscreen")),
+    (
+        (true, "-webkit-file-upload-button"),
+        (true, "file-selector-button"),
+    ),
+];
+
+/// Removes vendor-prefixed declarations, values, selectors and at-rules which
+/// have an unprefixed counterpart next to them.
+///
+/// The prefixer adds back the ones required by the browser targets, so running
+/// this first drops the prefixes which are no longer needed.
+#[derive(Default)]
+pub struct PrefixRemover {
+    ticker: Ticker,
+}
+
+impl VisitMut for PrefixRemover {
+    fn visit_mut_rules(&mut self, n: &mut Vec<Rule>) {
+        if !self.ticker.tick() {
+            return;
+        }
+
+        n.visit_mut_children_with(self);
+
+        let unprefixed = n
+            .iter()
+            .filter_map(|rule| match rule {
+                Rule::AtRule(at_rule) => Some(&**at_rule),
+                _ => None,
+            })
+            .filter(|at_rule| unprefix(at_rule_name(at_rule)).is_none())
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let preludes = n
+            .iter()
+            .filter_map(|rule| match rule {
+                Rule::QualifiedRule(rule) => Some(&rule.prelude),
+                _ => None,
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+
+        n.retain(|rule| match rule {
+            Rule::AtRule(at_rule) => !has_unprefixed_at_rule(at_rule, &unprefixed),
+            Rule::QualifiedRule(rule) => !has_unprefixed_selector(rule, &preludes),
+            _ => true,
+        });
+    }
+
+    fn visit_mut_simple_block(&mut self, n: &mut SimpleBlock) {
+        if !self.ticker.tick() {
+            return;
+        }
+
+        n.visit_mut_children_with(self);
+
+        let declarations = n
+            .value
+            .iter()
+            .filter_map(|value| match value {
+                ComponentValue::Declaration(decl) => Some(&**decl),
+                _ => None,
+            })
+            .filter(|decl| unprefix(declaration_name(decl)).is_none())
+            .cloned()
+            .collect::<Vec<_>>();
+
+        if declarations.is_empty() {
+            return;
+        }
+
+        let unprefixed = declarations
+            .iter()
+            .map(declaration_name)
+            .cloned()
+            .collect::<AHashSet<_>>();
+
+        n.value.retain(|value| match value {
+            ComponentValue::Declaration(decl) => match unprefix(declaration_name(decl)) {
+                Some(name) => !unprefixed.contains(&JsWord::from(name)),
+                None => !has_unprefixed_value(decl, &declarations),
+            },
+            _ => true,
+        });
+    }
+}
+
+fn has_unprefixed_at_rule(at_rule: &AtRule, unprefixed: &[AtRule]) -> bool {
+    let name = match unprefix(at_rule_name(at_rule)) {
+        Some(name) => name,
+        None => return false,
+    };
+
+    unprefixed.iter().any(|other| {
+        &**at_rule_name(other) == name && other.prelude.eq_ignore_span(&at_rule.prelude)
+    })
+}
+
+/// Whether the selector of `rule` has a prefixed pseudo-class or
+/// pseudo-element, and is one of `preludes` without the prefixes.
+fn has_unprefixed_selector(rule: &QualifiedRule, preludes: &[QualifiedRulePrelude]) -> bool {
+    let mut unprefixed = rule.prelude.clone();
+    let mut remover = Unprefixer::default();
+    unprefixed.visit_mut_with(&mut remover);
+
+    remover.changed
+        && preludes
+            .iter()
+            .any(|other| other.eq_ignore_span(&unprefixed))
+}
+
+/// Whether `decl` has a prefixed value, like `position: -webkit-sticky`, and
+/// one of `declarations` is the same without the prefixes.
+fn has_unprefixed_value(decl: &Declaration, declarations: &[Declaration]) -> bool {
+    let mut unprefixed = decl.clone();
+    let mut remover = Unprefixer::default();
+    unprefixed.value.visit_mut_with(&mut remover);
+
+    remover.changed
+        && declarations
+            .iter()
+            .any(|other| other.eq_ignore_span(&unprefixed))
+}
+
+/// Removes the vendor prefixes of the values and selectors it visits.
+#[derive(Default)]
+struct Unprefixer {
+    changed: bool,
+}
+
+impl Unprefixer {
+    fn unprefix_ident(&mut self, ident: &mut Ident) {
+        if let Some(name) = unprefix(&ident.value) {
+            ident.value = name.into();
+            ident.raw = None;
+            self.changed = true;
+        }
+    }
+}
+
+impl VisitMut for Unprefixer {
+    fn visit_mut_component_value(&mut self, n: &mut ComponentValue) {
+        n.visit_mut_children_with(self);
+
+        if let ComponentValue::Ident(ident) = n {
+            self.unprefix_ident(ident);
+        }
+    }
+
+    fn visit_mut_function_name(&mut self, n: &mut FunctionName) {
+        if let FunctionName::Ident(ident) = n {
+            self.unprefix_ident(ident);
+        }
+    }
+
+    fn visit_mut_subclass_selector(&mut self, n: &mut SubclassSelector) {
+        n.visit_mut_children_with(self);
+
+        let (is_element, name) = match &*n {
+            SubclassSelector::PseudoClass(pseudo) => (false, &pseudo.name.value),
+            SubclassSelector::PseudoElement(pseudo) => (true, &pseudo.name.value),
+            _ => return,
+        };
+
+        let target = PSEUDOS
+            .iter()
+            .find(|((element, prefixed), _)| *element == is_element && name == prefixed)
+            .map(|(_, target)| *target);
+
+        let (to_element, unprefixed) = match target {
+            Some(target) => target,
+            None => match unprefix(name) {
+                Some(unprefixed) => (is_element, unprefixed),
+                None => return,
+            },
+        };
+        let name = Ident {
+            span: DUMMY_SP,
+            value: unprefixed.into(),
+            raw: None,
+        };
+        self.changed = true;
+
+        // Keeps the arguments, if it's still the same kind of selector.
+        *n = match (&*n, to_element) {
+            (SubclassSelector::PseudoClass(pseudo), false) => {
+                SubclassSelector::PseudoClass(PseudoClassSelector {
+                    name,
+                    ..pseudo.clone()
+                })
+            }
+            (SubclassSelector::PseudoElement(pseudo), true) => {
+                SubclassSelector::PseudoElement(PseudoElementSelector {
+                    name,
+                    ..pseudo.clone()
+                })
+            }
+            (_, false) => SubclassSelector::PseudoClass(PseudoClassSelector {
+                span: DUMMY_SP,
+                name,
+                children: None,
+            }),
+            (_, true) => SubclassSelector::PseudoElement(PseudoElementSelector {
+                span: DUMMY_SP,
+                name,
+                children: None,
+            }),
+        };
+    }
+}
+
+fn unprefix(name: &str) -> Option<&str> {
+    VENDOR_PREFIXES
+        .iter()
+        .find_map(|prefix| name.strip_prefix(prefix))
+}
+
+fn declaration_name(n: &Declaration) -> &JsWord {
+    match &n.name {
+        DeclarationName::Ident(i) => &i.value,
+        DeclarationName::DashedIdent(i) => &i.value,
+    }
+}
+
+fn at_rule_name(n: &AtRule) -> &JsWord {
+    match &n.name {
+        AtRuleName::Ident(i) => &i.value,
+        AtRuleName::DashedIdent(i) => &i.value,
+    }
+}
@@ -0,0 +1,468 @@
+use schemars::JsonSchema;
+use serde::Deserialize;
+use swc_atoms::JsWord;
+use swc_cached::regex::CachedRegex;
+use swc_common::collections::{AHashMap, AHashSet};
+use swc_css_ast::{
+    AtRule, AtRuleName, AtRulePrelude, ComplexSelector, ComplexSelectorChildren, ComponentValue,
+    CompoundSelector, Declaration, DeclarationName, FunctionName, KeyframesName, QualifiedRule,
+    QualifiedRulePrelude, Rule, SimpleBlock, Stylesheet, SubclassSelector, Token, TypeSelector,
+};
+use swc_css_visit::{Visit, VisitMut, VisitMutWith, VisitWith};
+
+use crate::abort::Ticker;
+
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[schemars(deny_unknown_fields)]
+pub struct PurgeOptions {
+    /// Raw HTML or JS sources to scan for used names.
+    #[serde(default)]
+    pub content: Vec<String>,
+
+    /// A category which is not specified is checked only against `content`,
+    /// and is not checked at all if `content` is empty.
+    #[serde(default)]
+    pub classes: Option<Vec<String>>,
+
+    /// A category which is not specified is checked only against `content`,
+    /// and is not checked at all if `content` is empty.
+    #[serde(default)]
+    pub ids: Option<Vec<String>>,
+
+    /// A category which is not specified is checked only against `content`,
+    /// and is not checked at all if `content` is empty.
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+
+    /// A category which is not specified is checked only against `content`,
+    /// and is not checked at all if `content` is empty.
+    #[serde(default)]
+    pub attributes: Option<Vec<String>>,
+
+    /// Regular expressions. Selectors, `@keyframes` and `@font-face` rules with
+    /// a name matching one of these are always kept.
+    #[serde(default)]
+    #[schemars(schema_with = "crate::schema::regexes")]
+    pub safelist: Vec<CachedRegex>,
+}
+
+/// Removes rules whose selectors cannot match the used names, then removes
+/// `@keyframes` and `@font-face` rules which are not referenced by the
+/// remaining rules, including through custom properties.
+pub fn purge(ss: &mut Stylesheet, opts: &PurgeOptions) {
+    let mut purger = Purger::new(opts);
+
+    ss.visit_mut_with(&mut purger);
+
+    let mut refs = References::default();
+    ss.visit_with(&mut refs);
+    refs.resolve_custom_properties();
+
+    ss.visit_mut_with(&mut UnusedAtRuleRemover {
+        refs,
+        safelist: &opts.safelist,
+        ticker: Ticker::default(),
+    });
+}
+
+/// Names used by the content. A category which is [None] is not checked.
+struct Purger<'a> {
+    classes: Option<AHashSet<JsWord>>,
+    ids: Option<AHashSet<JsWord>>,
+    tags: Option<AHashSet<JsWord>>,
+    attributes: Option<AHashSet<JsWord>>,
+    safelist: &'a [CachedRegex],
+    ticker: Ticker,
+}
+
+impl<'a> Purger<'a> {
+    fn new(opts: &'a PurgeOptions) -> Self {
+        let tokens = if opts.content.is_empty() {
+            None
+        } else {
+            Some(
+                opts.content
+                    .iter()
+                    .flat_map(|content| extract_tokens(content))
+                    .collect::<AHashSet<_>>(),
+            )
+        };
+
+        let used = |names: &Option<Vec<String>>| match (names, &tokens) {
+            (None, None) => None,
+            (names, tokens) => Some(
+                names
+                    .iter()
+                    .flatten()
+                    .map(|name| JsWord::from(&**name))
+                    .chain(tokens.iter().flatten().cloned())
+                    .collect::<AHashSet<_>>(),
+            ),
+        };
+
+        Purger {
+            classes: used(&opts.classes),
+            ids: used(&opts.ids),
+            // Tag names are case-insensitive.
+            tags: used(&opts.tags).map(|tags| {
+                tags.into_iter()
+                    .map(|tag| tag.to_ascii_lowercase())
+                    .collect()
+            }),
+            attributes: used(&opts.attributes),
+            safelist: &opts.safelist,
+            ticker: Ticker::default(),
+        }
+    }
+
+    fn is_used(&self, used: &Option<AHashSet<JsWord>>, name: &JsWord) -> bool {
+        match used {
+            Some(used) => used.contains(name) || is_safelisted(self.safelist, name),
+            None => true,
+        }
+    }
+
+    fn may_match_compound(&self, n: &CompoundSelector) -> bool {
+        if let Some(TypeSelector::TagName(tag)) = n.type_selector.as_deref() {
+            let name = tag.name.value.value.to_ascii_lowercase();
+
+            if !self.is_used(&self.tags, &name) {
+                return false;
+            }
+        }
+
+        n.subclass_selectors.iter().all(|s| match s {
+            SubclassSelector::Class(s) => self.is_used(&self.classes, &s.text.value),
+            SubclassSelector::Id(s) => self.is_used(&self.ids, &s.text.value),
+            SubclassSelector::Attribute(s) => self.is_used(&self.attributes, &s.name.value.value),
+            // Pseudo classes like `:not()` may match anything.
+            _ => true,
+        })
+    }
+
+    fn may_match(&self, n: &ComplexSelector) -> bool {
+        n.children.iter().all(|c| match c {
+            ComplexSelectorChildren::CompoundSelector(c) => self.may_match_compound(c),
+            ComplexSelectorChildren::Combinator(..) => true,
+        })
+    }
+
+    /// Returns false if the rule should be removed.
+    fn purge_qualified_rule(&self, n: &mut QualifiedRule) -> bool {
+        match &mut n.prelude {
+            QualifiedRulePrelude::SelectorList(list) => {
+                list.children.retain(|s| self.may_match(s));
+
+                !list.children.is_empty()
+            }
+            _ => true,
+        }
+    }
+}
+
+impl VisitMut for Purger<'_> {
+    fn visit_mut_rules(&mut self, n: &mut Vec<Rule>) {
+        if !self.ticker.tick() {
+            return;
+        }
+
+        n.retain_mut(|rule| match rule {
+            Rule::QualifiedRule(rule) => self.purge_qualified_rule(rule),
+            _ => true,
+        });
+
+        n.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_simple_block(&mut self, n: &mut SimpleBlock) {
+        if !self.ticker.tick() {
+            return;
+        }
+
+        n.value.retain_mut(|value| match value {
+            ComponentValue::QualifiedRule(rule) => self.purge_qualified_rule(rule),
+            _ => true,
+        });
+
+        n.visit_mut_children_with(self);
+    }
+}
+
+/// Splits content into everything which may be a class name, an id, a tag name
+/// or an attribute name.
+fn extract_tokens(content: &str) -> impl Iterator<Item = JsWord> + '_ {
+    content
+        .split(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | '`' | '<' | '>' | '='))
+        .filter(|s| !s.is_empty())
+        .flat_map(|token| {
+            let parts = token
+                .split(|c: char| !(c.is_alphanumeric() || c == '-' || c == '_'))
+                .filter(move |part| !part.is_empty() && *part != token);
+
+            std::iter::once(token).chain(parts)
+        })
+        .map(JsWord::from)
+}
+
+fn is_safelisted(safelist: &[CachedRegex], name: &str) -> bool {
+    safelist.iter().any(|re| re.is_match(name))
+}
+
+/// Animation and font family names referenced by declarations.
+#[derive(Default)]
+struct References {
+    animations: AHashSet<JsWord>,
+    font_families: AHashSet<JsWord>,
+    /// The custom properties used by `var()` in animation and font
+    /// declarations, whose names are added by
+    /// [References::resolve_custom_properties].
+    animation_vars: Vec<JsWord>,
+    font_family_vars: Vec<JsWord>,
+    /// The names and the custom properties used by `var()` in the values of
+    /// each custom property.
+    custom_properties: AHashMap<JsWord, (Vec<JsWord>, Vec<JsWord>)>,
+}
+
+impl References {
+    fn resolve_custom_properties(&mut self) {
+        let animations = self.custom_property_names(&self.animation_vars);
+        let font_families = self.custom_property_names(&self.font_family_vars);
+
+        self.animations.extend(animations);
+        self.font_families.extend(font_families);
+    }
+
+    /// Returns the names in the values of `vars`, and of the custom
+    /// properties they use in turn.
+    fn custom_property_names(&self, vars: &[JsWord]) -> Vec<JsWord> {
+        let mut names = vec![];
+        let mut seen = AHashSet::default();
+        let mut pending = vars.to_vec();
+
+        while let Some(var) = pending.pop() {
+            if !seen.insert(var.clone()) {
+                continue;
+            }
+
+            if let Some((values, vars)) = self.custom_properties.get(&var) {
+                names.extend(values.iter().cloned());
+                pending.extend(vars.iter().cloned());
+            }
+        }
+
+        names
+    }
+}
+
+impl Visit for References {
+    fn visit_at_rule(&mut self, n: &AtRule) {
+        // Descriptors of `@font-face` are not references.
+        if at_rule_name(n).eq_ignore_ascii_case("font-face") {
+            return;
+        }
+
+        n.visit_children_with(self);
+    }
+
+    fn visit_declaration(&mut self, n: &Declaration) {
+        let name = match &n.name {
+            DeclarationName::Ident(i) => i.value.to_ascii_lowercase(),
+            DeclarationName::DashedIdent(i) => {
+                let (values, vars) = self.custom_properties.entry(i.value.clone()).or_default();
+
+                values.extend(value_names(&n.value));
+                vars.extend(var_names(&n.value));
+
+                return;
+            }
+        };
+
+        if name.ends_with("animation") || name.ends_with("animation-name") {
+            self.animations.extend(value_names(&n.value));
+            self.animation_vars.extend(var_names(&n.value));
+        } else if name == "font" || name == "font-family" {
+            self.font_families.extend(value_names(&n.value));
+            self.font_family_vars.extend(var_names(&n.value));
+        }
+
+        n.visit_children_with(self);
+    }
+}
+
+struct UnusedAtRuleRemover<'a> {
+    refs: References,
+    safelist: &'a [CachedRegex],
+    ticker: Ticker,
+}
+
+impl UnusedAtRuleRemover<'_> {
+    fn is_used(&self, n: &AtRule) -> bool {
+        let name = at_rule_name(n).to_ascii_lowercase();
+
+        if name.ends_with("keyframes") {
+            let keyframes = match n.prelude.as_deref() {
+                Some(AtRulePrelude::KeyframesPrelude(KeyframesName::CustomIdent(i))) => &i.value,
+                Some(AtRulePrelude::KeyframesPrelude(KeyframesName::Str(s))) => &s.value,
+                _ => return true,
+            };
+
+            return self.refs.animations.contains(keyframes)
+                || is_safelisted(self.safelist, keyframes);
+        }
+
+        if name == "font-face" {
+            let family = n.block.as_ref().and_then(|block| {
+                block.value.iter().find_map(|value| match value {
+                    ComponentValue::Declaration(decl)
+                        if declaration_name(decl).eq_ignore_ascii_case("font-family") =>
+                    {
+                        value_names(&decl.value).into_iter().last()
+                    }
+                    _ => None,
+                })
+            });
+
+            return match family {
+                Some(family) => {
+                    self.refs.font_families.contains(&family)
+                        || is_safelisted(self.safelist, &family)
+                }
+                None => true,
+            };
+        }
+
+        true
+    }
+}
+
+impl VisitMut for UnusedAtRuleRemover<'_> {
+    fn visit_mut_rules(&mut self, n: &mut Vec<Rule>) {
+        if !self.ticker.tick() {
+            return;
+        }
+
+        n.retain(|rule| match rule {
+            Rule::AtRule(rule) => self.is_used(rule),
+            _ => true,
+        });
+
+        n.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_simple_block(&mut self, n: &mut SimpleBlock) {
+        if !self.ticker.tick() {
+            return;
+        }
+
+        n.value.retain(|value| match value {
+            ComponentValue::AtRule(rule) => self.is_used(rule),
+            _ => true,
+        });
+
+        n.visit_mut_children_with(self);
+    }
+}
+
+/// Returns every identifier and string in `values`, and additionally each run
+/// of identifiers joined by spaces, so unquoted font family names like
+/// `Open Sans` are found.
+///
+/// The values of custom properties are tokens instead of parsed values.
+fn value_names(values: &[ComponentValue]) -> Vec<JsWord> {
+    let mut names = vec![];
+    let mut run: Vec<&str> = vec![];
+
+    for value in values {
+        let ident = match value {
+            ComponentValue::Ident(i) => Some(&i.value),
+            ComponentValue::PreservedToken(t) => match &t.token {
+                Token::Ident { value, .. } => Some(value),
+                Token::String { value, .. } => {
+                    names.push(value.clone());
+                    None
+                }
+                Token::WhiteSpace { .. } => continue,
+                _ => None,
+            },
+            ComponentValue::Str(s) => {
+                names.push(s.value.clone());
+                None
+            }
+            _ => None,
+        };
+
+        if let Some(ident) = ident {
+            names.push(ident.clone());
+            run.push(ident);
+            continue;
+        }
+
+        if run.len() > 1 {
+            names.push(run.join(" ").into());
+        }
+        run.clear();
+    }
+
+    if run.len() > 1 {
+        names.push(run.join(" ").into());
+    }
+
+    names
+}
+
+/// Returns the custom properties used by `var()` in `values`.
+fn var_names(values: &[ComponentValue]) -> Vec<JsWord> {
+    let mut names = vec![];
+
+    for value in values {
+        if let ComponentValue::Function(f) = value {
+            let is_var = match &f.name {
+                FunctionName::Ident(i) => i.value.eq_ignore_ascii_case("var"),
+                FunctionName::DashedIdent(..) => false,
+            };
+
+            if is_var {
+                let name = f.value.iter().find(|value| !is_whitespace(value));
+
+                names.extend(name.and_then(custom_property_name));
+            }
+
+            // A fallback may use other custom properties.
+            names.extend(var_names(&f.value));
+        }
+    }
+
+    names
+}
+
+/// Returns the name of the custom property `value`, without the dashes.
+fn custom_property_name(value: &ComponentValue) -> Option<JsWord> {
+    match value {
+        ComponentValue::DashedIdent(i) => Some(i.value.clone()),
+        // In the values of custom properties, the name is a token.
+        ComponentValue::PreservedToken(t) => match &t.token {
+            Token::Ident { value, .. } => value.strip_prefix("--").map(JsWord::from),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn is_whitespace(value: &ComponentValue) -> bool {
+    matches!(value, ComponentValue::PreservedToken(t) if matches!(t.token, Token::WhiteSpace { .. }))
+}
+
+fn declaration_name(n: &Declaration) -> &JsWord {
+    match &n.name {
+        DeclarationName::Ident(i) => &i.value,
+        DeclarationName::DashedIdent(i) => &i.value,
+    }
+}
+
+fn at_rule_name(n: &AtRule) -> &JsWord {
+    match &n.name {
+        AtRuleName::Ident(i) => &i.value,
+        AtRuleName::DashedIdent(i) => &i.value,
+    }
+}
@@ -0,0 +1,82 @@
+//! `rewriteUrl` of the transform options, which replaces the URLs of `url()`
+//! and `@import`.
+
+use swc_atoms::JsWord;
+use swc_css_ast::{ImportHref, Stylesheet, Url, UrlValue};
+use swc_css_visit::{VisitMut, VisitMutWith};
+
+/// Returns the replacement of a URL.
+pub type UrlRewriter<'a> = &'a dyn Fn(&str) -> anyhow::Result<String>;
+
+pub fn rewrite_urls(ss: &mut Stylesheet, rewrite: UrlRewriter) -> anyhow::Result<()> {
+    let mut v = Rewriter {
+        rewrite,
+        error: None,
+    };
+
+    ss.visit_mut_with(&mut v);
+
+    match v.error {
+        Some(err) => Err(err),
+        None => Ok(()),
+    }
+}
+
+struct Rewriter<'a> {
+    rewrite: UrlRewriter<'a>,
+    /// The first error. Remaining URLs are not rewritten.
+    error: Option<anyhow::Error>,
+}
+
+impl Rewriter<'_> {
+    /// Returns [None] if the URL is unchanged.
+    fn rewrite(&mut self, url: &JsWord) -> Option<JsWord> {
+        if self.error.is_some() {
+            return None;
+        }
+
+        match (self.rewrite)(url) {
+            Ok(v) if *v == **url => None,
+            Ok(v) => Some(v.into()),
+            Err(err) => {
+                self.error = Some(err.context(format!("failed to rewrite `{}`", url)));
+                None
+            }
+        }
+    }
+}
+
+impl VisitMut for Rewriter<'_> {
+    fn visit_mut_import_href(&mut self, n: &mut ImportHref) {
+        match n {
+            ImportHref::Url(n) => n.visit_mut_with(self),
+            ImportHref::Str(n) => {
+                if let Some(value) = self.rewrite(&n.value) {
+                    n.value = value;
+                    n.raw = None;
+                }
+            }
+        }
+    }
+
+    fn visit_mut_url(&mut self, n: &mut Url) {
+        let Some(v) = n.value.as_deref_mut() else {
+            return;
+        };
+
+        match v {
+            UrlValue::Str(v) => {
+                if let Some(value) = self.rewrite(&v.value) {
+                    v.value = value;
+                    v.raw = None;
+                }
+            }
+            UrlValue::Raw(v) => {
+                if let Some(value) = self.rewrite(&v.value) {
+                    v.value = value;
+                    v.raw = None;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,53 @@
+//! JSON Schema of the options, for the autocompletion of editors and to
+//! generate the types of `options.ts`.
+
+use extra_common::schema::{from_json, root_schema};
+use schemars::{gen::SchemaGenerator, schema::Schema};
+use serde_json::{json, Value};
+
+use crate::{FormatOptions, LintOptions, MinifyOptions, TransformOptions};
+
+/// Returns the schemas of the options, keyed by the function using them.
+pub fn options_schema() -> anyhow::Result<Value> {
+    Ok(json!({
+        "minify": root_schema::<MinifyOptions>()?,
+        "transform": root_schema::<TransformOptions>()?,
+        "format": root_schema::<FormatOptions>()?,
+        "lint": root_schema::<LintOptions>()?,
+    }))
+}
+
+/// [preset_env_base::query::Targets]
+pub fn targets(_: &mut SchemaGenerator) -> Schema {
+    from_json(json!({
+        "anyOf": [
+            { "type": "string" },
+            { "type": "array", "items": { "type": "string" } },
+            { "type": "object", "additionalProperties": { "type": "string" } },
+        ],
+    }))
+}
+
+/// [swc_cached::regex::CachedRegex]
+pub fn regexes(_: &mut SchemaGenerator) -> Schema {
+    from_json(json!({
+        "type": "array",
+        "items": { "type": "string", "format": "regex" },
+    }))
+}
+
+/// [LintOptions::rules], which maps the ids to the `RuleConfig` of
+/// `swc_css_lints`.
+pub fn lint_rules(_: &mut SchemaGenerator) -> Schema {
+    let level = json!({ "enum": ["off", "warning", "error"] });
+
+    from_json(json!({
+        "type": "object",
+        "additionalProperties": {
+            "anyOf": [
+                level,
+                { "type": "array", "items": [level, {}], "minItems": 1, "maxItems": 2 },
+            ],
+        },
+    }))
+}
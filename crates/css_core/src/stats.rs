@@ -0,0 +1,165 @@
+//! `stats` of the output, if requested with the `stats` option.
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+use swc_css_ast::{AtRule, Declaration, QualifiedRule, Stylesheet};
+use swc_css_visit::{Visit, VisitWith};
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
+#[cfg_attr(
+    all(feature = "napi", not(feature = "namespace")),
+    napi_derive::napi(object)
+)]
+#[cfg_attr(
+    all(feature = "napi", feature = "namespace"),
+    napi_derive::napi(object, namespace = "css")
+)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Stats {
+    /// Bytes of the input.
+    pub input_size: u32,
+    /// Bytes of `code`.
+    pub output_size: u32,
+    /// Milliseconds spent in each phase. A phase which did not run is `0`.
+    pub durations: Durations,
+    /// Counted after parsing.
+    pub before: Counts,
+    /// Counted before generating the code, so the difference with `before`
+    /// is what was removed or merged.
+    pub after: Counts,
+}
+
+#[cfg_attr(
+    all(feature = "napi", not(feature = "namespace")),
+    napi_derive::napi(object)
+)]
+#[cfg_attr(
+    all(feature = "napi", feature = "namespace"),
+    napi_derive::napi(object, namespace = "css")
+)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Durations {
+    pub parse: f64,
+    /// Everything between parsing and minifying, like CSS Modules and
+    /// prefixing.
+    pub transforms: f64,
+    pub minify: f64,
+    pub codegen: f64,
+    pub source_map: f64,
+}
+
+#[cfg_attr(
+    all(feature = "napi", not(feature = "namespace")),
+    napi_derive::napi(object)
+)]
+#[cfg_attr(
+    all(feature = "napi", feature = "namespace"),
+    napi_derive::napi(object, namespace = "css")
+)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Counts {
+    /// Qualified rules and at-rules, including nested ones.
+    pub rules: u32,
+    pub declarations: u32,
+}
+
+#[derive(Clone, Copy)]
+pub enum Phase {
+    Parse,
+    Transforms,
+    Minify,
+    Codegen,
+    SourceMap,
+}
+
+/// Measures the phases, as laps of a stopwatch.
+pub struct StatsRecorder {
+    stats: Option<Stats>,
+    lap: Instant,
+}
+
+impl StatsRecorder {
+    /// Starts the first lap. Nothing is recorded unless `enabled`.
+    pub fn new(enabled: bool, input_size: usize) -> Self {
+        StatsRecorder {
+            stats: enabled.then(|| Stats {
+                input_size: input_size as u32,
+                ..Default::default()
+            }),
+            lap: Instant::now(),
+        }
+    }
+
+    /// Adds the time since the previous lap to `phase`.
+    pub fn lap(&mut self, phase: Phase) {
+        let now = Instant::now();
+
+        if let Some(stats) = &mut self.stats {
+            let d = &mut stats.durations;
+            let duration = match phase {
+                Phase::Parse => &mut d.parse,
+                Phase::Transforms => &mut d.transforms,
+                Phase::Minify => &mut d.minify,
+                Phase::Codegen => &mut d.codegen,
+                Phase::SourceMap => &mut d.source_map,
+            };
+
+            *duration += (now - self.lap).as_secs_f64() * 1000.0;
+        }
+
+        self.lap = now;
+    }
+
+    pub fn count_before(&mut self, ss: &Stylesheet) {
+        if let Some(stats) = &mut self.stats {
+            stats.before = Counts::of(ss);
+        }
+
+        // Counting is not part of the next phase.
+        self.lap = Instant::now();
+    }
+
+    pub fn count_after(&mut self, ss: &Stylesheet) {
+        if let Some(stats) = &mut self.stats {
+            stats.after = Counts::of(ss);
+        }
+
+        self.lap = Instant::now();
+    }
+
+    /// `output_size` is set with the final code.
+    pub fn finish(self) -> Option<Stats> {
+        self.stats
+    }
+}
+
+impl Counts {
+    fn of(ss: &Stylesheet) -> Self {
+        let mut v = Counts::default();
+        ss.visit_with(&mut v);
+        v
+    }
+}
+
+impl Visit for Counts {
+    fn visit_qualified_rule(&mut self, n: &QualifiedRule) {
+        self.rules += 1;
+        n.visit_children_with(self);
+    }
+
+    fn visit_at_rule(&mut self, n: &AtRule) {
+        self.rules += 1;
+        n.visit_children_with(self);
+    }
+
+    fn visit_declaration(&mut self, n: &Declaration) {
+        self.declarations += 1;
+        n.visit_children_with(self);
+    }
+}
@@ -0,0 +1,109 @@
+//! The features of the compat pass required by the browser targets.
+
+use std::sync::OnceLock;
+
+use browserslist::{resolve, Opts};
+use preset_env_base::{
+    query::{targets_to_versions, Targets},
+    version::should_enable,
+    Versions,
+};
+use swc_css_compat::feature::Features;
+
+/// The features tracked by caniuse, by their id in the data of browserslist.
+/// Their first versions are the lowest ones it reports as fully supporting
+/// them.
+const CANIUSE: &[(Features, &str)] = &[
+    (Features::NESTING, "css-nesting"),
+    (Features::MEDIA_QUERY_RANGES, "css-media-range-syntax"),
+    (Features::COLOR_HEX_ALPHA, "css-rrggbbaa"),
+    (Features::SELECTOR_NOT, "css-not-sel-list"),
+];
+
+/// The first versions supporting the features which caniuse does not track,
+/// from `browser_support` of the cssdb features `custom-media-queries`,
+/// `color-functional-notation` and `hwb-function`, like postcss-preset-env. A
+/// browser which is not listed never supported it.
+const CSSDB: &[(Features, &[(&str, &str)])] = &[
+    // No browser supports `@custom-media`.
+    (Features::CUSTOM_MEDIA, &[]),
+    (Features::COLOR_ALPHA_PARAMETER, COLOR_FUNCTIONAL_NOTATION),
+    (
+        Features::COLOR_SPACE_SEPARATED_PARAMETERS,
+        COLOR_FUNCTIONAL_NOTATION,
+    ),
+    (
+        Features::COLOR_LEGACY_RGB_AND_HSL,
+        COLOR_FUNCTIONAL_NOTATION,
+    ),
+    (
+        Features::COLOR_HWB,
+        &[
+            ("chrome", "101"),
+            ("edge", "101"),
+            ("firefox", "96"),
+            ("safari", "15"),
+            ("ios", "15"),
+            ("opera", "87"),
+            ("samsung", "19"),
+        ],
+    ),
+];
+
+const COLOR_FUNCTIONAL_NOTATION: &[(&str, &str)] = &[
+    ("chrome", "65"),
+    ("edge", "79"),
+    ("firefox", "52"),
+    ("safari", "12.1"),
+    ("ios", "12.2"),
+    ("opera", "52"),
+    ("samsung", "9.2"),
+];
+
+/// Returns the features which are not supported by one of `targets`, or all
+/// of them without targets.
+pub fn compat_features(targets: Option<&Targets>) -> anyhow::Result<Features> {
+    let targets = match targets {
+        Some(targets) => targets_to_versions(Some(targets.clone()))?,
+        None => return Ok(Features::all()),
+    };
+
+    Ok(support()
+        .iter()
+        .filter(|(_, versions)| should_enable(targets, *versions, true))
+        .fold(Features::empty(), |features, (feature, _)| {
+            features | *feature
+        }))
+}
+
+/// The first versions supporting each feature, resolved once.
+fn support() -> &'static [(Features, Versions)] {
+    static SUPPORT: OnceLock<Vec<(Features, Versions)>> = OnceLock::new();
+
+    SUPPORT.get_or_init(|| {
+        let caniuse = CANIUSE.iter().map(|(feature, id)| {
+            let distribs = resolve([format!("fully supports {}", id)], &Opts::default())
+                .unwrap_or_else(|err| panic!("`{}` is not a feature of caniuse: {}", id, err));
+
+            (
+                *feature,
+                Versions::parse_versions(distribs).expect("failed to parse the versions"),
+            )
+        });
+        let cssdb = CSSDB
+            .iter()
+            .map(|(feature, support)| (*feature, versions(support)));
+
+        caniuse.chain(cssdb).collect()
+    })
+}
+
+fn versions(support: &[(&str, &str)]) -> Versions {
+    let mut versions = Versions::default();
+
+    for (browser, version) in support {
+        versions.insert(browser, version.parse().ok());
+    }
+
+    versions
+}
@@ -0,0 +1,729 @@
+use std::path::Path;
+
+use css_core::{
+    abort::{self, AbortFlag, Aborted},
+    limits::{take_exceeded, Limit, Limits},
+    parse_options, CssClassNameSegment, CssModulesConfig, FormatOptions, LintOptions,
+    MinifyOptions, TransformOptions,
+};
+
+#[test]
+fn minify_removes_whitespace() {
+    let output = css_core::minify(b"a {\n  color: red;\n}\n", None, &Default::default()).unwrap();
+
+    assert_eq!(output.code, "a{color:red}");
+    assert!(output.map.is_none());
+    assert!(output.errors.is_none());
+}
+
+#[test]
+fn minify_returns_recoverable_errors() {
+    let output = css_core::minify(b"a { color: red", None, &Default::default()).unwrap();
+
+    assert_eq!(output.code, "a{color:red}");
+    assert!(output.errors.unwrap().iter().all(|d| !d.fatal));
+}
+
+#[test]
+fn code_frames_can_be_turned_off() {
+    let output = css_core::minify(b"a { color: red", None, &Default::default()).unwrap();
+    assert!(output.errors.unwrap()[0].code_frame.is_some());
+
+    let opts = MinifyOptions {
+        code_frame: false,
+        ..Default::default()
+    };
+    let output = css_core::minify(b"a { color: red", None, &opts).unwrap();
+    assert!(output.errors.unwrap()[0].code_frame.is_none());
+}
+
+#[test]
+fn minify_fails_on_recoverable_errors_in_strict_mode() {
+    let opts = MinifyOptions {
+        strict: true,
+        ..Default::default()
+    };
+
+    assert!(css_core::minify(b"a { color: red", None, &opts).is_err());
+}
+
+#[test]
+fn strict_mode_fails_even_if_no_error_is_reported() {
+    let opts = MinifyOptions {
+        strict: true,
+        max_errors: Some(0),
+        ..Default::default()
+    };
+
+    assert!(css_core::minify(b"a { color: red", None, &opts).is_err());
+}
+
+#[test]
+#[cfg(feature = "sourcemap")]
+fn minify_returns_source_map() {
+    let opts = MinifyOptions {
+        source_map: true,
+        ..Default::default()
+    };
+    let output = css_core::minify(b"a { color: red }", Some("a.css"), &opts).unwrap();
+
+    assert!(output.map.unwrap().contains("a.css"));
+}
+
+#[test]
+#[cfg(not(feature = "sourcemap"))]
+fn source_map_is_not_compiled_in() {
+    let opts = MinifyOptions {
+        source_map: true,
+        ..Default::default()
+    };
+    let err = css_core::minify(b"a { color: red }", None, &opts).unwrap_err();
+
+    assert!(err.to_string().contains("not compiled in"));
+}
+
+#[test]
+fn parse_pattern() {
+    let config = CssModulesConfig {
+        pattern: "[name]__[local]-[hash]".into(),
+    };
+
+    assert_eq!(
+        config.parse_pattern().unwrap(),
+        vec![
+            CssClassNameSegment::Name,
+            CssClassNameSegment::Literal("__".into()),
+            CssClassNameSegment::Local,
+            CssClassNameSegment::Literal("-".into()),
+            CssClassNameSegment::Hash,
+        ]
+    );
+}
+
+#[test]
+fn parse_pattern_rejects_unknown_placeholders() {
+    let config = CssModulesConfig {
+        pattern: "[path]".into(),
+    };
+
+    assert!(config.parse_pattern().is_err());
+}
+
+#[test]
+#[cfg(feature = "modules")]
+fn transform_renames_classes() {
+    let opts = TransformOptions {
+        css_modules: Some(CssModulesConfig {
+            pattern: "[name]__[local]".into(),
+        }),
+        ..Default::default()
+    };
+    let output = css_core::transform(b".foo { color: red }", Some("button.css"), &opts).unwrap();
+
+    assert!(output.code.contains(".button__foo"));
+    assert!(output.modules_mapping.unwrap().contains("button__foo"));
+}
+
+#[test]
+#[cfg(not(feature = "modules"))]
+fn css_modules_are_not_compiled_in() {
+    let opts = TransformOptions {
+        css_modules: Some(CssModulesConfig {
+            pattern: "[local]".into(),
+        }),
+        ..Default::default()
+    };
+
+    assert!(opts.validate().is_err());
+    assert!(css_core::transform(b".foo {}", None, &opts).is_err());
+}
+
+#[test]
+fn transform_analyzes_dependencies() {
+    let opts = TransformOptions {
+        analyze_dependencies: true,
+        ..Default::default()
+    };
+    let output = css_core::transform(
+        b"@import 'a.css'; b { background: url(b.png) }",
+        None,
+        &opts,
+    )
+    .unwrap();
+    let deps = output.deps.unwrap();
+
+    assert!(deps.contains("a.css"));
+    assert!(deps.contains("b.png"));
+}
+
+#[test]
+fn transform_rewrites_urls() {
+    let rewrite = |url: &str| Ok(format!("/static/{}", url));
+    let output = css_core::transform_with(
+        b"a { background: url(a.png) }",
+        None,
+        &Default::default(),
+        Some(&rewrite),
+    )
+    .unwrap();
+
+    assert!(output.code.contains("/static/a.png"));
+}
+
+#[test]
+fn validate_reports_invalid_pattern() {
+    let opts = TransformOptions {
+        css_modules: Some(CssModulesConfig {
+            pattern: "[local".into(),
+        }),
+        ..Default::default()
+    };
+
+    assert!(opts.validate().is_err());
+}
+
+#[test]
+fn format_indents() {
+    let output = css_core::format(b"a{color:red}", None, &FormatOptions::default()).unwrap();
+
+    assert!(output.code.contains('\n'));
+    assert!(output.code.contains("color: red"));
+}
+
+#[test]
+fn format_accepts_codegen_options() {
+    let (opts, _): (FormatOptions, _) =
+        parse_options(r#"{ "indentType": "tab", "lineFeed": "crlf" }"#).unwrap();
+    let output = css_core::format(b"a{color:red}", None, &opts).unwrap();
+
+    assert!(
+        output.code.contains("{\r\n\tcolor: red"),
+        "{:?}",
+        output.code
+    );
+
+    assert!(parse_options::<FormatOptions>(r#"{ "indentWidht": 4 }"#).is_err());
+}
+
+fn format_with_options(code: &str, options: &str) -> anyhow::Result<String> {
+    let (opts, _): (FormatOptions, _) = parse_options(options).unwrap();
+
+    Ok(css_core::format(code.as_bytes(), None, &opts)?.code)
+}
+
+#[test]
+fn format_uses_indent_width() {
+    let code = format_with_options("a{color:red}", r#"{ "indentWidth": 4 }"#).unwrap();
+
+    assert_eq!(code, "a {\n    color: red;\n}");
+}
+
+#[test]
+fn format_rejects_invalid_indent_width() {
+    for width in ["-1", "17"] {
+        let err =
+            format_with_options("a{}", &format!(r#"{{ "indentWidth": {} }}"#, width)).unwrap_err();
+
+        assert!(err.to_string().contains("`indentWidth`"), "{}", err);
+    }
+}
+
+#[test]
+fn format_preserves_nested_comments() {
+    let code = format_with_options(
+        "/* top */\na {\n  /* before */\n  color: /* value */ red;\n}\n@media print {\n  /* rule \
+         */\n  b { color: blue }\n}\n/* end */",
+        r#"{ "preserveComments": true }"#,
+    )
+    .unwrap();
+
+    for comment in [
+        "/* top */",
+        "/* before */",
+        "/* value */",
+        "/* rule */",
+        "/* end */",
+    ] {
+        assert!(code.contains(comment), "{:?}", code);
+    }
+
+    assert!(
+        code.starts_with("/* top */\na {\n  /* before */\n  color: /* value */ red;"),
+        "{:?}",
+        code
+    );
+}
+
+#[test]
+fn format_drops_comments_by_default() {
+    let code = format_with_options("a { /* x */ color: red }", "{}").unwrap();
+
+    assert!(!code.contains("/*"), "{:?}", code);
+}
+
+#[test]
+fn lint_reports_enabled_rules() {
+    let (opts, warnings): (LintOptions, _) =
+        parse_options(r#"{ "rules": { "block-no-empty": "error" } }"#).unwrap();
+    let output = css_core::lint(b"a {}", None, &opts).unwrap();
+
+    assert!(warnings.is_empty());
+    assert_eq!(output.diagnostics.len(), 1);
+    assert_eq!(
+        output.diagnostics[0].code.as_deref(),
+        Some("block-no-empty")
+    );
+}
+
+fn lint_with_rules(code: &str, rules: &str) -> anyhow::Result<Vec<(String, String)>> {
+    let (opts, _): (LintOptions, _) =
+        parse_options(&format!(r#"{{ "rules": {} }}"#, rules)).unwrap();
+
+    Ok(css_core::lint(code.as_bytes(), None, &opts)?
+        .diagnostics
+        .into_iter()
+        .map(|d| (d.code.unwrap(), d.level))
+        .collect())
+}
+
+#[test]
+fn lint_reports_the_level_of_each_rule() {
+    let diagnostics = lint_with_rules(
+        "a { color: #fff } b { color: red !important }",
+        r#"{ "color-hex-length": "warning", "declaration-no-important": ["error"] }"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        diagnostics,
+        vec![
+            ("color-hex-length".into(), "warning".into()),
+            ("declaration-no-important".into(), "error".into()),
+        ]
+    );
+}
+
+#[test]
+fn lint_skips_rules_which_are_off() {
+    let diagnostics = lint_with_rules("a {}", r#"{ "block-no-empty": "off" }"#).unwrap();
+
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn lint_rejects_unknown_rules_and_invalid_configs() {
+    let err = lint_with_rules("a {}", r#"{ "no-such-rule": "error" }"#).unwrap_err();
+    assert!(
+        err.to_string().contains("unknown lint rule `no-such-rule`"),
+        "{}",
+        err
+    );
+
+    let err = lint_with_rules("a {}", r#"{ "block-no-empty": "fatal" }"#).unwrap_err();
+    assert!(err.to_string().contains("`block-no-empty`"), "{}", err);
+}
+
+#[test]
+fn lint_returns_recoverable_errors() {
+    let (opts, _): (LintOptions, _) =
+        parse_options(r#"{ "rules": { "block-no-empty": "error" } }"#).unwrap();
+    let output = css_core::lint(b"a {} b { color: red", None, &opts).unwrap();
+
+    assert_eq!(output.diagnostics.len(), 1);
+    assert!(!output.errors.unwrap().is_empty());
+}
+
+#[test]
+fn parse_options_reports_unknown_keys() {
+    let (_, warnings): (MinifyOptions, _) =
+        parse_options(r#"{ "unknownOptions": "warn", "foo": 1 }"#).unwrap();
+
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].contains("`foo`"));
+
+    assert!(parse_options::<MinifyOptions>(r#"{ "foo": 1 }"#).is_err());
+}
+
+#[test]
+fn stats_are_opt_in() {
+    let output = css_core::minify(b"a { color: red }", None, &Default::default()).unwrap();
+    assert!(output.stats.is_none());
+
+    let opts = MinifyOptions {
+        stats: true,
+        ..Default::default()
+    };
+    let stats = css_core::minify(b"a { color: red } b {}", None, &opts)
+        .unwrap()
+        .stats
+        .unwrap();
+
+    assert_eq!(stats.input_size, 21);
+    assert_eq!(stats.before.rules, 2);
+}
+
+#[test]
+fn stats_count_rules_before_and_after_minifying() {
+    let opts = MinifyOptions {
+        stats: true,
+        ..Default::default()
+    };
+    let output = css_core::minify(
+        b"@media print { a { color: red; margin: 0 } } b {} c { color: blue }",
+        None,
+        &opts,
+    )
+    .unwrap();
+    let stats = output.stats.unwrap();
+
+    assert_eq!(stats.before.rules, 4);
+    assert_eq!(stats.before.declarations, 3);
+    // The empty rule is removed.
+    assert_eq!(stats.after.rules, 3);
+    assert_eq!(stats.after.declarations, 3);
+    assert_eq!(stats.output_size as usize, output.code.len());
+}
+
+#[test]
+fn stats_skip_phases_which_did_not_run() {
+    let opts = MinifyOptions {
+        stats: true,
+        ..Default::default()
+    };
+    let stats = css_core::minify(b"a { color: red }", None, &opts)
+        .unwrap()
+        .stats
+        .unwrap();
+
+    assert_eq!(stats.durations.source_map, 0.0);
+    assert!(stats.durations.parse >= 0.0);
+}
+
+#[test]
+fn transform_returns_stats() {
+    let (opts, _): (TransformOptions, _) = parse_options(r#"{ "stats": true }"#).unwrap();
+    let output = css_core::transform(b"a { color: red }", None, &opts).unwrap();
+    let stats = output.stats.unwrap();
+
+    assert_eq!(stats.input_size, 16);
+    assert_eq!(stats.before.declarations, 1);
+    assert_eq!(stats.output_size as usize, output.code.len());
+}
+
+#[test]
+fn limits_fail_on_large_input() {
+    let opts = MinifyOptions {
+        limits: Limits {
+            max_input_bytes: Some(8),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let err = css_core::minify(b"a { color: red }", None, &opts).unwrap_err();
+
+    assert!(err.to_string().contains("limits.maxInputBytes"));
+    assert_eq!(take_exceeded(), Some(Limit::InputBytes));
+    assert_eq!(Limit::InputBytes.code(), "SWC_LIMIT_INPUT_BYTES");
+    assert_eq!(take_exceeded(), None);
+}
+
+#[test]
+fn limits_fail_on_deep_input() {
+    let opts = MinifyOptions {
+        limits: Limits {
+            max_depth: Some(2),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert!(css_core::minify(b"a { b: c(d) }", None, &opts).is_ok());
+    // Brackets in strings and comments are not counted.
+    assert!(css_core::minify(b"a { b: \"(((\" } /* {{{ */", None, &opts).is_ok());
+
+    assert!(css_core::minify(b"a { b: c(d(e)) }", None, &opts).is_err());
+    assert_eq!(take_exceeded(), Some(Limit::Depth));
+    assert_eq!(Limit::Depth.code(), "SWC_LIMIT_DEPTH");
+}
+
+#[test]
+fn limits_fail_on_many_nodes() {
+    let opts = MinifyOptions {
+        limits: Limits {
+            max_nodes: Some(4),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert!(css_core::minify(b"a { color: red }", None, &opts).is_ok());
+    assert!(css_core::minify(b"a {} b {} c {} d {} e {}", None, &opts).is_err());
+    assert_eq!(take_exceeded(), Some(Limit::Nodes));
+    assert_eq!(Limit::Nodes.code(), "SWC_LIMIT_NODES");
+}
+
+#[test]
+fn limits_fail_after_timeout() {
+    let opts = MinifyOptions {
+        limits: Limits {
+            timeout_ms: Some(0),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let err = css_core::minify(b"a { color: red }", None, &opts).unwrap_err();
+
+    assert!(err.to_string().contains("limits.timeoutMs"));
+    assert_eq!(take_exceeded().map(Limit::code), Some("SWC_LIMIT_TIMEOUT"));
+}
+
+#[test]
+fn aborted_calls_fail() {
+    let flag = AbortFlag::default();
+    let minify = || {
+        abort::scope(Some(&flag), || {
+            css_core::minify(b"a {}", None, &Default::default())
+        })
+    };
+
+    assert!(minify().is_ok());
+
+    flag.abort();
+    assert!(minify().unwrap_err().is::<Aborted>());
+    // The flag is only checked in its scope.
+    assert!(css_core::minify(b"a {}", None, &Default::default()).is_ok());
+}
+
+fn transform_with_options(code: &str, options: &str) -> String {
+    let (opts, _): (TransformOptions, _) = parse_options(options).unwrap();
+
+    css_core::transform(code.as_bytes(), None, &opts)
+        .unwrap()
+        .code
+}
+
+#[test]
+fn prefix_adds_prefixes_for_old_targets() {
+    let code = transform_with_options(
+        "a { user-select: none } ::placeholder { color: red }",
+        r#"{ "prefix": true, "minify": true, "targets": "firefox 40, safari 10" }"#,
+    );
+
+    assert!(code.contains("-webkit-user-select:none"));
+    assert!(code.contains("-moz-user-select:none"));
+    assert!(code.contains("::-moz-placeholder"), "{}", code);
+}
+
+#[test]
+fn prefix_removes_prefixes_for_new_targets() {
+    let code = transform_with_options(
+        "a { -webkit-user-select: none; user-select: none; position: -webkit-sticky; position: \
+         sticky } a::-moz-placeholder { color: red } a::placeholder { color: red } \
+         @-webkit-keyframes k { to { color: red } } @keyframes k { to { color: red } }",
+        r#"{ "prefix": true, "minify": true, "targets": "chrome 120" }"#,
+    );
+
+    assert!(!code.contains("-webkit-"), "{}", code);
+    assert!(!code.contains("-moz-"), "{}", code);
+    assert!(code.contains("position:sticky"));
+    assert!(code.contains("a::placeholder"));
+}
+
+#[test]
+fn prefix_keeps_prefixes_without_counterpart() {
+    let code = transform_with_options(
+        "a::-webkit-scrollbar { width: 0 } a { -webkit-tap-highlight-color: red }",
+        r#"{ "prefix": true, "minify": true, "targets": "chrome 120" }"#,
+    );
+
+    assert!(code.contains("::-webkit-scrollbar"));
+    assert!(code.contains("-webkit-tap-highlight-color"));
+}
+
+#[test]
+#[cfg(feature = "compat")]
+fn targets_select_the_lowered_syntax() {
+    let input = "a { color: #0000; & b { color: red } }";
+
+    let code = transform_with_options(input, r#"{ "minify": true, "targets": "chrome 120" }"#);
+    assert!(code.contains('&'), "{}", code);
+
+    let code = transform_with_options(input, r#"{ "minify": true, "targets": "chrome 60" }"#);
+    assert!(!code.contains('&'), "{}", code);
+    assert!(code.contains("a b{"), "{}", code);
+}
+
+#[test]
+#[cfg(feature = "compat")]
+fn targets_use_the_first_versions_of_caniuse() {
+    let input = "a { & b { color: red } }";
+
+    let code = transform_with_options(input, r#"{ "minify": true, "targets": "safari 17.2" }"#);
+    assert!(code.contains('&'), "{}", code);
+
+    let code = transform_with_options(input, r#"{ "minify": true, "targets": "safari 17.1" }"#);
+    assert!(!code.contains('&'), "{}", code);
+}
+
+#[test]
+fn purge_removes_unused_selectors() {
+    let code = transform_with_options(
+        ".used, .unused { color: red } #id { color: blue } div.used > span { margin: 0 }",
+        r#"{ "minify": true, "purge": { "content": ["<div class=\"used\">"] } }"#,
+    );
+
+    assert_eq!(code, ".used{color:red}");
+}
+
+#[test]
+fn purge_keeps_safelisted_names() {
+    let code = transform_with_options(
+        ".btn-primary { color: red } .other { color: blue }",
+        r#"{ "minify": true, "purge": { "content": [""], "safelist": ["^btn-"] } }"#,
+    );
+
+    assert_eq!(code, ".btn-primary{color:red}");
+}
+
+#[test]
+fn purge_removes_unreferenced_keyframes_and_font_faces() {
+    let code = transform_with_options(
+        "@keyframes spin { to { opacity: 0 } } @keyframes unused { to { opacity: 0 } } @font-face \
+         { font-family: Used; src: url(a.woff) } @font-face { font-family: Unused; src: \
+         url(b.woff) } .a { animation: spin 1s; font-family: Used, serif }",
+        r#"{ "minify": true, "purge": { "content": ["a"] } }"#,
+    );
+
+    assert!(code.contains("@keyframes spin"), "{}", code);
+    assert!(code.contains("font-family:Used;"), "{}", code);
+    assert!(!code.contains("unused"), "{}", code);
+    assert!(!code.contains("Unused"), "{}", code);
+}
+
+#[test]
+fn purge_keeps_keyframes_referenced_through_custom_properties() {
+    let code = transform_with_options(
+        ":root { --anim: var(--spin); --spin: spin; --font: \"Open Sans\" } @keyframes spin { to \
+         { opacity: 0 } } @font-face { font-family: \"Open Sans\"; src: url(a.woff) } .a { \
+         animation: var(--anim) 1s; font: 1em var(--font) }",
+        r#"{ "minify": true, "purge": { "content": ["a"] } }"#,
+    );
+
+    assert!(code.contains("@keyframes spin"), "{}", code);
+    assert!(code.contains("@font-face"), "{}", code);
+}
+
+#[test]
+fn purge_removes_keyframes_only_used_by_removed_rules() {
+    let code = transform_with_options(
+        ":root { --font: Unused } @keyframes spin { to { opacity: 0 } } @font-face { font-family: \
+         Unused; src: url(a.woff) } .unused { animation: spin 1s; --font: Unused } .a { \
+         font-family: var(--font) }",
+        r#"{ "minify": true, "purge": { "content": ["a"] } }"#,
+    );
+
+    assert!(!code.contains("@keyframes"), "{}", code);
+    assert!(!code.contains(".unused"), "{}", code);
+    // Still referenced through `--font` of `:root`.
+    assert!(code.contains("@font-face"), "{}", code);
+}
+
+fn resolve_custom_properties(code: &str) -> (String, Vec<String>) {
+    let (opts, _): (TransformOptions, _) =
+        parse_options(r#"{ "minify": true, "customProperties": { "preserve": false } }"#).unwrap();
+
+    let output = css_core::transform(code.as_bytes(), None, &opts).unwrap();
+    let warnings = output
+        .errors
+        .unwrap_or_default()
+        .into_iter()
+        .map(|d| d.message)
+        .collect();
+
+    (output.code, warnings)
+}
+
+#[test]
+fn custom_properties_use_fallbacks() {
+    let (code, warnings) = resolve_custom_properties(
+        ":root { --a: red; --b: var(--missing, var(--a)) } a { color: var(--b) } b { color: \
+         var(--missing, blue) }",
+    );
+
+    assert!(code.contains("a{color:red}"), "{}", code);
+    assert!(code.contains("b{color:blue}"), "{}", code);
+    assert_eq!(warnings, Vec::<String>::new());
+}
+
+#[test]
+fn custom_properties_report_cycles() {
+    let (code, warnings) = resolve_custom_properties(
+        ":root { --a: var(--b); --b: var(--a) } a { color: var(--a) } b { color: var(--a, blue) }",
+    );
+
+    assert!(code.contains("a{color:var(--a)}"), "{}", code);
+    assert!(code.contains("b{color:blue}"), "{}", code);
+    assert!(
+        warnings
+            .iter()
+            .all(|w| w.contains("`--a` has a cyclic reference: --a -> --b -> --a")),
+        "{:?}",
+        warnings
+    );
+    assert_eq!(warnings.len(), 2);
+}
+
+#[test]
+fn custom_properties_report_undefined_ones_for_each_usage() {
+    let (_, warnings) = resolve_custom_properties(
+        ":root { --a: var(--missing) } a { color: var(--a) } b { color: var(--a) }",
+    );
+
+    assert_eq!(
+        warnings,
+        vec!["custom property `--missing` is not defined"; 2]
+    );
+}
+
+#[test]
+fn custom_properties_reject_long_values() {
+    // Each property doubles the length of the previous one, so `--p40` would
+    // be terabytes long.
+    let mut code = ":root { --p0: x;".to_string();
+    for i in 1..=40 {
+        code.push_str(&format!(" --p{}: var(--p{}) var(--p{});", i, i - 1, i - 1));
+    }
+    code.push_str(" } a { color: var(--p40, red) } b { color: var(--p4) }");
+
+    let (code, warnings) = resolve_custom_properties(&code);
+
+    assert!(code.contains("a{color:red}"), "{}", code);
+    assert!(
+        code.contains("b{color:x x x x x x x x x x x x x x x x}"),
+        "{}",
+        code
+    );
+    assert_eq!(
+        warnings,
+        vec!["the resolved value of custom properties is longer than 65536 bytes"]
+    );
+}
+
+#[test]
+fn options_ts_is_generated_from_the_schema() {
+    let schema = css_core::schema::options_schema().unwrap();
+    let generated = extra_common::schema::typescript_types(
+        &schema,
+        &[
+            ("minify", "MinifyOptions"),
+            ("transform", "TransformOptions"),
+            ("format", "FormatOptions"),
+            ("lint", "LintOptions"),
+        ],
+    );
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../packages/css/options.ts");
+
+    if let Err(err) = extra_common::schema::check_generated(&path, &generated) {
+        panic!("{}", err);
+    }
+}
@@ -0,0 +1,70 @@
+use css_core::TransformOutput;
+pub use extra_common::node::batch::File;
+use napi::bindgen_prelude::Buffer;
+use serde::de::DeserializeOwned;
+
+use crate::cache::cached;
+
+#[cfg_attr(not(feature = "namespace"), napi_derive::napi(object))]
+#[cfg_attr(feature = "namespace", napi_derive::napi(object, namespace = "css"))]
+pub struct FileInput {
+    pub code: Buffer,
+    /// Overrides `filename` of the shared options.
+    pub filename: Option<String>,
+}
+
+#[cfg_attr(not(feature = "namespace"), napi_derive::napi(object))]
+#[cfg_attr(feature = "namespace", napi_derive::napi(object, namespace = "css"))]
+pub struct FileOutput {
+    pub filename: Option<String>,
+    pub output: Option<TransformOutput>,
+    /// The message of the error, if this file failed.
+    pub error: Option<String>,
+}
+
+impl From<FileInput> for File {
+    fn from(input: FileInput) -> Self {
+        File {
+            code: input.code.to_vec(),
+            filename: input.filename,
+        }
+    }
+}
+
+/// Runs [extra_common::node::batch::run_batch] with `op`, whose results are
+/// [cached] as `kind`.
+pub fn run_batch<O, F>(
+    kind: &str,
+    files: &[File],
+    options: &str,
+    op: F,
+) -> anyhow::Result<Vec<FileOutput>>
+where
+    O: DeserializeOwned + Sync,
+    F: Fn(&[u8], Option<&str>, &O) -> anyhow::Result<TransformOutput> + Sync,
+{
+    let results = extra_common::node::batch::run_batch(files, options, |file, opts, warnings| {
+        let filename = file.filename.as_deref();
+
+        cached(kind, options, &file.code, filename, || {
+            op(&file.code, filename, opts).map(|output| output.with_warnings(warnings))
+        })
+    })?;
+
+    Ok(files
+        .iter()
+        .zip(results)
+        .map(|(file, result)| match result {
+            Ok(output) => FileOutput {
+                filename: file.filename.clone(),
+                output: Some(output),
+                error: None,
+            },
+            Err(error) => FileOutput {
+                filename: file.filename.clone(),
+                output: None,
+                error: Some(error),
+            },
+        })
+        .collect())
+}
@@ -0,0 +1,201 @@
+//! Handles which deserialize and validate options once, so they can be reused
+//! for many inputs.
+//!
+//! The warnings about unknown options are reported in the output of every
+//! run.
+
+use anyhow::Context;
+use css_core::{MinifyOptions, TransformOptions, TransformOutput};
+use extra_common::node::{
+    abort::{abortable, AbortSignal},
+    handle::Prepared,
+};
+use napi::{bindgen_prelude::*, Env, Task};
+use swc_nodejs_common::MapErr;
+
+use crate::{
+    cache::cached,
+    panic::{ReportPanic, Reported},
+};
+
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "css"))]
+pub struct Transformer {
+    prepared: Prepared<TransformOptions>,
+}
+
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "css"))]
+pub struct Minifier {
+    prepared: Prepared<MinifyOptions>,
+}
+
+pub struct RunTransformerTask {
+    code: Vec<u8>,
+    filename: Option<String>,
+    prepared: Prepared<TransformOptions>,
+}
+
+pub struct RunMinifierTask {
+    code: Vec<u8>,
+    filename: Option<String>,
+    prepared: Prepared<MinifyOptions>,
+}
+
+#[napi]
+impl Task for RunTransformerTask {
+    type JsValue = TransformOutput;
+    type Output = TransformOutput;
+
+    fn compute(&mut self) -> napi::Result<Self::Output> {
+        let filename = self.filename.as_deref();
+
+        cached(
+            "transform",
+            &self.prepared.json,
+            &self.code,
+            filename,
+            || {
+                css_core::transform(&self.code, filename, &self.prepared.options)
+                    .map(|output| output.with_warnings(&self.prepared.warnings))
+            },
+        )
+        .convert_err()
+    }
+
+    fn resolve(&mut self, _env: napi::Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+        Ok(output)
+    }
+}
+
+#[napi]
+impl Task for RunMinifierTask {
+    type JsValue = TransformOutput;
+    type Output = TransformOutput;
+
+    fn compute(&mut self) -> napi::Result<Self::Output> {
+        let filename = self.filename.as_deref();
+
+        cached("minify", &self.prepared.json, &self.code, filename, || {
+            css_core::minify(&self.code, filename, &self.prepared.options)
+                .map(|output| output.with_warnings(&self.prepared.warnings))
+        })
+        .convert_err()
+    }
+
+    fn resolve(&mut self, _env: napi::Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+        Ok(output)
+    }
+}
+
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "css"))]
+impl Transformer {
+    /// `filename` overrides the one of the options.
+    ///
+    /// `signal` aborts it while parsing, between phases and while walking
+    /// the tree; a running minifier or lint rule of swc is not interrupted.
+    #[napi]
+    pub fn run(
+        &self,
+        code: Buffer,
+        filename: Option<String>,
+        signal: Option<AbortSignal>,
+    ) -> AsyncTask<Reported<RunTransformerTask>> {
+        let code = code.to_vec();
+
+        let task = RunTransformerTask {
+            code,
+            filename,
+            prepared: self.prepared.clone(),
+        };
+
+        abortable(task, signal)
+    }
+
+    /// `filename` overrides the one of the options.
+    #[napi]
+    pub fn run_sync(
+        &self,
+        env: Env,
+        code: Buffer,
+        filename: Option<String>,
+    ) -> napi::Result<TransformOutput> {
+        let filename = filename.as_deref();
+
+        cached("transform", &self.prepared.json, &code, filename, || {
+            css_core::transform(&code, filename, &self.prepared.options)
+                .map(|output| output.with_warnings(&self.prepared.warnings))
+        })
+        .convert_err()
+        .report_panic(&env)
+    }
+}
+
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "css"))]
+impl Minifier {
+    /// `filename` overrides the one of the options.
+    ///
+    /// `signal` aborts it while parsing, between phases and while walking
+    /// the tree; a running minifier or lint rule of swc is not interrupted.
+    #[napi]
+    pub fn run(
+        &self,
+        code: Buffer,
+        filename: Option<String>,
+        signal: Option<AbortSignal>,
+    ) -> AsyncTask<Reported<RunMinifierTask>> {
+        let code = code.to_vec();
+
+        let task = RunMinifierTask {
+            code,
+            filename,
+            prepared: self.prepared.clone(),
+        };
+
+        abortable(task, signal)
+    }
+
+    /// `filename` overrides the one of the options.
+    #[napi]
+    pub fn run_sync(
+        &self,
+        env: Env,
+        code: Buffer,
+        filename: Option<String>,
+    ) -> napi::Result<TransformOutput> {
+        let filename = filename.as_deref();
+
+        cached("minify", &self.prepared.json, &code, filename, || {
+            css_core::minify(&code, filename, &self.prepared.options)
+                .map(|output| output.with_warnings(&self.prepared.warnings))
+        })
+        .convert_err()
+        .report_panic(&env)
+    }
+}
+
+#[allow(unused)]
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "css"))]
+pub fn create_transformer(opts: Buffer) -> napi::Result<Transformer> {
+    let prepared: Prepared<TransformOptions> = Prepared::parse(&opts)
+        .context("failed to deserialize transform options")
+        .convert_err()?;
+
+    prepared.options.validate().convert_err()?;
+
+    Ok(Transformer { prepared })
+}
+
+#[allow(unused)]
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "css"))]
+pub fn create_minifier(opts: Buffer) -> napi::Result<Minifier> {
+    let prepared = Prepared::parse(&opts)
+        .context("failed to deserialize minifier options")
+        .convert_err()?;
+
+    Ok(Minifier { prepared })
+}
@@ -1,224 +1,154 @@
 #[macro_use]
 extern crate napi_derive;
 
-use std::{
-    backtrace::Backtrace, collections::HashMap, env, fmt::Write, panic::set_hook, sync::Arc,
+use anyhow::Context;
+use css_core::{parse_options, rewrite_url::UrlRewriter, LintOutput, TransformOutput};
+use extra_common::node::{
+    abort::{abortable, AbortSignal},
+    js_options::{parse_js_options, JsOptions},
 };
-
-use anyhow::{bail, Context};
-use napi::{bindgen_prelude::*, Task};
-use serde::{Deserialize, Serialize};
-use swc_atoms::JsWord;
-use swc_common::FileName;
-use swc_css_codegen::{
-    writer::basic::{BasicCssWriter, BasicCssWriterConfig, IndentType, LineFeed},
-    CodeGenerator, CodegenConfig, Emit,
-};
-use swc_css_compat::{
-    compiler::{Compiler, Config},
-    feature::Features,
+use napi::{
+    bindgen_prelude::*,
+    threadsafe_function::{ErrorStrategy, ThreadSafeCallContext, ThreadsafeFunction},
+    Env, JsObject, Task,
 };
-use swc_css_visit::{VisitMutWith, VisitWith};
-use swc_nodejs_common::{deserialize_json, get_deserialized, MapErr};
+use swc_nodejs_common::MapErr;
 
-use crate::util::try_with;
+use crate::{
+    batch::{run_batch, File, FileInput, FileOutput},
+    panic::{ReportPanic, Reported},
+    rewrite_url::{call_sync, call_threadsafe, never_throwing},
+};
 
-mod deps;
-mod util;
+mod batch;
+mod cache;
+mod handle;
+mod panic;
+mod rewrite_url;
+mod trace;
 
 #[napi::module_init]
 fn init() {
-    if cfg!(debug_assertions) || env::var("SWC_DEBUG").unwrap_or_default() == "1" {
-        set_hook(Box::new(|panic_info| {
-            let backtrace = Backtrace::force_capture();
-            println!("Panic: {:?}\nBacktrace: {:?}", panic_info, backtrace);
-        }));
-    }
+    css_core::panic::install_hook();
 }
 
-#[napi_derive::napi(object)]
-#[derive(Debug, Serialize)]
-pub struct Diagnostic {
-    pub level: String,
-    pub message: String,
-    pub span: serde_json::Value,
+struct MinifyTask {
+    code: Vec<u8>,
+    options: String,
 }
 
-#[napi_derive::napi(object)]
-#[derive(Debug, Serialize)]
-pub struct TransformOutput {
-    pub code: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub map: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub errors: Option<Vec<Diagnostic>>,
+struct TransformTask {
+    code: Vec<u8>,
+    options: String,
+    rewrite_url: Option<ThreadsafeFunction<String, ErrorStrategy::Fatal>>,
+}
 
-    /// JSON string.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub deps: Option<String>,
+struct TransformManyTask {
+    files: Vec<File>,
+    options: String,
+}
 
-    /// JSON string.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub modules_mapping: Option<String>,
+struct MinifyManyTask {
+    files: Vec<File>,
+    options: String,
 }
 
-struct MinifyTask {
-    code: String,
+struct FormatTask {
+    code: Vec<u8>,
     options: String,
 }
 
-struct TransformTask {
-    code: String,
+struct LintTask {
+    code: Vec<u8>,
     options: String,
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct MinifyOptions {
-    #[serde(default)]
-    filename: Option<String>,
+#[napi]
+impl Task for TransformTask {
+    type JsValue = TransformOutput;
+    type Output = TransformOutput;
 
-    #[serde(default)]
-    source_map: bool,
-}
+    fn compute(&mut self) -> napi::Result<Self::Output> {
+        let rewrite_url = self
+            .rewrite_url
+            .as_ref()
+            .map(|f| move |url: &str| call_threadsafe(f, url));
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct TransformOptions {
-    #[serde(default)]
-    filename: Option<String>,
+        transform_json(
+            &self.code,
+            None,
+            &self.options,
+            rewrite_url.as_ref().map(|f| f as UrlRewriter),
+        )
+        .convert_err()
+    }
 
-    #[serde(default)]
-    source_map: bool,
+    fn resolve(&mut self, _env: napi::Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+        Ok(output)
+    }
+}
 
-    #[serde(default)]
-    css_modules: Option<CssModulesConfig>,
+#[napi]
+impl Task for TransformManyTask {
+    type JsValue = Vec<FileOutput>;
+    type Output = Vec<FileOutput>;
 
-    #[serde(default)]
-    minify: bool,
+    fn compute(&mut self) -> napi::Result<Self::Output> {
+        run_batch("transform", &self.files, &self.options, css_core::transform).convert_err()
+    }
 
-    #[serde(default)]
-    analyze_dependencies: bool,
+    fn resolve(&mut self, _env: napi::Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+        Ok(output)
+    }
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct CssModulesConfig {
-    pattern: String,
-}
+#[napi]
+impl Task for MinifyManyTask {
+    type JsValue = Vec<FileOutput>;
+    type Output = Vec<FileOutput>;
 
-#[derive(Debug)]
-struct CssModuleTransformConfig {
-    file_name: Arc<FileName>,
-    file_name_hash: u8,
-    pattern: Vec<CssClassNameSegment>,
-}
+    fn compute(&mut self) -> napi::Result<Self::Output> {
+        run_batch("minify", &self.files, &self.options, css_core::minify).convert_err()
+    }
 
-#[derive(Debug)]
-enum CssClassNameSegment {
-    /// A literal string segment.
-    Literal(JsWord),
-    /// The base file name.
-    Name,
-    /// The original class name.
-    Local,
-    /// A hash of the file name.
-    Hash,
+    fn resolve(&mut self, _env: napi::Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+        Ok(output)
+    }
 }
 
-#[derive(Serialize)]
-#[serde(tag = "type")]
-pub enum CssClassName {
-    Local { name: JsWord },
-    Global { name: JsWord },
-    Import { name: JsWord, from: JsWord },
-}
+#[napi]
+impl Task for FormatTask {
+    type JsValue = TransformOutput;
+    type Output = TransformOutput;
 
-impl swc_css_modules::TransformConfig for CssModuleTransformConfig {
-    fn new_name_for(&self, local: &JsWord) -> JsWord {
-        let mut buf = String::new();
+    fn compute(&mut self) -> napi::Result<Self::Output> {
+        let (opts, warnings) = parse_options(&self.options)
+            .context("failed to deserialize format options")
+            .convert_err()?;
 
-        for segment in &self.pattern {
-            match segment {
-                CssClassNameSegment::Literal(s) => buf.push_str(s),
-                CssClassNameSegment::Name => match &*self.file_name {
-                    FileName::Real(f) => {
-                        write!(buf, "{}", f.file_stem().unwrap().to_str().unwrap()).unwrap();
-                    }
-                    FileName::Anon => buf.push_str("[anon]"),
-                    _ => {
-                        unreachable!("CssModuleTransformConfig::new_name_for: invalid file name")
-                    }
-                },
-                CssClassNameSegment::Local => buf.push_str(local),
-                CssClassNameSegment::Hash => {
-                    write!(buf, "{:x}", self.file_name_hash).unwrap();
-                }
-            }
-        }
-
-        buf.into()
+        css_core::format(&self.code, None, &opts)
+            .map(|output| output.with_warnings(&warnings))
+            .convert_err()
     }
-}
 
-impl CssModulesConfig {
-    /// Adapted from lightningcss
-    fn parse_pattern(&self) -> anyhow::Result<Vec<CssClassNameSegment>> {
-        let mut res = Vec::with_capacity(2);
-
-        let mut idx = 0;
-
-        let mut s = &*self.pattern;
-
-        while !s.is_empty() {
-            if s.starts_with('[') {
-                if let Some(end_idx) = s.find(']') {
-                    let segment = match &s[0..=end_idx] {
-                        "[name]" => CssClassNameSegment::Name,
-                        "[local]" => CssClassNameSegment::Local,
-                        "[hash]" => CssClassNameSegment::Hash,
-                        s => {
-                            bail!(
-                                "Unknown placeholder {} at {} in CSS Modules pattern: {}",
-                                s,
-                                idx,
-                                self.pattern
-                            )
-                        }
-                    };
-                    res.push(segment);
-                    idx += end_idx + 1;
-                    s = &s[end_idx + 1..];
-                } else {
-                    bail!(
-                        "Unclosed brackets at {} in CSS Modules pattern: {}",
-                        idx,
-                        self.pattern
-                    )
-                }
-            } else {
-                let end_idx = s.find('[').unwrap_or(s.len());
-                res.push(CssClassNameSegment::Literal(s[0..end_idx].into()));
-                idx += end_idx;
-                s = &s[end_idx..];
-            }
-        }
-
-        Ok(res)
+    fn resolve(&mut self, _env: napi::Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+        Ok(output)
     }
 }
 
 #[napi]
-impl Task for TransformTask {
-    type JsValue = TransformOutput;
-    type Output = TransformOutput;
+impl Task for LintTask {
+    type JsValue = LintOutput;
+    type Output = LintOutput;
 
     fn compute(&mut self) -> napi::Result<Self::Output> {
-        let opts = deserialize_json(&self.options)
-            .context("failed to deserialize transform options")
+        let (opts, warnings) = parse_options(&self.options)
+            .context("failed to deserialize lint options")
             .convert_err()?;
 
-        transform_inner(&self.code, opts).convert_err()
+        css_core::lint(&self.code, None, &opts)
+            .map(|output| output.with_warnings(&warnings))
+            .convert_err()
     }
 
     fn resolve(&mut self, _env: napi::Env, output: Self::Output) -> napi::Result<Self::JsValue> {
@@ -232,11 +162,7 @@ impl Task for MinifyTask {
     type Output = TransformOutput;
 
     fn compute(&mut self) -> napi::Result<Self::Output> {
-        let opts = deserialize_json(&self.options)
-            .context("failed to deserialize minifier options")
-            .convert_err()?;
-
-        minify_inner(&self.code, opts).convert_err()
+        minify_json(&self.code, None, &self.options).convert_err()
     }
 
     fn resolve(&mut self, _env: napi::Env, output: Self::Output) -> napi::Result<Self::JsValue> {
@@ -244,314 +170,407 @@ impl Task for MinifyTask {
     }
 }
 
-fn minify_inner(code: &str, opts: MinifyOptions) -> anyhow::Result<TransformOutput> {
-    swc_common::GLOBALS.set(&swc_common::Globals::new(), || {
-        try_with(|cm, handler| {
-            let filename = match opts.filename {
-                Some(v) => FileName::Real(v.into()),
-                None => FileName::Anon,
-            };
-
-            let fm = cm.new_source_file(filename, code.into());
-
-            let mut errors = vec![];
-            let ss = swc_css_parser::parse_file::<swc_css_ast::Stylesheet>(
-                &fm,
-                None,
-                swc_css_parser::parser::ParserConfig {
-                    allow_wrong_line_comments: false,
-                    css_modules: false,
-                    legacy_nesting: false,
-                    legacy_ie: false,
-                },
-                &mut errors,
-            );
-
-            let mut ss = match ss {
-                Ok(v) => v,
-                Err(err) => {
-                    err.to_diagnostics(handler).emit();
-
-                    for err in errors {
-                        err.to_diagnostics(handler).emit();
-                    }
-
-                    bail!("failed to parse input as stylesheet")
-                }
-            };
-
-            let mut returned_errors = None;
-
-            if !errors.is_empty() {
-                returned_errors = Some(Vec::with_capacity(errors.len()));
-
-                for err in errors {
-                    let mut buf = vec![];
-
-                    err.to_diagnostics(handler).buffer(&mut buf);
-
-                    for i in buf {
-                        returned_errors.as_mut().unwrap().push(Diagnostic {
-                            level: i.level.to_string(),
-                            message: i.message(),
-                            span: serde_json::to_value(&i.span)?,
-                        });
-                    }
-                }
-            }
-
-            swc_css_minifier::minify(&mut ss, Default::default());
-
-            let mut src_map = vec![];
-            let code = {
-                let mut buf = String::new();
-                {
-                    let wr = BasicCssWriter::new(
-                        &mut buf,
-                        if opts.source_map {
-                            Some(&mut src_map)
-                        } else {
-                            None
-                        },
-                        BasicCssWriterConfig {
-                            indent_type: IndentType::Space,
-                            indent_width: 0,
-                            linefeed: LineFeed::LF,
-                        },
-                    );
-                    let mut gen = CodeGenerator::new(wr, CodegenConfig { minify: true });
-
-                    gen.emit(&ss).context("failed to emit")?;
-                }
-
-                buf
-            };
-
-            let map = if opts.source_map {
-                let map = cm.build_source_map(&src_map);
-                let mut buf = vec![];
-                map.to_writer(&mut buf)
-                    .context("failed to generate sourcemap")?;
-                Some(String::from_utf8(buf).context("the generated source map is not utf8")?)
-            } else {
-                None
-            };
-
-            Ok(TransformOutput {
-                code,
-                map,
-                errors: returned_errors,
-                deps: Default::default(),
-                modules_mapping: Default::default(),
-            })
-        })
+/// [css_core::minify] with the options deserialized from `options`, through
+/// the [cache].
+fn minify_json(
+    code: &[u8],
+    filename: Option<&str>,
+    options: &str,
+) -> anyhow::Result<TransformOutput> {
+    cache::cached("minify", options, code, filename, || {
+        let (opts, warnings) =
+            parse_options(options).context("failed to deserialize minifier options")?;
+
+        css_core::minify(code, filename, &opts).map(|output| output.with_warnings(&warnings))
     })
 }
 
-fn transform_inner(code: &str, opts: TransformOptions) -> anyhow::Result<TransformOutput> {
-    try_with(|cm, handler| {
-        let filename = match opts.filename {
-            Some(v) => FileName::Real(v.into()),
-            None => FileName::Anon,
-        };
+/// [css_core::transform_with] with the options deserialized from `options`,
+/// through the [cache] unless URLs are rewritten, as `rewrite_url` may return
+/// other URLs.
+fn transform_json(
+    code: &[u8],
+    filename: Option<&str>,
+    options: &str,
+    rewrite_url: Option<UrlRewriter>,
+) -> anyhow::Result<TransformOutput> {
+    let transform = || {
+        let (opts, warnings) =
+            parse_options(options).context("failed to deserialize transform options")?;
+
+        css_core::transform_with(code, filename, &opts, rewrite_url)
+            .map(|output| output.with_warnings(&warnings))
+    };
+
+    match rewrite_url {
+        Some(_) => transform(),
+        None => cache::cached("transform", options, code, filename, transform),
+    }
+}
 
-        let fm = cm.new_source_file(filename, code.into());
+/// `signal` aborts it while parsing, between phases and while walking
+/// the tree; a running minifier or lint rule of swc is not interrupted.
+#[allow(unused)]
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "css"))]
+fn minify(
+    code: Buffer,
+    opts: Buffer,
+    signal: Option<AbortSignal>,
+) -> AsyncTask<Reported<MinifyTask>> {
+    let code = code.to_vec();
+    let options = String::from_utf8_lossy(opts.as_ref()).to_string();
 
-        let mut errors = vec![];
-        let ss = swc_css_parser::parse_file::<swc_css_ast::Stylesheet>(
-            &fm,
-            None,
-            swc_css_parser::parser::ParserConfig {
-                allow_wrong_line_comments: false,
-                css_modules: opts.css_modules.is_some(),
-                legacy_nesting: false,
-                legacy_ie: false,
-            },
-            &mut errors,
-        );
-
-        let mut ss = match ss {
-            Ok(v) => v,
-            Err(err) => {
-                err.to_diagnostics(handler).emit();
-
-                for err in errors {
-                    err.to_diagnostics(handler).emit();
-                }
-
-                bail!("failed to parse input as stylesheet")
-            }
-        };
-
-        let deps = if opts.analyze_dependencies {
-            let mut v = deps::Analyzer::default();
-
-            ss.visit_with(&mut v);
-
-            Some(v.deps)
-        } else {
-            None
-        };
-
-        let mut returned_errors = None;
-
-        if !errors.is_empty() {
-            returned_errors = Some(Vec::with_capacity(errors.len()));
-
-            for err in errors {
-                let mut buf = vec![];
-
-                err.to_diagnostics(handler).buffer(&mut buf);
-
-                for i in buf {
-                    returned_errors.as_mut().unwrap().push(Diagnostic {
-                        level: i.level.to_string(),
-                        message: i.message(),
-                        span: serde_json::to_value(&i.span)?,
-                    });
-                }
-            }
-        }
-
-        let modules_mapping = if let Some(config) = opts.css_modules {
-            let result = swc_css_modules::compile(
-                &mut ss,
-                CssModuleTransformConfig {
-                    file_name: Arc::new(fm.name.clone()),
-                    file_name_hash: fm.name_hash as _,
-                    pattern: config
-                        .parse_pattern()
-                        .context("failed to parse the pattern for CSS Modules")?,
-                },
-            );
-            let map: HashMap<_, _> = result
-                .renamed
-                .into_iter()
-                .map(|(k, v)| {
-                    (
-                        k,
-                        v.into_iter()
-                            .map(|v| match v {
-                                swc_css_modules::CssClassName::Local { name } => {
-                                    CssClassName::Local { name: name.value }
-                                }
-                                swc_css_modules::CssClassName::Global { name } => {
-                                    CssClassName::Global { name: name.value }
-                                }
-                                swc_css_modules::CssClassName::Import { name, from } => {
-                                    CssClassName::Import {
-                                        name: name.value,
-                                        from,
-                                    }
-                                }
-                            })
-                            .collect::<Vec<_>>(),
-                    )
-                })
-                .collect();
-            Some(
-                serde_json::to_string(&map)
-                    .context("failed to serialize the mapping for CSS Modules")?,
-            )
-        } else {
-            None
-        };
-
-        ss.visit_mut_with(&mut Compiler::new(Config {
-            // TODO: preset-env
-            process: Features::all(),
-        }));
-
-        let mut src_map = vec![];
-        let code = {
-            let mut buf = String::new();
-            {
-                let wr = BasicCssWriter::new(
-                    &mut buf,
-                    if opts.source_map {
-                        Some(&mut src_map)
-                    } else {
-                        None
-                    },
-                    if opts.minify {
-                        BasicCssWriterConfig {
-                            indent_type: IndentType::Space,
-                            indent_width: 0,
-                            linefeed: LineFeed::LF,
-                        }
-                    } else {
-                        BasicCssWriterConfig::default()
-                    },
-                );
-                let mut gen = CodeGenerator::new(
-                    wr,
-                    CodegenConfig {
-                        minify: opts.minify,
-                    },
-                );
-
-                gen.emit(&ss).context("failed to emit")?;
-            }
-
-            buf
-        };
-
-        let map = if opts.source_map {
-            let map = cm.build_source_map(&src_map);
-            let mut buf = vec![];
-            map.to_writer(&mut buf)
-                .context("failed to generate sourcemap")?;
-            Some(String::from_utf8(buf).context("the generated source map is not utf8")?)
-        } else {
-            None
-        };
-
-        Ok(TransformOutput {
-            code,
-            map,
-            errors: returned_errors,
-            deps: deps.map(|v| serde_json::to_string(&v).unwrap()),
-            modules_mapping,
-        })
-    })
+    let task = MinifyTask { code, options };
+
+    abortable(task, signal)
 }
 
 #[allow(unused)]
-#[napi]
-fn minify(code: Buffer, opts: Buffer, signal: Option<AbortSignal>) -> AsyncTask<MinifyTask> {
-    let code = String::from_utf8_lossy(code.as_ref()).to_string();
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "css"))]
+pub fn minify_sync(env: Env, code: Buffer, opts: Buffer) -> napi::Result<TransformOutput> {
+    minify_json(&code, None, &String::from_utf8_lossy(opts.as_ref()))
+        .convert_err()
+        .report_panic(&env)
+}
+
+/// `signal` aborts it while parsing, between phases and while walking
+/// the tree; a running minifier or lint rule of swc is not interrupted.
+#[allow(unused)]
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "css"))]
+fn transform(
+    code: Buffer,
+    opts: Buffer,
+    signal: Option<AbortSignal>,
+) -> AsyncTask<Reported<TransformTask>> {
+    let code = code.to_vec();
     let options = String::from_utf8_lossy(opts.as_ref()).to_string();
 
-    let task = MinifyTask { code, options };
+    let task = TransformTask {
+        code,
+        options,
+        rewrite_url: None,
+    };
 
-    AsyncTask::with_optional_signal(task, signal)
+    abortable(task, signal)
 }
 
 #[allow(unused)]
-#[napi]
-pub fn minify_sync(code: Buffer, opts: Buffer) -> napi::Result<TransformOutput> {
-    let code = String::from_utf8_lossy(code.as_ref());
-    let opts = get_deserialized(opts)?;
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "css"))]
+pub fn transform_sync(env: Env, code: Buffer, opts: Buffer) -> napi::Result<TransformOutput> {
+    transform_json(&code, None, &String::from_utf8_lossy(opts.as_ref()), None)
+        .convert_err()
+        .report_panic(&env)
+}
 
-    minify_inner(&code, opts).convert_err()
+/// Transforms `files` in parallel, sharing `opts`.
+///
+/// `signal` aborts it while parsing, between phases and while walking
+/// the tree; a running minifier or lint rule of swc is not interrupted.
+#[allow(unused)]
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "css"))]
+fn transform_many(
+    files: Vec<FileInput>,
+    opts: Buffer,
+    signal: Option<AbortSignal>,
+) -> AsyncTask<Reported<TransformManyTask>> {
+    let files = files.into_iter().map(File::from).collect();
+    let options = String::from_utf8_lossy(opts.as_ref()).to_string();
+
+    let task = TransformManyTask { files, options };
+
+    abortable(task, signal)
 }
 
+/// Minifies `files` in parallel, sharing `opts`.
+///
+/// `signal` aborts it while parsing, between phases and while walking
+/// the tree; a running minifier or lint rule of swc is not interrupted.
 #[allow(unused)]
-#[napi]
-fn transform(code: Buffer, opts: Buffer, signal: Option<AbortSignal>) -> AsyncTask<TransformTask> {
-    let code = String::from_utf8_lossy(code.as_ref()).to_string();
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "css"))]
+fn minify_many(
+    files: Vec<FileInput>,
+    opts: Buffer,
+    signal: Option<AbortSignal>,
+) -> AsyncTask<Reported<MinifyManyTask>> {
+    let files = files.into_iter().map(File::from).collect();
     let options = String::from_utf8_lossy(opts.as_ref()).to_string();
 
-    let task = TransformTask { code, options };
+    let task = MinifyManyTask { files, options };
 
-    AsyncTask::with_optional_signal(task, signal)
+    abortable(task, signal)
 }
 
+/// `signal` aborts it while parsing, between phases and while walking
+/// the tree; a running minifier or lint rule of swc is not interrupted.
 #[allow(unused)]
-#[napi]
-pub fn transform_sync(code: Buffer, opts: Buffer) -> napi::Result<TransformOutput> {
-    let code = String::from_utf8_lossy(code.as_ref());
-    let opts = get_deserialized(opts)?;
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "css"))]
+fn format(
+    code: Buffer,
+    opts: Buffer,
+    signal: Option<AbortSignal>,
+) -> AsyncTask<Reported<FormatTask>> {
+    let code = code.to_vec();
+    let options = String::from_utf8_lossy(opts.as_ref()).to_string();
+
+    let task = FormatTask { code, options };
+
+    abortable(task, signal)
+}
+
+#[allow(unused)]
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "css"))]
+pub fn format_sync(env: Env, code: Buffer, opts: Buffer) -> napi::Result<TransformOutput> {
+    let (opts, warnings) = parse_options(&String::from_utf8_lossy(opts.as_ref()))
+        .context("failed to deserialize format options")
+        .convert_err()?;
+
+    css_core::format(&code, None, &opts)
+        .map(|output| output.with_warnings(&warnings))
+        .convert_err()
+        .report_panic(&env)
+}
+
+/// `signal` aborts it while parsing, between phases and while walking
+/// the tree; a running minifier or lint rule of swc is not interrupted.
+#[allow(unused)]
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "css"))]
+fn lint_css(
+    code: Buffer,
+    opts: Buffer,
+    signal: Option<AbortSignal>,
+) -> AsyncTask<Reported<LintTask>> {
+    let code = code.to_vec();
+    let options = String::from_utf8_lossy(opts.as_ref()).to_string();
+
+    let task = LintTask { code, options };
+
+    abortable(task, signal)
+}
+
+#[allow(unused)]
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "css"))]
+pub fn lint_css_sync(env: Env, code: Buffer, opts: Buffer) -> napi::Result<LintOutput> {
+    let (opts, warnings) = parse_options(&String::from_utf8_lossy(opts.as_ref()))
+        .context("failed to deserialize lint options")
+        .convert_err()?;
+
+    css_core::lint(&code, None, &opts)
+        .map(|output| output.with_warnings(&warnings))
+        .convert_err()
+        .report_panic(&env)
+}
+
+/// Like [minify], with `opts` as a JS object instead of a JSON buffer.
+///
+/// `signal` aborts it while parsing, between phases and while walking
+/// the tree; a running minifier or lint rule of swc is not interrupted.
+#[allow(unused)]
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "css"))]
+fn minify_object(
+    env: Env,
+    code: Buffer,
+    opts: JsObject,
+    signal: Option<AbortSignal>,
+) -> napi::Result<AsyncTask<Reported<MinifyTask>>> {
+    let code = code.to_vec();
+    let options = JsOptions::from_object(&env, opts)
+        .and_then(JsOptions::finish)
+        .context("failed to convert minifier options")
+        .convert_err()?
+        .to_string();
 
-    transform_inner(&code, opts).convert_err()
+    let task = MinifyTask { code, options };
+
+    Ok(abortable(task, signal))
+}
+
+#[allow(unused)]
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "css"))]
+pub fn minify_object_sync(env: Env, code: Buffer, opts: JsObject) -> napi::Result<TransformOutput> {
+    let options = JsOptions::from_object(&env, opts)
+        .and_then(JsOptions::finish)
+        .context("failed to convert minifier options")
+        .convert_err()?
+        .to_string();
+
+    minify_json(&code, None, &options)
+        .convert_err()
+        .report_panic(&env)
+}
+
+/// Like [transform], with `opts` as a JS object instead of a JSON buffer,
+/// which may contain `rewriteUrl`.
+///
+/// `signal` aborts it while parsing, between phases and while walking
+/// the tree; a running minifier or lint rule of swc is not interrupted.
+#[allow(unused)]
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "css"))]
+fn transform_object(
+    env: Env,
+    code: Buffer,
+    opts: JsObject,
+    signal: Option<AbortSignal>,
+) -> napi::Result<AsyncTask<Reported<TransformTask>>> {
+    let code = code.to_vec();
+    let mut options = JsOptions::from_object(&env, opts)
+        .context("failed to convert transform options")
+        .convert_err()?;
+
+    let rewrite_url = options
+        .take_function("rewriteUrl")
+        .map(|f| {
+            never_throwing(&env, f)?.create_threadsafe_function(
+                0,
+                |ctx: ThreadSafeCallContext<String>| {
+                    ctx.env.create_string(&ctx.value).map(|v| vec![v])
+                },
+            )
+        })
+        .transpose()?;
+
+    let options = options
+        .finish()
+        .context("failed to convert transform options")
+        .convert_err()?
+        .to_string();
+
+    let task = TransformTask {
+        code,
+        options,
+        rewrite_url,
+    };
+
+    Ok(abortable(task, signal))
+}
+
+#[allow(unused)]
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "css"))]
+pub fn transform_object_sync(
+    env: Env,
+    code: Buffer,
+    opts: JsObject,
+) -> napi::Result<TransformOutput> {
+    let mut options = JsOptions::from_object(&env, opts)
+        .context("failed to convert transform options")
+        .convert_err()?;
+
+    let rewrite_url = options.take_function("rewriteUrl");
+    let rewrite_url = rewrite_url
+        .as_ref()
+        .map(|f| |url: &str| call_sync(&env, f, url));
+
+    let options = options
+        .finish()
+        .context("failed to convert transform options")
+        .convert_err()?
+        .to_string();
+
+    transform_json(
+        &code,
+        None,
+        &options,
+        rewrite_url.as_ref().map(|f| f as UrlRewriter),
+    )
+    .convert_err()
+    .report_panic(&env)
+}
+
+/// Like [format], with `opts` as a JS object.
+///
+/// `signal` aborts it while parsing, between phases and while walking
+/// the tree; a running minifier or lint rule of swc is not interrupted.
+#[allow(unused)]
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "css"))]
+fn format_object(
+    env: Env,
+    code: Buffer,
+    opts: JsObject,
+    signal: Option<AbortSignal>,
+) -> napi::Result<AsyncTask<Reported<FormatTask>>> {
+    let code = code.to_vec();
+    let options = JsOptions::from_object(&env, opts)
+        .and_then(JsOptions::finish)
+        .context("failed to convert format options")
+        .convert_err()?
+        .to_string();
+
+    let task = FormatTask { code, options };
+
+    Ok(abortable(task, signal))
+}
+
+#[allow(unused)]
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "css"))]
+pub fn format_object_sync(env: Env, code: Buffer, opts: JsObject) -> napi::Result<TransformOutput> {
+    let (opts, warnings) = parse_js_options(&env, opts)
+        .context("failed to deserialize format options")
+        .convert_err()?;
+
+    css_core::format(&code, None, &opts)
+        .map(|output| output.with_warnings(&warnings))
+        .convert_err()
+        .report_panic(&env)
+}
+
+/// Like [lint_css], with `opts` as a JS object.
+///
+/// `signal` aborts it while parsing, between phases and while walking
+/// the tree; a running minifier or lint rule of swc is not interrupted.
+#[allow(unused)]
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "css"))]
+fn lint_css_object(
+    env: Env,
+    code: Buffer,
+    opts: JsObject,
+    signal: Option<AbortSignal>,
+) -> napi::Result<AsyncTask<Reported<LintTask>>> {
+    let code = code.to_vec();
+    let options = JsOptions::from_object(&env, opts)
+        .and_then(JsOptions::finish)
+        .context("failed to convert lint options")
+        .convert_err()?
+        .to_string();
+
+    let task = LintTask { code, options };
+
+    Ok(abortable(task, signal))
+}
+
+#[allow(unused)]
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "css"))]
+pub fn lint_css_object_sync(env: Env, code: Buffer, opts: JsObject) -> napi::Result<LintOutput> {
+    let (opts, warnings) = parse_js_options(&env, opts)
+        .context("failed to deserialize lint options")
+        .convert_err()?;
+
+    css_core::lint(&code, None, &opts)
+        .map(|output| output.with_warnings(&warnings))
+        .convert_err()
+        .report_panic(&env)
+}
+
+/// Returns the JSON Schema of the options, keyed by the function using them.
+#[allow(unused)]
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "css"))]
+pub fn get_options_schema() -> napi::Result<serde_json::Value> {
+    css_core::schema::options_schema().convert_err()
 }
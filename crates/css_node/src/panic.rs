@@ -0,0 +1,15 @@
+//! `setPanicSink`, and the shared [Reported] and [ReportPanic], which throw
+//! the errors of [panics](css_core::panic) with their `code`.
+
+use css_core::panic::set_sink;
+use extra_common::node::panic::parse_sink;
+pub use extra_common::node::panic::{ReportPanic, Reported};
+
+/// Sets where panics are logged: `"stderr"`, which is the default, `"none"`
+/// or the path of a file.
+#[allow(unused)]
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "css"))]
+pub fn set_panic_sink(sink: Option<String>) {
+    set_sink(parse_sink(sink.as_deref()));
+}
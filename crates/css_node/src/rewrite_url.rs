@@ -0,0 +1,111 @@
+//! Calls the `rewriteUrl` function of the transform options, as the
+//! [UrlRewriter](css_core::rewrite_url::UrlRewriter) of a transform.
+
+use std::sync::mpsc;
+
+use anyhow::{anyhow, bail, Context};
+use napi::{
+    bindgen_prelude::ToNapiValue,
+    threadsafe_function::{ErrorStrategy, ThreadsafeFunction, ThreadsafeFunctionCallMode},
+    Env, JsFunction, JsObject, JsUnknown, ValueType,
+};
+
+/// Wraps `f` in a function which does not throw, for a [ThreadsafeFunction]:
+/// napi aborts the process if the function called on the JS thread throws.
+///
+/// The wrapper returns the URL, or an `Error` if `f` throws or does not return
+/// a string, which [call_threadsafe] turns back into an error.
+pub fn never_throwing(env: &Env, f: JsFunction) -> napi::Result<JsFunction> {
+    // `f` is bound as the first argument instead of being captured, as the
+    // closure of a function created by napi is never freed.
+    let wrapper = env.create_function_from_closure("rewriteUrl", |ctx| {
+        let f = ctx.get::<JsFunction>(0)?;
+        let url = ctx.get::<JsUnknown>(1)?;
+
+        let message = match f.call(None, &[url]) {
+            Ok(ret) if ret.get_type()? == ValueType::String => return Ok(ret),
+            Ok(_) => "`rewriteUrl` did not return a string".to_string(),
+            Err(err) => format!("`rewriteUrl` failed: {}", thrown(ctx.env, err)?),
+        };
+
+        Ok(ctx
+            .env
+            .create_error(napi::Error::from_reason(message))?
+            .into_unknown())
+    })?;
+
+    let wrapper = wrapper.coerce_to_object()?;
+    let bind = wrapper.get_named_property::<JsFunction>("bind")?;
+    let bound = bind.call(
+        Some(&wrapper),
+        &[env.get_undefined()?.into_unknown(), f.into_unknown()],
+    )?;
+
+    // Safety: `bind` returns a function.
+    Ok(unsafe { bound.cast() })
+}
+
+/// Calls `f`, wrapped by [never_throwing], on the JS thread from the thread of
+/// a task.
+pub fn call_threadsafe(
+    f: &ThreadsafeFunction<String, ErrorStrategy::Fatal>,
+    url: &str,
+) -> anyhow::Result<String> {
+    let (tx, rx) = mpsc::channel();
+
+    f.call_with_return_value(
+        url.to_string(),
+        ThreadsafeFunctionCallMode::Blocking,
+        move |ret: JsUnknown| {
+            let _ = tx.send(url_or_error(ret));
+            Ok(())
+        },
+    );
+
+    // The sender is dropped without sending if the function is not called, as
+    // the environment is being torn down.
+    rx.recv().context("`rewriteUrl` could not be called")?
+}
+
+/// Calls `f` directly, for the sync functions.
+pub fn call_sync(env: &Env, f: &JsFunction, url: &str) -> anyhow::Result<String> {
+    let ret = match f.call(None, &[env.create_string(url)?]) {
+        Ok(ret) => ret,
+        Err(err) => bail!("`rewriteUrl` failed: {}", thrown(env, err)?),
+    };
+
+    if ret.get_type()? != ValueType::String {
+        bail!("`rewriteUrl` did not return a string")
+    }
+
+    Ok(ret.coerce_to_string()?.into_utf8()?.into_owned()?)
+}
+
+/// Returns the URL returned by the wrapper of [never_throwing], or the message
+/// of its `Error`.
+fn url_or_error(ret: JsUnknown) -> anyhow::Result<String> {
+    if ret.get_type()? == ValueType::String {
+        return Ok(ret.coerce_to_string()?.into_utf8()?.into_owned()?);
+    }
+
+    // Safety: the wrapper returns an `Error` otherwise.
+    let error: JsObject = unsafe { ret.cast() };
+    let message = error
+        .get_named_property::<JsUnknown>("message")?
+        .coerce_to_string()?
+        .into_utf8()?
+        .into_owned()?;
+
+    Err(anyhow!(message))
+}
+
+/// Returns the message of the value thrown by a JS function, releasing the
+/// reference to it held by `err`.
+fn thrown(env: &Env, err: napi::Error) -> napi::Result<String> {
+    let message = err.reason.clone();
+
+    // Safety: `env` is the environment of the call which threw.
+    unsafe { napi::Error::to_napi_value(env.raw(), err) }?;
+
+    Ok(message)
+}
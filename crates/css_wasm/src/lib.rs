@@ -0,0 +1,193 @@
+//! WebAssembly binding of `@swc/css`, for runtimes which cannot load the
+//! native one.
+//!
+//! The functions take options as objects, like `minifyObjectSync` of the
+//! native binding, and return the same outputs. They are synchronous.
+
+use anyhow::Context;
+use css_core::{
+    rewrite_url::UrlRewriter, FormatOptions, LintOptions, MinifyOptions, TransformOptions,
+};
+use js_sys::{Function, Uint8Array};
+use serde::Serialize;
+use wasm_bindgen::{prelude::*, JsCast};
+
+use crate::js_options::{parse_js_options, JsOptions};
+
+mod js_options;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TYPES: &str = r#"
+export interface Diagnostic {
+  level: string
+  message: string
+  /** The id of the lint rule or the error code, if any. */
+  code?: string
+  span?: DiagnosticSpan
+  /** The source code around `span`, rendered like thrown errors. */
+  codeFrame?: string
+  /** True if this error prevented the input from being processed. */
+  fatal: boolean
+}
+export interface DiagnosticSpan {
+  filename: string
+  /** Byte offset from the start of the file. */
+  start: number
+  /** Byte offset from the start of the file. */
+  end: number
+  /** 1-based. */
+  startLine: number
+  /** 1-based, in characters. */
+  startColumn: number
+  /** 1-based. */
+  endLine: number
+  /** 1-based, in characters. */
+  endColumn: number
+}
+export interface TransformOutput {
+  /** The input as-is if it could not be parsed with `recover`. */
+  code: string
+  map?: string
+  errors?: Array<Diagnostic>
+  /** Unknown keys of the options, if `unknownOptions` is `"warn"`. */
+  warnings?: Array<string>
+  /** JSON string. */
+  deps?: string
+  /** JSON string. */
+  modulesMapping?: string
+  /** If `stats` is requested. */
+  stats?: Stats
+}
+export interface LintOutput {
+  diagnostics: Array<Diagnostic>
+  /** Recoverable errors of the parser. */
+  errors?: Array<Diagnostic>
+  /** Unknown keys of the options, if `unknownOptions` is `"warn"`. */
+  warnings?: Array<string>
+}
+export interface Stats {
+  inputSize: number
+  outputSize: number
+  durations: Durations
+  before: Counts
+  after: Counts
+}
+export interface Durations {
+  parse: number
+  transforms: number
+  minify: number
+  codegen: number
+  sourceMap: number
+}
+export interface Counts {
+  rules: number
+  declarations: number
+}
+export function minify(code: string | Uint8Array, opts?: object): TransformOutput
+/** `opts` may contain `rewriteUrl`. */
+export function transform(code: string | Uint8Array, opts?: object): TransformOutput
+export function format(code: string | Uint8Array, opts?: object): TransformOutput
+export function lintCss(code: string | Uint8Array, opts?: object): LintOutput
+"#;
+
+#[wasm_bindgen(skip_typescript)]
+pub fn minify(code: JsValue, opts: JsValue) -> Result<JsValue, JsError> {
+    let code = input(&code)?;
+    let (opts, warnings) = parse_js_options::<MinifyOptions>(&opts)
+        .context("failed to deserialize minifier options")
+        .convert_err()?;
+
+    let output = css_core::minify(&code, None, &opts).convert_err()?;
+
+    to_js(&output.with_warnings(&warnings))
+}
+
+#[wasm_bindgen(skip_typescript)]
+pub fn transform(code: JsValue, opts: JsValue) -> Result<JsValue, JsError> {
+    let code = input(&code)?;
+    let mut options = JsOptions::from_value(&opts)
+        .context("failed to convert transform options")
+        .convert_err()?;
+
+    let rewrite_url = options.take_function("rewriteUrl");
+    let rewrite_url = rewrite_url
+        .as_ref()
+        .map(|f| |url: &str| call_rewrite_url(f, url));
+
+    let (opts, warnings) = options
+        .finish()
+        .and_then(css_core::parse_options_value::<TransformOptions>)
+        .context("failed to deserialize transform options")
+        .convert_err()?;
+
+    let output = css_core::transform_with(
+        &code,
+        None,
+        &opts,
+        rewrite_url.as_ref().map(|f| f as UrlRewriter),
+    )
+    .convert_err()?;
+
+    to_js(&output.with_warnings(&warnings))
+}
+
+#[wasm_bindgen(skip_typescript)]
+pub fn format(code: JsValue, opts: JsValue) -> Result<JsValue, JsError> {
+    let code = input(&code)?;
+    let (opts, warnings) = parse_js_options::<FormatOptions>(&opts)
+        .context("failed to deserialize format options")
+        .convert_err()?;
+
+    let output = css_core::format(&code, None, &opts).convert_err()?;
+
+    to_js(&output.with_warnings(&warnings))
+}
+
+#[wasm_bindgen(js_name = "lintCss", skip_typescript)]
+pub fn lint_css(code: JsValue, opts: JsValue) -> Result<JsValue, JsError> {
+    let code = input(&code)?;
+    let (opts, warnings) = parse_js_options::<LintOptions>(&opts)
+        .context("failed to deserialize lint options")
+        .convert_err()?;
+
+    let output = css_core::lint(&code, None, &opts).convert_err()?;
+
+    to_js(&output.with_warnings(&warnings))
+}
+
+/// A string is encoded as UTF-8, like a `Buffer` created from it.
+fn input(code: &JsValue) -> Result<Vec<u8>, JsError> {
+    if let Some(code) = code.as_string() {
+        return Ok(code.into_bytes());
+    }
+
+    match code.dyn_ref::<Uint8Array>() {
+        Some(code) => Ok(code.to_vec()),
+        None => Err(JsError::new("`code` must be a string or an Uint8Array")),
+    }
+}
+
+fn call_rewrite_url(f: &Function, url: &str) -> anyhow::Result<String> {
+    f.call1(&JsValue::NULL, &JsValue::from_str(url))
+        .ok()
+        .and_then(|v| v.as_string())
+        .context("`rewriteUrl` failed or did not return a string")
+}
+
+/// Like the objects of the native binding, so `None` is omitted.
+fn to_js<T: Serialize>(output: &T) -> Result<JsValue, JsError> {
+    output
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(JsError::from)
+}
+
+/// Like `MapErr` of `swc_nodejs_common`, which keeps the causes.
+trait MapErr<T> {
+    fn convert_err(self) -> Result<T, JsError>;
+}
+
+impl<T> MapErr<T> for anyhow::Result<T> {
+    fn convert_err(self) -> Result<T, JsError> {
+        self.map_err(|err| JsError::new(&format!("{:?}", err)))
+    }
+}
@@ -0,0 +1,90 @@
+//! Run with `wasm-pack test --node crates/css_wasm`.
+
+#![cfg(target_arch = "wasm32")]
+
+use js_sys::{Function, Object, Reflect, Uint8Array, JSON};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn options(json: &str) -> JsValue {
+    JSON::parse(json).unwrap()
+}
+
+fn get(value: &JsValue, key: &str) -> JsValue {
+    Reflect::get(value, &JsValue::from_str(key)).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn minify_string() {
+    let output = css_wasm::minify("a { color: red; }".into(), JsValue::UNDEFINED)
+        .map_err(JsValue::from)
+        .unwrap();
+
+    assert_eq!(get(&output, "code").as_string().unwrap(), "a{color:red}");
+    assert!(get(&output, "map").is_undefined());
+}
+
+#[wasm_bindgen_test]
+fn minify_bytes() {
+    let code = Uint8Array::from(&b"a { color: red; }"[..]);
+    let output = css_wasm::minify(code.into(), Object::new().into())
+        .map_err(JsValue::from)
+        .unwrap();
+
+    assert_eq!(get(&output, "code").as_string().unwrap(), "a{color:red}");
+}
+
+#[wasm_bindgen_test]
+fn minify_rejects_unknown_options() {
+    assert!(css_wasm::minify("a {}".into(), options(r#"{ "foo": 1 }"#)).is_err());
+}
+
+#[wasm_bindgen_test]
+fn transform_returns_camel_case_outputs() {
+    let output = css_wasm::transform(
+        ".foo { color: red }".into(),
+        options(r#"{ "cssModules": { "pattern": "x-[local]" } }"#),
+    )
+    .map_err(JsValue::from)
+    .unwrap();
+
+    assert!(get(&output, "modulesMapping")
+        .as_string()
+        .unwrap()
+        .contains("x-foo"));
+}
+
+#[wasm_bindgen_test]
+fn transform_calls_rewrite_url() {
+    let opts = Object::new();
+    Reflect::set(
+        &opts,
+        &JsValue::from_str("rewriteUrl"),
+        &Function::new_with_args("url", "return '/static/' + url"),
+    )
+    .unwrap();
+
+    let output = css_wasm::transform("a { background: url(a.png) }".into(), opts.into())
+        .map_err(JsValue::from)
+        .unwrap();
+
+    assert!(get(&output, "code")
+        .as_string()
+        .unwrap()
+        .contains("/static/a.png"));
+}
+
+#[wasm_bindgen_test]
+fn lint_css_reports_diagnostics() {
+    let output = css_wasm::lint_css(
+        "a {}".into(),
+        options(r#"{ "rules": { "block-no-empty": "error" } }"#),
+    )
+    .map_err(JsValue::from)
+    .unwrap();
+
+    assert_eq!(
+        js_sys::Array::from(&get(&output, "diagnostics")).length(),
+        1
+    );
+}
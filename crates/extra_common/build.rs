@@ -0,0 +1,16 @@
+use std::{env, fs, hash::Hasher, path::Path};
+
+use siphasher::sip::SipHasher13;
+
+/// Sets `CARGO_LOCK_HASH` to a hash of the `Cargo.lock` of the workspace, so
+/// the cache does not return results of other versions of the dependencies.
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").expect("Manifest dir should exist");
+    let lock = Path::new(&manifest_dir).join("../../Cargo.lock");
+    println!("cargo:rerun-if-changed={}", lock.display());
+
+    let mut hasher = SipHasher13::new();
+    hasher.write(&fs::read(&lock).unwrap_or_default());
+
+    println!("cargo:rustc-env=CARGO_LOCK_HASH={:016x}", hasher.finish());
+}
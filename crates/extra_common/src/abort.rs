@@ -0,0 +1,146 @@
+//! Cooperative cancellation of a call which is already running.
+//!
+//! A call stops once the [AbortFlag] of its [scope] is set or its [deadline]
+//! has passed. Both are checked by [check], which the crates call between
+//! their phases, and through a [Ticker] while parsers read tokens and while
+//! their own visitors walk the tree. The minifiers and lint rules of swc
+//! cannot be interrupted, so they are only checked before and after they run.
+//!
+//! A call which is aborted fails with [Aborted], and one whose deadline has
+//! passed with the [LimitExceeded](crate::limits::LimitExceeded) of
+//! `timeoutMs`.
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant};
+use std::{
+    cell::{Cell, RefCell},
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+#[cfg(target_arch = "wasm32")]
+use web_time::{Duration, Instant};
+
+use crate::limits::{exceeded, Limit};
+
+/// A flag which can be set from another thread to abort a call.
+#[derive(Debug, Clone, Default)]
+pub struct AbortFlag(Arc<AtomicBool>);
+
+impl AbortFlag {
+    pub fn abort(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_aborted(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+#[derive(Debug)]
+pub struct Aborted;
+
+impl fmt::Display for Aborted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the operation was aborted")
+    }
+}
+
+impl std::error::Error for Aborted {}
+
+thread_local! {
+    /// The flag of the innermost [scope] on this thread.
+    static FLAG: RefCell<Option<AbortFlag>> = RefCell::new(None);
+
+    /// The deadline of the innermost call on this thread, with its
+    /// `timeoutMs`.
+    static DEADLINE: Cell<Option<(Instant, u64)>> = Cell::new(None);
+}
+
+/// Runs `f` with `flag` checked by the calls it makes on this thread.
+pub fn scope<R>(flag: Option<&AbortFlag>, f: impl FnOnce() -> R) -> R {
+    let _restore = Restore(FLAG.with(|cur| cur.replace(flag.cloned())));
+
+    f()
+}
+
+/// The flag of the current [scope], to pass it to other threads.
+pub fn current() -> Option<AbortFlag> {
+    FLAG.with(|cur| cur.borrow().clone())
+}
+
+/// Restores the flag of the outer [scope], even if the call panics.
+struct Restore(Option<AbortFlag>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        FLAG.with(|cur| *cur.borrow_mut() = self.0.take());
+    }
+}
+
+/// Starts the clock of `timeout_ms` for the call running on this thread,
+/// until the returned guard is dropped.
+pub fn deadline(timeout_ms: Option<u64>) -> Deadline {
+    let deadline = timeout_ms.map(|ms| (Instant::now() + Duration::from_millis(ms), ms));
+
+    Deadline(DEADLINE.with(|cur| cur.replace(deadline)))
+}
+
+/// Restores the deadline of the outer call when dropped.
+#[must_use]
+pub struct Deadline(Option<(Instant, u64)>);
+
+impl Drop for Deadline {
+    fn drop(&mut self) {
+        DEADLINE.with(|cur| cur.set(self.0.take()));
+    }
+}
+
+/// Whether [check] can fail on this thread, so the tree is worth walking to
+/// check it.
+pub fn is_scoped() -> bool {
+    FLAG.with(|cur| cur.borrow().is_some()) || DEADLINE.with(|cur| cur.get().is_some())
+}
+
+/// Fails if the flag of the current [scope] is set or if the [deadline] has
+/// passed.
+pub fn check() -> anyhow::Result<()> {
+    if FLAG.with(|cur| cur.borrow().as_ref().map_or(false, AbortFlag::is_aborted)) {
+        return Err(Aborted.into());
+    }
+
+    match DEADLINE.with(Cell::get) {
+        Some((deadline, max)) if Instant::now() > deadline => Err(exceeded(Limit::Timeout, max)),
+        _ => Ok(()),
+    }
+}
+
+/// How many [tick](Ticker::tick)s there are between [check]s.
+const TICKS_PER_CHECK: u32 = 1024;
+
+/// Calls [check] periodically from code which cannot return its error, like
+/// the token streams of parsers and visitors.
+#[derive(Debug, Clone, Default)]
+pub struct Ticker {
+    ticks: u32,
+    stopped: bool,
+}
+
+impl Ticker {
+    /// Returns false once the call must stop. As the flag stays set and the
+    /// deadline stays passed, the error is returned by the next [check].
+    pub fn tick(&mut self) -> bool {
+        if !self.stopped {
+            self.ticks = self.ticks.wrapping_add(1);
+
+            if self.ticks % TICKS_PER_CHECK == 0 {
+                self.stopped = check().is_err();
+            }
+        }
+
+        !self.stopped
+    }
+}
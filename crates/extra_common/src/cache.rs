@@ -0,0 +1,303 @@
+//! Opt-in cache of the results on disk, so the unchanged inputs of repeated
+//! builds are not processed again.
+//!
+//! A result is keyed by a hash of the input, the filename, the options
+//! normalized as JSON, the name and the version of the package and a hash of
+//! `Cargo.lock` taken at build time, and stored in a file named after the
+//! key. Once the files exceed `maxSize`, the least recently used ones are
+//! removed. Errors are not cached, nor results with `stats`, whose durations
+//! would be the ones of the call which stored them.
+
+use std::{
+    fs,
+    hash::Hash,
+    io,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, PoisonError, RwLock,
+    },
+    time::SystemTime,
+};
+
+use anyhow::{bail, Context};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use siphasher::sip128::{Hasher128, SipHasher13};
+
+/// The default of `maxSize`.
+const DEFAULT_MAX_SIZE: u64 = 512 * 1024 * 1024;
+
+/// The extension of the cached results.
+const EXTENSION: &str = "json";
+
+/// The cache of a package, which is disabled until [Cache::enable] is called.
+pub struct Cache {
+    /// The name and the version of the package, as a part of the key.
+    package: &'static str,
+    dir: RwLock<Option<Arc<Dir>>>,
+}
+
+struct Dir {
+    path: PathBuf,
+    max_size: u64,
+    /// The size of the files, as of the last scan and the results stored
+    /// since.
+    size: Mutex<u64>,
+}
+
+/// Makes the temporary files of a process unique.
+static NEXT_TEMP: AtomicUsize = AtomicUsize::new(0);
+
+impl Cache {
+    /// `package` is like `@swc/css@0.0.29`.
+    pub const fn new(package: &'static str) -> Self {
+        Cache {
+            package,
+            dir: RwLock::new(None),
+        }
+    }
+
+    /// Caches the results in `dir`, removing the least recently used ones
+    /// over `max_size` bytes. Calling it again replaces the options.
+    pub fn enable(&self, dir: &str, max_size: Option<i64>) -> anyhow::Result<()> {
+        let max_size = match max_size {
+            Some(size) if size <= 0 => bail!("`maxSize` must be positive, got {}", size),
+            Some(size) => size as u64,
+            None => DEFAULT_MAX_SIZE,
+        };
+
+        let path = PathBuf::from(dir);
+        fs::create_dir_all(&path)
+            .with_context(|| format!("failed to create `{}`", path.display()))?;
+
+        let size = entries(&path)?.iter().map(|e| e.size).sum();
+
+        *self.dir.write().unwrap_or_else(PoisonError::into_inner) = Some(Arc::new(Dir {
+            path,
+            max_size,
+            size: Mutex::new(size),
+        }));
+
+        Ok(())
+    }
+
+    /// Removes the cached results. The cache stays enabled.
+    pub fn clear(&self) -> anyhow::Result<()> {
+        let dir = match self.current() {
+            Some(dir) => dir,
+            None => return Ok(()),
+        };
+
+        let mut size = dir.size.lock().unwrap_or_else(PoisonError::into_inner);
+        for entry in entries(&dir.path)? {
+            remove(&entry.path)?;
+        }
+        *size = 0;
+
+        Ok(())
+    }
+
+    /// Returns the cached result of `kind` for the input, or the result of
+    /// `compute`, which is stored if the cache is enabled.
+    ///
+    /// `options` is the JSON of the options, which `compute` deserializes.
+    pub fn cached<T, F>(
+        &self,
+        kind: &str,
+        options: &str,
+        code: &[u8],
+        filename: Option<&str>,
+        compute: F,
+    ) -> anyhow::Result<T>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce() -> anyhow::Result<T>,
+    {
+        let (dir, options) = match (self.current(), normalize_options(options)) {
+            (Some(dir), Some(options)) => (dir, options),
+            _ => return compute(),
+        };
+
+        let path = dir.path(self.package, kind, &options, code, filename);
+        if let Some(result) = dir.get(&path) {
+            return Ok(result);
+        }
+
+        let result = compute()?;
+        // The result is returned even if it cannot be stored.
+        let _ = dir.insert(&path, &result);
+
+        Ok(result)
+    }
+
+    /// Not borrowed while computing, which would block [Cache::enable].
+    fn current(&self) -> Option<Arc<Dir>> {
+        self.dir
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+/// Returns the options as a part of the key, or [None] if the result should
+/// not be cached.
+fn normalize_options(options: &str) -> Option<String> {
+    let options: Value = serde_json::from_str(options).ok()?;
+
+    if options.get("stats") == Some(&Value::Bool(true)) {
+        return None;
+    }
+
+    Some(sort_keys(options).to_string())
+}
+
+/// Sorts the keys of the objects in `value`, so their order does not change
+/// the key.
+fn sort_keys(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries = map.into_iter().collect::<Vec<_>>();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(k, v)| (k, sort_keys(v)))
+                    .collect(),
+            )
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(sort_keys).collect()),
+        value => value,
+    }
+}
+
+impl Dir {
+    fn path(
+        &self,
+        package: &str,
+        kind: &str,
+        options: &str,
+        code: &[u8],
+        filename: Option<&str>,
+    ) -> PathBuf {
+        let mut hasher = SipHasher13::new();
+        (
+            package,
+            env!("CARGO_LOCK_HASH"),
+            kind,
+            options,
+            filename,
+            code,
+        )
+            .hash(&mut hasher);
+
+        self.path.join(format!(
+            "{:032x}.{}",
+            u128::from(hasher.finish128()),
+            EXTENSION
+        ))
+    }
+
+    fn get<T: DeserializeOwned>(&self, path: &Path) -> Option<T> {
+        let bytes = fs::read(path).ok()?;
+        // A corrupted result is computed again.
+        let result = serde_json::from_slice(&bytes).ok()?;
+
+        // Marks it as recently used.
+        if let Ok(file) = fs::File::options().write(true).open(path) {
+            let _ = file.set_modified(SystemTime::now());
+        }
+
+        Some(result)
+    }
+
+    fn insert<T: Serialize>(&self, path: &Path, result: &T) -> anyhow::Result<()> {
+        let bytes = serde_json::to_vec(result)?;
+
+        // Renamed once written, so it's never read partially.
+        let temp = path.with_extension(format!(
+            "{}-{}.tmp",
+            std::process::id(),
+            NEXT_TEMP.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::write(&temp, &bytes)?;
+        if let Err(err) = fs::rename(&temp, path) {
+            let _ = fs::remove_file(&temp);
+            return Err(err.into());
+        }
+
+        let mut size = self.size.lock().unwrap_or_else(PoisonError::into_inner);
+        *size += bytes.len() as u64;
+
+        if *size > self.max_size {
+            *size = self.evict()?;
+        }
+
+        Ok(())
+    }
+
+    /// Removes the least recently used results, until they take at most 3/4
+    /// of `max_size`, so it does not run for every insert. Returns the size
+    /// of the rest.
+    fn evict(&self) -> anyhow::Result<u64> {
+        let mut entries = entries(&self.path)?;
+        entries.sort_by_key(|e| e.modified);
+
+        let mut size: u64 = entries.iter().map(|e| e.size).sum();
+        for entry in entries {
+            if size <= self.max_size / 4 * 3 {
+                break;
+            }
+
+            remove(&entry.path)?;
+            size -= entry.size;
+        }
+
+        Ok(size)
+    }
+}
+
+struct Entry {
+    path: PathBuf,
+    size: u64,
+    modified: SystemTime,
+}
+
+/// The cached results in `dir`.
+fn entries(dir: &Path) -> anyhow::Result<Vec<Entry>> {
+    let mut entries = vec![];
+
+    for entry in fs::read_dir(dir).with_context(|| format!("failed to read `{}`", dir.display()))? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.extension().map_or(true, |ext| ext != EXTENSION) {
+            continue;
+        }
+
+        // Removed by another process.
+        let metadata = match entry.metadata() {
+            Ok(metadata) => metadata,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+            Err(err) => return Err(err.into()),
+        };
+
+        entries.push(Entry {
+            path,
+            size: metadata.len(),
+            modified: metadata.modified()?,
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Removes a cached result, which may have been removed by another process.
+fn remove(path: &Path) -> anyhow::Result<()> {
+    match fs::remove_file(path) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => {
+            Err(err).with_context(|| format!("failed to remove `{}`", path.display()))
+        }
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,202 @@
+//! Conversion of the diagnostics of swc, with positions relative to their
+//! file and code frames.
+
+use std::{
+    fmt,
+    mem::take,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use serde::{Deserialize, Serialize};
+use swc_common::{
+    errors::{Diagnostic as SwcDiagnostic, DiagnosticBuilder, DiagnosticId, Emitter, Handler},
+    sync::Lrc,
+    SourceMap, Span,
+};
+use swc_error_reporters::{
+    GraphicalReportHandler, GraphicalTheme, PrettyEmitter, PrettyEmitterConfig,
+};
+
+#[cfg_attr(feature = "napi", napi_derive::napi(object))]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticSpan {
+    pub filename: String,
+    /// Byte offset from the start of the file.
+    pub start: u32,
+    /// Byte offset from the start of the file.
+    pub end: u32,
+    /// 1-based.
+    pub start_line: u32,
+    /// 1-based, in characters.
+    pub start_column: u32,
+    /// 1-based.
+    pub end_line: u32,
+    /// 1-based, in characters.
+    pub end_column: u32,
+}
+
+/// A diagnostic of the css and html tools, which can recover from errors.
+#[cfg_attr(feature = "napi", napi_derive::napi(object))]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Diagnostic {
+    pub level: String,
+    pub message: String,
+    /// The id of the lint rule or the error code, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub span: Option<DiagnosticSpan>,
+    /// The source code around `span`, rendered like thrown errors.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code_frame: Option<String>,
+    /// True if this error prevented the input from being processed.
+    pub fatal: bool,
+}
+
+/// A diagnostic of the linter, which has no `fatal` as it cannot recover from
+/// errors.
+#[cfg_attr(
+    all(feature = "napi", not(feature = "namespace")),
+    napi_derive::napi(object, js_name = "Diagnostic")
+)]
+#[cfg_attr(
+    all(feature = "napi", feature = "namespace"),
+    napi_derive::napi(object, js_name = "Diagnostic", namespace = "linter")
+)]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintDiagnostic {
+    pub level: String,
+    pub message: String,
+    /// The id of the lint rule or the error code, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub span: Option<DiagnosticSpan>,
+    /// The source code around `span`, rendered like thrown errors.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code_frame: Option<String>,
+}
+
+/// Converts `d`, which is not fatal. The code frame is only rendered if
+/// `with_code_frame`.
+pub fn to_diagnostic(cm: &Lrc<SourceMap>, d: &SwcDiagnostic, with_code_frame: bool) -> Diagnostic {
+    Diagnostic {
+        level: d.level.to_string(),
+        message: d.message(),
+        code: code(d),
+        span: span(cm, d),
+        code_frame: with_code_frame.then(|| code_frame(cm, d)).flatten(),
+        fatal: false,
+    }
+}
+
+impl From<Diagnostic> for LintDiagnostic {
+    fn from(d: Diagnostic) -> Self {
+        LintDiagnostic {
+            level: d.level,
+            message: d.message,
+            code: d.code,
+            span: d.span,
+            code_frame: d.code_frame,
+        }
+    }
+}
+
+/// The primary span of `d`, if it has one.
+fn span(cm: &Lrc<SourceMap>, d: &SwcDiagnostic) -> Option<DiagnosticSpan> {
+    primary_span(d).map(|span| to_diagnostic_span(cm, span))
+}
+
+/// The id of the lint rule or the error code of `d`, if any.
+fn code(d: &SwcDiagnostic) -> Option<String> {
+    d.code.as_ref().map(|code| match code {
+        DiagnosticId::Error(s) => s.clone(),
+        DiagnosticId::Lint(s) => s.clone(),
+    })
+}
+
+/// Renders `d` with the emitter used by
+/// [swc_error_reporters::handler::try_with_handler], without colors, if it
+/// has a span. This is slow for many diagnostics, so it can be turned off with
+/// the `codeFrame` option.
+fn code_frame(cm: &Lrc<SourceMap>, d: &SwcDiagnostic) -> Option<String> {
+    primary_span(d)?;
+
+    let buf = SharedBuffer::default();
+
+    let handler = Handler::with_emitter(
+        true,
+        false,
+        Box::new(PrettyEmitter::new(
+            cm.clone(),
+            Box::new(buf.clone()),
+            GraphicalReportHandler::new_themed(GraphicalTheme::unicode_nocolor()),
+            PrettyEmitterConfig {
+                skip_filename: false,
+            },
+        )),
+    );
+
+    DiagnosticBuilder::new_diagnostic(&handler, d.clone()).emit();
+
+    let frame = take(&mut *buf.0.lock().unwrap());
+
+    if frame.is_empty() {
+        None
+    } else {
+        Some(frame)
+    }
+}
+
+fn primary_span(d: &SwcDiagnostic) -> Option<Span> {
+    d.span.primary_span().filter(|span| !span.is_dummy())
+}
+
+fn to_diagnostic_span(cm: &Lrc<SourceMap>, span: Span) -> DiagnosticSpan {
+    let start = cm.lookup_char_pos(span.lo);
+    let end = cm.lookup_char_pos(span.hi);
+
+    DiagnosticSpan {
+        filename: start.file.name.to_string(),
+        start: (span.lo - start.file.start_pos).0,
+        end: (span.hi - start.file.start_pos).0,
+        start_line: start.line as _,
+        start_column: start.col.0 as u32 + 1,
+        end_line: end.line as _,
+        end_column: end.col.0 as u32 + 1,
+    }
+}
+
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<String>>);
+
+impl fmt::Write for SharedBuffer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.lock().unwrap().push_str(s);
+        Ok(())
+    }
+}
+
+/// An [Emitter] which stores diagnostics instead of printing them. The
+/// clones share the stored diagnostics.
+#[derive(Clone, Default)]
+pub struct Collector(Arc<Mutex<Vec<SwcDiagnostic>>>);
+
+impl Collector {
+    /// Takes the diagnostics emitted so far.
+    pub fn take(&self) -> Vec<SwcDiagnostic> {
+        take(&mut *self.0.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+impl Emitter for Collector {
+    fn emit(&mut self, db: &DiagnosticBuilder<'_>) {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push((**db).clone());
+    }
+}
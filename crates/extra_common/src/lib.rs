@@ -0,0 +1,13 @@
+//! The parts shared by the css, html and linter crates, without the bindings
+//! for Node.js.
+
+pub mod abort;
+pub mod cache;
+pub mod diagnostic;
+pub mod limits;
+#[cfg(feature = "node")]
+pub mod node;
+pub mod options;
+pub mod panic;
+pub mod schema;
+pub mod util;
@@ -0,0 +1,75 @@
+//! The errors of an exceeded limit of the options, for untrusted input.
+//!
+//! The [Limit] of an error is kept until it's taken with [take_exceeded],
+//! like the reports of [panic](crate::panic), so the bindings can throw the
+//! error with its [code](Limit::code).
+
+use std::{cell::Cell, fmt};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Limit {
+    InputBytes,
+    Depth,
+    Nodes,
+    Timeout,
+}
+
+impl Limit {
+    /// The `code` of the errors thrown to JS.
+    pub fn code(self) -> &'static str {
+        match self {
+            Limit::InputBytes => "SWC_LIMIT_INPUT_BYTES",
+            Limit::Depth => "SWC_LIMIT_DEPTH",
+            Limit::Nodes => "SWC_LIMIT_NODES",
+            Limit::Timeout => "SWC_LIMIT_TIMEOUT",
+        }
+    }
+
+    /// The key of the option in `limits`.
+    fn option(self) -> &'static str {
+        match self {
+            Limit::InputBytes => "maxInputBytes",
+            Limit::Depth => "maxDepth",
+            Limit::Nodes => "maxNodes",
+            Limit::Timeout => "timeoutMs",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct LimitExceeded {
+    pub limit: Limit,
+    /// The value of the option.
+    pub max: u64,
+}
+
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "exceeded `limits.{}` of {}",
+            self.limit.option(),
+            self.max
+        )
+    }
+}
+
+impl std::error::Error for LimitExceeded {}
+
+thread_local! {
+    /// The last limit exceeded on this thread.
+    static EXCEEDED: Cell<Option<Limit>> = Cell::new(None);
+}
+
+/// Takes the last limit exceeded on this thread.
+pub fn take_exceeded() -> Option<Limit> {
+    EXCEEDED.with(Cell::take)
+}
+
+/// Returns the error of `limit`, which is kept until it's taken with
+/// [take_exceeded].
+pub fn exceeded(limit: Limit, max: u64) -> anyhow::Error {
+    EXCEEDED.with(|e| e.set(Some(limit)));
+
+    LimitExceeded { limit, max }.into()
+}
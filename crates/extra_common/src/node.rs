@@ -0,0 +1,10 @@
+//! The parts of the bindings for Node.js shared by `css_node`, `html_node` and
+//! `linter_node`. Their napi functions and objects are declared by each of
+//! them, in the namespace of their part of `extra_node`.
+
+pub mod abort;
+pub mod batch;
+pub mod handle;
+pub mod js_options;
+pub mod panic;
+pub mod trace;
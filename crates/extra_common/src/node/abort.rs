@@ -0,0 +1,59 @@
+//! Aborting the async functions with an `AbortSignal`.
+//!
+//! napi-rs only cancels a task which is still queued. The signal also sets an
+//! [AbortFlag], which the core crates check while the task runs, so a task
+//! which already started stops early instead of running to completion.
+
+use napi::{
+    bindgen_prelude::{AsyncTask, FromNapiValue},
+    sys, CallContext, Env, JsFunction, JsObject, Task,
+};
+
+use crate::{abort::AbortFlag, node::panic::Reported};
+
+/// The `signal` argument of the async functions.
+pub struct AbortSignal {
+    signal: napi::bindgen_prelude::AbortSignal,
+    flag: AbortFlag,
+}
+
+impl FromNapiValue for AbortSignal {
+    unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> napi::Result<Self> {
+        let flag = AbortFlag::default();
+
+        let js_env = Env::from_raw(env);
+        let target = JsObject::from_napi_value(env, napi_val)?;
+        let add_event_listener: JsFunction = target.get_named_property("addEventListener")?;
+
+        let listener = {
+            let flag = flag.clone();
+            js_env.create_function_from_closure("onabort", move |_: CallContext| {
+                flag.abort();
+                Ok(())
+            })?
+        };
+        add_event_listener.call(
+            Some(&target),
+            &[
+                js_env.create_string("abort")?.into_unknown(),
+                listener.into_unknown(),
+            ],
+        )?;
+
+        Ok(AbortSignal {
+            signal: FromNapiValue::from_napi_value(env, napi_val)?,
+            flag,
+        })
+    }
+}
+
+/// Like [AsyncTask::with_optional_signal], but `signal` also aborts `task`
+/// once it's running.
+pub fn abortable<T: Task>(task: T, signal: Option<AbortSignal>) -> AsyncTask<Reported<T>> {
+    match signal {
+        Some(AbortSignal { signal, flag }) => {
+            AsyncTask::with_signal(Reported::new(task).abort_on(flag), signal)
+        }
+        None => AsyncTask::new(Reported::new(task)),
+    }
+}
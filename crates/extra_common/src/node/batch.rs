@@ -0,0 +1,58 @@
+//! The parallel processing of the `*Many` functions.
+
+use anyhow::Context;
+use rayon::prelude::*;
+use serde::de::DeserializeOwned;
+
+use crate::{
+    abort,
+    limits::take_exceeded,
+    options::parse_options,
+    panic::{catch, caught, take_caught},
+};
+
+/// A file of a batch, which can be sent to another thread.
+pub struct File {
+    pub code: Vec<u8>,
+    pub filename: Option<String>,
+}
+
+/// Processes `files` in parallel with the options deserialized from `options`,
+/// passing `op` the warnings about their unknown keys.
+///
+/// An error of a file, even a panic outside of the core crates, is returned
+/// in its place, so it does not fail the other files. The [abort] flag of the
+/// calling thread is passed to the threads of the files.
+pub fn run_batch<O, T, F>(
+    files: &[File],
+    options: &str,
+    op: F,
+) -> anyhow::Result<Vec<Result<T, String>>>
+where
+    O: DeserializeOwned + Sync,
+    T: Send,
+    F: Fn(&File, &O, &[String]) -> anyhow::Result<T> + Sync,
+{
+    let (opts, warnings): (O, _) =
+        parse_options(options).context("failed to deserialize options")?;
+    let flag = abort::current();
+
+    Ok(files
+        .par_iter()
+        .map(|file| {
+            let result = abort::scope(flag.as_ref(), || {
+                catch(|| op(file, &opts, &warnings)).unwrap_or_else(|mut report| {
+                    report.filename = file.filename.clone();
+                    Err(caught(report))
+                })
+            });
+
+            // The error is returned with the file, so its cause must not be
+            // reported for a later call on this thread.
+            take_caught();
+            take_exceeded();
+
+            result.map_err(|err| format!("{:?}", err))
+        })
+        .collect())
+}
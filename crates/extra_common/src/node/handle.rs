@@ -0,0 +1,41 @@
+//! The options of the handles, which are deserialized once so they can be
+//! reused for many inputs.
+
+use std::sync::Arc;
+
+use serde::de::DeserializeOwned;
+
+use crate::options::parse_options;
+
+pub struct Prepared<O> {
+    pub options: Arc<O>,
+    /// The warnings about unknown options, which are reported in the output
+    /// of every run.
+    pub warnings: Arc<Vec<String>>,
+    /// The JSON of `options`, for the keys of the [cache](crate::cache).
+    pub json: Arc<String>,
+}
+
+impl<O: DeserializeOwned> Prepared<O> {
+    pub fn parse(opts: &[u8]) -> anyhow::Result<Self> {
+        let json = String::from_utf8_lossy(opts).into_owned();
+        let (options, warnings) = parse_options(&json)?;
+
+        Ok(Prepared {
+            options: Arc::new(options),
+            warnings: Arc::new(warnings),
+            json: Arc::new(json),
+        })
+    }
+}
+
+/// Clones the [Arc]s, for a task of the handle.
+impl<O> Clone for Prepared<O> {
+    fn clone(&self) -> Self {
+        Prepared {
+            options: self.options.clone(),
+            warnings: self.warnings.clone(),
+            json: self.json.clone(),
+        }
+    }
+}
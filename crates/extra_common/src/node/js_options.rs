@@ -0,0 +1,184 @@
+//! Options passed as JS objects instead of JSON buffers.
+//!
+//! Values which cannot be represented in JSON are converted before the
+//! options are deserialized by [parse_options_value]:
+//!
+//! - A `RegExp` is rejected, as its syntax differs from the one of the
+//!   patterns, which is the one of the `regex` crate.
+//! - A typed array or a `Buffer` becomes an array of numbers.
+//! - A property which is `undefined` is omitted.
+//! - A function is taken out of the options, and must be claimed with
+//!   [JsOptions::take_function], so it's rejected unless the option accepts
+//!   one.
+
+use anyhow::bail;
+use napi::{Env, JsFunction, JsObject, JsUnknown, ValueType};
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Number, Value};
+
+use crate::options::parse_options_value;
+
+/// Objects nested deeper than this are rejected, which also rejects cycles.
+const MAX_DEPTH: usize = 64;
+
+/// Options converted from a JS object.
+pub struct JsOptions {
+    value: Value,
+    functions: Vec<(String, JsFunction)>,
+}
+
+impl JsOptions {
+    pub fn from_object(env: &Env, object: JsObject) -> anyhow::Result<Self> {
+        let regexp = env
+            .get_global()?
+            .get_named_property_unchecked::<JsFunction>("RegExp")?;
+
+        let mut converter = Converter {
+            regexp,
+            functions: vec![],
+        };
+
+        let value = converter.convert_object(object, "", 0)?;
+
+        Ok(JsOptions {
+            value,
+            functions: converter.functions,
+        })
+    }
+
+    /// Removes the function at `path`, like `rewriteUrl`.
+    pub fn take_function(&mut self, path: &str) -> Option<JsFunction> {
+        let idx = self.functions.iter().position(|(p, _)| p == path)?;
+
+        Some(self.functions.remove(idx).1)
+    }
+
+    /// Returns the options as JSON, failing if a function was not taken.
+    pub fn finish(self) -> anyhow::Result<Value> {
+        if let Some((path, _)) = self.functions.first() {
+            bail!("option `{}` does not accept a function", path)
+        }
+
+        Ok(self.value)
+    }
+}
+
+/// Deserializes options from `object`, which may not contain functions.
+pub fn parse_js_options<T>(env: &Env, object: JsObject) -> anyhow::Result<(T, Vec<String>)>
+where
+    T: DeserializeOwned,
+{
+    parse_options_value(JsOptions::from_object(env, object)?.finish()?)
+}
+
+struct Converter {
+    regexp: JsFunction,
+    functions: Vec<(String, JsFunction)>,
+}
+
+impl Converter {
+    /// Returns [None] if `value` should be omitted.
+    fn convert(
+        &mut self,
+        value: JsUnknown,
+        path: String,
+        depth: usize,
+    ) -> anyhow::Result<Option<Value>> {
+        if depth > MAX_DEPTH {
+            bail!("option `{}` is nested too deeply", path)
+        }
+
+        Ok(Some(match value.get_type()? {
+            ValueType::Undefined => return Ok(None),
+            ValueType::Null => Value::Null,
+            ValueType::Boolean => Value::Bool(value.coerce_to_bool()?.get_value()?),
+            ValueType::Number => number(value.coerce_to_number()?.get_double()?),
+            ValueType::String => {
+                Value::String(value.coerce_to_string()?.into_utf8()?.into_owned()?)
+            }
+            ValueType::Function => {
+                // Safety: the type is checked above.
+                self.functions.push((path, unsafe { value.cast() }));
+                return Ok(None);
+            }
+            // Safety: the type is checked above.
+            ValueType::Object => self.convert_object(unsafe { value.cast() }, &path, depth)?,
+            ty => bail!("option `{}` has an unsupported type: {:?}", path, ty),
+        }))
+    }
+
+    fn convert_object(
+        &mut self,
+        object: JsObject,
+        path: &str,
+        depth: usize,
+    ) -> anyhow::Result<Value> {
+        if object.instanceof(&self.regexp)? {
+            bail!(
+                "option `{}` is a RegExp, which is not supported: pass its source as a string, in \
+                 the syntax of the Rust `regex` crate",
+                path
+            )
+        }
+
+        if object.is_array()? || object.is_typedarray()? {
+            let len = object
+                .get_named_property_unchecked::<JsUnknown>("length")?
+                .coerce_to_number()?
+                .get_uint32()?;
+
+            let mut items = Vec::with_capacity(len as usize);
+
+            for i in 0..len {
+                let item = object.get_element::<JsUnknown>(i)?;
+
+                // Like `JSON.stringify`, `undefined` and functions are kept as
+                // `null` to preserve the indices.
+                items.push(
+                    self.convert(item, format!("{}[{}]", path, i), depth + 1)?
+                        .unwrap_or(Value::Null),
+                );
+            }
+
+            return Ok(Value::Array(items));
+        }
+
+        let keys = object.get_property_names()?;
+        let len = keys.get_array_length()?;
+
+        let mut map = Map::new();
+
+        for i in 0..len {
+            let key = keys
+                .get_element::<JsUnknown>(i)?
+                .coerce_to_string()?
+                .into_utf8()?
+                .into_owned()?;
+            let value = object.get_named_property_unchecked::<JsUnknown>(&key)?;
+
+            let path = if path.is_empty() {
+                key.clone()
+            } else {
+                format!("{}.{}", path, key)
+            };
+
+            if let Some(value) = self.convert(value, path, depth + 1)? {
+                map.insert(key, value);
+            }
+        }
+
+        Ok(Value::Object(map))
+    }
+}
+
+/// Integers are kept as integers so they can be deserialized as `usize`.
+fn number(v: f64) -> Value {
+    if v.fract() == 0.0 && v.abs() < 9_007_199_254_740_992.0 {
+        Value::from(v as i64)
+    } else {
+        // `NaN` and infinities become `null`, like `JSON.stringify`.
+        Number::from_f64(v)
+            .map(Value::Number)
+            .unwrap_or(Value::Null)
+    }
+}
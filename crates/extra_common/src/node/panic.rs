@@ -0,0 +1,137 @@
+//! Throws the errors caused by an internal panic to JS with the code
+//! `SWC_INTERNAL_PANIC` and the properties of its [PanicReport], and the
+//! errors caused by an exceeded limit with the code of its [Limit].
+
+use napi::{Env, JsObject, Task};
+
+use crate::{
+    abort::{self, AbortFlag},
+    limits::{take_exceeded, Limit},
+    panic::{take_caught, PanicReport, Sink},
+};
+
+/// The `code` of the errors thrown for a panic.
+const CODE: &str = "SWC_INTERNAL_PANIC";
+
+/// Why a call failed, if it's thrown with a `code`.
+enum Cause {
+    Panic(PanicReport),
+    Limit(Limit),
+}
+
+impl Cause {
+    /// Both are taken, so neither is reported for a later error.
+    fn take() -> Option<Self> {
+        let report = take_caught();
+        let limit = take_exceeded();
+
+        report.map(Cause::Panic).or(limit.map(Cause::Limit))
+    }
+}
+
+/// The [Sink] of the `setPanicSink` functions: `"stderr"`, which is the
+/// default, `"none"` or the path of a file.
+pub fn parse_sink(sink: Option<&str>) -> Sink {
+    match sink {
+        None | Some("stderr") => Sink::Stderr,
+        Some("none") => Sink::None,
+        Some(path) => Sink::File(path.into()),
+    }
+}
+
+/// Converts `err` to a JS error with the code and the properties of `cause`.
+fn into_error(cause: Cause, env: &Env, err: napi::Error) -> napi::Error {
+    let create = || -> napi::Result<JsObject> {
+        let mut error = env.create_error(napi::Error::new(err.status, err.reason.clone()))?;
+
+        match cause {
+            Cause::Panic(report) => {
+                error.set_named_property("code", CODE)?;
+                for (key, value) in [
+                    ("location", report.location),
+                    ("backtrace", report.backtrace),
+                    ("filename", report.filename),
+                ] {
+                    if let Some(value) = value {
+                        error.set_named_property(key, value)?;
+                    }
+                }
+            }
+            Cause::Limit(limit) => error.set_named_property("code", limit.code())?,
+        }
+
+        Ok(error)
+    };
+
+    match create() {
+        Ok(error) => napi::Error::from(error.into_unknown()),
+        Err(_) => err,
+    }
+}
+
+/// Throws the errors of the sync functions caused by a panic or an exceeded
+/// limit with their code.
+pub trait ReportPanic {
+    fn report_panic(self, env: &Env) -> Self;
+}
+
+impl<T> ReportPanic for napi::Result<T> {
+    fn report_panic(self, env: &Env) -> Self {
+        // Taken even on success, so it's not reported for a later error.
+        let cause = Cause::take();
+
+        match (self, cause) {
+            (Err(err), Some(cause)) => Err(into_error(cause, env, err)),
+            (result, _) => result,
+        }
+    }
+}
+
+/// [ReportPanic] for a task, whose cause is taken on the thread running it.
+pub struct Reported<T> {
+    task: T,
+    cause: Option<Cause>,
+    abort: Option<AbortFlag>,
+}
+
+impl<T> Reported<T> {
+    pub fn new(task: T) -> Self {
+        Reported {
+            task,
+            cause: None,
+            abort: None,
+        }
+    }
+
+    /// Runs the task in the [scope](abort::scope) of `flag`.
+    pub fn abort_on(mut self, flag: AbortFlag) -> Self {
+        self.abort = Some(flag);
+        self
+    }
+}
+
+impl<T: Task> Task for Reported<T> {
+    type JsValue = T::JsValue;
+    type Output = T::Output;
+
+    fn compute(&mut self) -> napi::Result<Self::Output> {
+        let result = abort::scope(self.abort.as_ref(), || self.task.compute());
+        self.cause = Cause::take();
+        result
+    }
+
+    fn resolve(&mut self, env: Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+        self.task.resolve(env, output)
+    }
+
+    fn reject(&mut self, env: Env, err: napi::Error) -> napi::Result<Self::JsValue> {
+        match self.cause.take() {
+            Some(cause) => Err(into_error(cause, &env, err)),
+            None => self.task.reject(env, err),
+        }
+    }
+
+    fn finally(&mut self, env: Env) -> napi::Result<()> {
+        self.task.finally(env)
+    }
+}
@@ -0,0 +1,72 @@
+//! Opt-in tracing of the phases of each function, like `parse` and
+//! `codegen`, to find the slow one for an input.
+
+use std::{fs::File, io::stderr, sync::Mutex};
+
+use anyhow::{bail, Context};
+use napi::Env;
+use tracing_chrome::ChromeLayerBuilder;
+use tracing_subscriber::{
+    fmt::{format::FmtSpan, writer::BoxMakeWriter},
+    layer::SubscriberExt,
+    util::SubscriberInitExt,
+    EnvFilter,
+};
+
+/// The options of the `initTracing` functions, which are documented there.
+#[derive(Default)]
+pub struct Config<'a> {
+    pub filter: Option<&'a str>,
+    pub format: Option<&'a str>,
+    pub path: Option<&'a str>,
+}
+
+/// Installs the subscriber which records the phases. It can be called only
+/// once, for all the crates linked in a process.
+pub fn init(mut env: Env, options: Config) -> anyhow::Result<()> {
+    let filter =
+        EnvFilter::try_new(options.filter.unwrap_or("info")).context("invalid `filter`")?;
+    let registry = tracing_subscriber::registry().with(filter);
+
+    let format = options.format.unwrap_or("pretty");
+
+    if format == "chrome" {
+        let mut builder = ChromeLayerBuilder::new().include_args(true);
+        if let Some(path) = options.path {
+            builder = builder.file(path);
+        }
+
+        let (layer, guard) = builder.build();
+        registry.with(layer).try_init()?;
+
+        // The trace is written when the guard is dropped.
+        env.add_env_cleanup_hook(guard, |guard| {
+            guard.flush();
+        })?;
+
+        return Ok(());
+    }
+
+    let writer = match options.path {
+        Some(path) => BoxMakeWriter::new(Mutex::new(
+            File::create(path).with_context(|| format!("failed to create `{}`", path))?,
+        )),
+        None => BoxMakeWriter::new(stderr),
+    };
+
+    let layer = tracing_subscriber::fmt::layer()
+        .with_span_events(FmtSpan::CLOSE)
+        .with_ansi(options.path.is_none())
+        .with_writer(writer);
+
+    match format {
+        "json" => registry.with(layer.json()).try_init()?,
+        "pretty" => registry.with(layer.pretty()).try_init()?,
+        _ => bail!(
+            "unknown `format` `{}`, expected `chrome`, `json` or `pretty`",
+            format
+        ),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,715 @@
+//! Deserialization of options which reports unknown keys and the path of
+//! invalid values.
+
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fmt::{self, Display},
+    marker::PhantomData,
+};
+
+use anyhow::{anyhow, bail, Context};
+use serde::{
+    de::{
+        self, value::BorrowedStrDeserializer, DeserializeOwned, DeserializeSeed, IntoDeserializer,
+        Visitor,
+    },
+    forward_to_deserialize_any, Deserialize,
+};
+use serde_json::Value;
+
+/// How unknown keys are handled. This is read from the top-level
+/// `unknownOptions` key.
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum UnknownOptions {
+    #[default]
+    Error,
+    Warn,
+}
+
+/// Deserializes options from `json`.
+///
+/// Unknown keys are rejected, or returned as warnings if `unknownOptions` is
+/// `"warn"`. Errors contain the path of the invalid value, like
+/// `minifyCss.parser.foo`.
+pub fn parse_options<T>(json: &str) -> anyhow::Result<(T, Vec<String>)>
+where
+    T: DeserializeOwned,
+{
+    let value: Value = serde_json::from_str(json).context("failed to parse options as JSON")?;
+
+    parse_options_value(value)
+}
+
+/// Deserializes options from `value`, like [parse_options].
+pub fn parse_options_value<T>(mut value: Value) -> anyhow::Result<(T, Vec<String>)>
+where
+    T: DeserializeOwned,
+{
+    let mode = match &mut value {
+        Value::Object(map) => match map.remove("unknownOptions") {
+            Some(mode) => {
+                UnknownOptions::deserialize(mode).context("failed to parse `unknownOptions`")?
+            }
+            None => Default::default(),
+        },
+        _ => Default::default(),
+    };
+
+    let state = State::default();
+
+    let options = T::deserialize(ValueDeserializer {
+        value: &value,
+        path: vec![],
+        state: &state,
+    })
+    .map_err(|err| anyhow!("{}", err))?;
+
+    let fields = state.fields.into_inner();
+    let unknown = state
+        .ignored
+        .into_inner()
+        .iter()
+        .map(|path| unknown_key_message(&fields, path))
+        .collect::<Vec<_>>();
+
+    match mode {
+        UnknownOptions::Error if !unknown.is_empty() => bail!("{}", unknown.join("\n")),
+        _ => Ok((options, unknown)),
+    }
+}
+
+fn unknown_key_message(
+    fields: &HashMap<String, &'static [&'static str]>,
+    path: &[Segment],
+) -> String {
+    let mut message = format!("unknown option `{}`", display_path(path));
+
+    if let Some((Segment::Key(key), parent)) = path.split_last() {
+        let suggestion = fields
+            .get(&display_path(parent))
+            .and_then(|fields| suggest(key, fields));
+
+        if let Some(suggestion) = suggestion {
+            message.push_str(&format!(", did you mean `{}`?", suggestion));
+        }
+    }
+
+    message
+}
+
+/// Returns the field most similar to `key`, if it's likely a typo or a
+/// different casing of it.
+fn suggest(key: &str, fields: &[&'static str]) -> Option<&'static str> {
+    let normalize = |s: &str| s.replace(['_', '-'], "").to_ascii_lowercase();
+    let normalized = normalize(key);
+
+    if let Some(field) = fields.iter().find(|field| normalize(field) == normalized) {
+        return Some(field);
+    }
+
+    fields
+        .iter()
+        .map(|field| (edit_distance(key, field), *field))
+        .filter(|(distance, _)| *distance <= (key.len() / 3).max(2))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, field)| field)
+}
+
+/// Levenshtein distance.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut prev = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut cur = vec![i + 1; b.len() + 1];
+
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+
+        prev = cur;
+    }
+
+    prev[b.len()]
+}
+
+#[derive(Debug, Clone)]
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+fn display_path(path: &[Segment]) -> String {
+    let mut s = String::new();
+
+    for segment in path {
+        match segment {
+            Segment::Key(key) => {
+                if !s.is_empty() {
+                    s.push('.');
+                }
+                s.push_str(key);
+            }
+            Segment::Index(index) => s.push_str(&format!("[{}]", index)),
+        }
+    }
+
+    s
+}
+
+#[derive(Default)]
+struct State {
+    /// Fields of the structs, keyed by their path.
+    fields: RefCell<HashMap<String, &'static [&'static str]>>,
+    /// Paths of the keys ignored by the deserializer.
+    ignored: RefCell<Vec<Vec<Segment>>>,
+}
+
+impl State {
+    fn snapshot(&self) -> (HashMap<String, &'static [&'static str]>, usize) {
+        (self.fields.borrow().clone(), self.ignored.borrow().len())
+    }
+
+    fn restore(&self, (fields, ignored): (HashMap<String, &'static [&'static str]>, usize)) {
+        *self.fields.borrow_mut() = fields;
+        self.ignored.borrow_mut().truncate(ignored);
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Error {
+    Invalid {
+        path: Vec<Segment>,
+        message: String,
+    },
+    /// A key rejected by a struct with `deny_unknown_fields`.
+    Unknown {
+        path: Vec<Segment>,
+        field: String,
+        suggestion: Option<&'static str>,
+    },
+}
+
+impl Error {
+    fn path(&self) -> &[Segment] {
+        match self {
+            Error::Invalid { path, .. } | Error::Unknown { path, .. } => path,
+        }
+    }
+
+    fn at(mut self, at: &[Segment]) -> Self {
+        match &mut self {
+            Error::Invalid { path, .. } | Error::Unknown { path, .. } => {
+                if path.is_empty() {
+                    *path = at.to_vec();
+                }
+            }
+        }
+        self
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Invalid { path, message } if path.is_empty() => {
+                write!(f, "invalid options: {}", message)
+            }
+            Error::Invalid { path, message } => {
+                write!(f, "invalid option `{}`: {}", display_path(path), message)
+            }
+            Error::Unknown {
+                path,
+                field,
+                suggestion,
+            } => {
+                if path.is_empty() {
+                    write!(f, "unknown option `{}`", field)?;
+                } else {
+                    write!(f, "unknown option `{}`", display_path(path))?;
+                }
+                match suggestion {
+                    Some(suggestion) => write!(f, ", did you mean `{}`?", suggestion),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error::Invalid {
+            path: vec![],
+            message: msg.to_string(),
+        }
+    }
+
+    fn unknown_field(field: &str, expected: &'static [&'static str]) -> Self {
+        Error::Unknown {
+            path: vec![],
+            field: field.to_string(),
+            suggestion: suggest(field, expected),
+        }
+    }
+}
+
+/// The name of the newtype struct with which [deserialize_untagged] asks
+/// [ValueDeserializer] to try each variant.
+const UNTAGGED: &str = "$extra_common::options::Untagged";
+
+/// Deserializes an untagged enum of another crate, whose variants are tried
+/// in order like with `#[serde(untagged)]`. `variants` are the constructors of
+/// the variants, like `(MinifyCssOption::Bool, MinifyCssOption::Options)`.
+///
+/// `#[serde(untagged)]` buffers the value before trying the variants, so
+/// unknown keys in it are ignored and a typo only fails with "data did not
+/// match any variant". With [parse_options], each variant is tried with the
+/// tracking of unknown keys instead, and the error of the variant which got
+/// the furthest is reported with its path.
+pub fn deserialize_untagged<'de, D, T, A>(
+    deserializer: D,
+    variants: impl Variants<T, A>,
+) -> Result<T, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    deserializer.deserialize_newtype_struct(
+        UNTAGGED,
+        UntaggedVisitor {
+            variants,
+            _marker: PhantomData::<(T, A)>,
+        },
+    )
+}
+
+/// The constructors of the variants of an untagged enum, as a tuple.
+pub trait Variants<T, A> {
+    /// Tries the variants on the elements of `trials`, which are the same
+    /// value each time.
+    fn try_seq<'de, S>(self, trials: S) -> Result<T, S::Error>
+    where
+        S: de::SeqAccess<'de>;
+
+    /// Tries the variants on `value`.
+    fn try_value(self, value: &Value) -> Option<T>;
+}
+
+impl<T, A0, A1, F0, F1> Variants<T, (A0, A1)> for (F0, F1)
+where
+    A0: DeserializeOwned,
+    A1: DeserializeOwned,
+    F0: FnOnce(A0) -> T,
+    F1: FnOnce(A1) -> T,
+{
+    fn try_seq<'de, S>(self, mut trials: S) -> Result<T, S::Error>
+    where
+        S: de::SeqAccess<'de>,
+    {
+        if let Ok(Some(v)) = trials.next_element() {
+            return Ok((self.0)(v));
+        }
+        if let Ok(Some(v)) = trials.next_element() {
+            return Ok((self.1)(v));
+        }
+
+        Err(de::Error::custom("data did not match any variant"))
+    }
+
+    fn try_value(self, value: &Value) -> Option<T> {
+        if let Ok(v) = A0::deserialize(value) {
+            return Some((self.0)(v));
+        }
+
+        A1::deserialize(value).ok().map(self.1)
+    }
+}
+
+struct UntaggedVisitor<V, A> {
+    variants: V,
+    _marker: PhantomData<A>,
+}
+
+impl<'de, V, T, A> Visitor<'de> for UntaggedVisitor<V, (T, A)>
+where
+    V: Variants<T, A>,
+{
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("any variant of an untagged enum")
+    }
+
+    fn visit_seq<S>(self, trials: S) -> Result<T, S::Error>
+    where
+        S: de::SeqAccess<'de>,
+    {
+        self.variants.try_seq(trials)
+    }
+
+    /// Other deserializers than [ValueDeserializer] are handled like
+    /// `#[serde(untagged)]`.
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<T, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+
+        self.variants
+            .try_value(&value)
+            .ok_or_else(|| de::Error::custom("data did not match any variant"))
+    }
+}
+
+/// The same value as each element, for [deserialize_untagged]. The keys
+/// recorded by failed trials are dropped, and their errors are kept to report
+/// the one which got the furthest.
+struct Trials<'a> {
+    value: &'a Value,
+    path: Vec<Segment>,
+    state: &'a State,
+    errors: Vec<Error>,
+}
+
+impl Trials<'_> {
+    /// Returns the first error with the longest path.
+    fn furthest_error(self) -> Option<Error> {
+        self.errors.into_iter().reduce(|furthest, err| {
+            if err.path().len() > furthest.path().len() {
+                err
+            } else {
+                furthest
+            }
+        })
+    }
+}
+
+impl<'de> de::SeqAccess<'de> for &mut Trials<'de> {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        let snapshot = self.state.snapshot();
+
+        let result = seed.deserialize(ValueDeserializer {
+            value: self.value,
+            path: self.path.clone(),
+            state: self.state,
+        });
+
+        match result {
+            Ok(v) => Ok(Some(v)),
+            Err(err) => {
+                let err = err.at(&self.path);
+                self.state.restore(snapshot);
+                self.errors.push(err.clone());
+                Err(err)
+            }
+        }
+    }
+}
+
+/// A deserializer for [Value] which records the fields of structs and the
+/// ignored keys, by path.
+struct ValueDeserializer<'a> {
+    value: &'a Value,
+    path: Vec<Segment>,
+    state: &'a State,
+}
+
+impl ValueDeserializer<'_> {
+    fn child(&self, segment: Segment) -> Vec<Segment> {
+        let mut path = self.path.clone();
+        path.push(segment);
+        path
+    }
+}
+
+impl<'de> de::Deserializer<'de> for ValueDeserializer<'de> {
+    type Error = Error;
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map identifier
+    }
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        let result = match self.value {
+            Value::Null => visitor.visit_unit(),
+            Value::Bool(v) => visitor.visit_bool(*v),
+            Value::Number(n) => {
+                if let Some(v) = n.as_u64() {
+                    visitor.visit_u64(v)
+                } else if let Some(v) = n.as_i64() {
+                    visitor.visit_i64(v)
+                } else {
+                    visitor.visit_f64(n.as_f64().unwrap_or_default())
+                }
+            }
+            Value::String(v) => visitor.visit_borrowed_str(v),
+            Value::Array(items) => visitor.visit_seq(SeqAccess {
+                iter: items.iter().enumerate(),
+                path: self.path.clone(),
+                state: self.state,
+            }),
+            Value::Object(map) => visitor.visit_map(MapAccess {
+                iter: map.iter(),
+                value: None,
+                path: self.path.clone(),
+                state: self.state,
+            }),
+        };
+
+        result.map_err(|err| err.at(&self.path))
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        if name != UNTAGGED {
+            return visitor.visit_newtype_struct(self);
+        }
+
+        let mut trials = Trials {
+            value: self.value,
+            path: self.path,
+            state: self.state,
+            errors: vec![],
+        };
+
+        match visitor.visit_seq(&mut trials) {
+            Ok(v) => Ok(v),
+            Err(err) => Err(trials.furthest_error().unwrap_or(err)),
+        }
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.state
+            .fields
+            .borrow_mut()
+            .insert(display_path(&self.path), fields);
+
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        let result = match self.value {
+            Value::String(variant) => visitor.visit_enum(variant.as_str().into_deserializer()),
+            Value::Object(map) if map.len() == 1 => {
+                let (variant, value) = map.iter().next().unwrap();
+
+                visitor.visit_enum(EnumAccess {
+                    variant,
+                    value: ValueDeserializer {
+                        value,
+                        path: self.child(Segment::Key(variant.clone())),
+                        state: self.state,
+                    },
+                })
+            }
+            _ => Err(de::Error::invalid_type(
+                de::Unexpected::Other(value_kind(self.value)),
+                &"a string or an object with a single key",
+            )),
+        };
+
+        result.map_err(|err| err.at(&self.path))
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        if let Some(Segment::Key(..)) = self.path.last() {
+            self.state.ignored.borrow_mut().push(self.path);
+        }
+
+        visitor.visit_unit()
+    }
+}
+
+fn value_kind(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(..) => "boolean",
+        Value::Number(..) => "number",
+        Value::String(..) => "string",
+        Value::Array(..) => "array",
+        Value::Object(..) => "object",
+    }
+}
+
+struct SeqAccess<'a, I> {
+    iter: I,
+    path: Vec<Segment>,
+    state: &'a State,
+}
+
+impl<'de, I> de::SeqAccess<'de> for SeqAccess<'de, I>
+where
+    I: Iterator<Item = (usize, &'de Value)>,
+{
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        let (index, value) = match self.iter.next() {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+
+        let mut path = self.path.clone();
+        path.push(Segment::Index(index));
+
+        seed.deserialize(ValueDeserializer {
+            value,
+            path,
+            state: self.state,
+        })
+        .map(Some)
+    }
+}
+
+struct MapAccess<'a> {
+    iter: serde_json::map::Iter<'a>,
+    value: Option<(&'a String, &'a Value)>,
+    path: Vec<Segment>,
+    state: &'a State,
+}
+
+impl<'de> de::MapAccess<'de> for MapAccess<'de> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        let (key, value) = match self.iter.next() {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+
+        self.value = Some((key, value));
+
+        let mut path = self.path.clone();
+        path.push(Segment::Key(key.clone()));
+
+        seed.deserialize(BorrowedStrDeserializer::<Error>::new(key))
+            .map(Some)
+            .map_err(|err| err.at(&path))
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let (key, value) = self
+            .value
+            .take()
+            .ok_or_else(|| de::Error::custom("value is missing"))?;
+
+        let mut path = self.path.clone();
+        path.push(Segment::Key(key.clone()));
+
+        seed.deserialize(ValueDeserializer {
+            value,
+            path,
+            state: self.state,
+        })
+    }
+}
+
+struct EnumAccess<'a> {
+    variant: &'a str,
+    value: ValueDeserializer<'a>,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumAccess<'de> {
+    type Error = Error;
+    type Variant = ValueDeserializer<'de>;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let variant = seed.deserialize(BorrowedStrDeserializer::<Error>::new(self.variant))?;
+
+        Ok((variant, self.value))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for ValueDeserializer<'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        de::Deserialize::deserialize(self)
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V>(self, _: usize, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_seq(self, visitor)
+    }
+
+    fn struct_variant<V>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_struct(self, "", fields, visitor)
+    }
+}
@@ -0,0 +1,182 @@
+//! Reporting of internal panics.
+//!
+//! The panic hook records the message, the location and the backtrace of a
+//! panic, and [try_with](crate::util::try_with) adds the filename once the
+//! panic is caught. The report is logged to the sink set by [set_sink], and
+//! kept until it's taken with [take_caught].
+//!
+//! The hook, the sink and the reports are shared by the css, html and linter
+//! crates, so an addon which links several of them reports every panic the
+//! same way.
+
+use std::{
+    any::Any,
+    backtrace::{Backtrace, BacktraceStatus},
+    cell::{Cell, RefCell},
+    env, fmt,
+    fs::OpenOptions,
+    io::{stderr, Write},
+    panic::{catch_unwind, set_hook, AssertUnwindSafe},
+    path::PathBuf,
+    sync::{Mutex, Once, PoisonError},
+};
+
+use anyhow::anyhow;
+
+#[derive(Debug, Clone)]
+pub struct PanicReport {
+    pub message: String,
+    /// Like `src/lib.rs:10:5`.
+    pub location: Option<String>,
+    /// Captured if `RUST_BACKTRACE` or `SWC_DEBUG` is set.
+    pub backtrace: Option<String>,
+    pub filename: Option<String>,
+}
+
+/// Where panics are logged.
+pub enum Sink {
+    Stderr,
+    None,
+    /// Appended to.
+    File(PathBuf),
+}
+
+static SINK: Mutex<Sink> = Mutex::new(Sink::Stderr);
+
+thread_local! {
+    /// The report of a panic which is being unwound.
+    static PENDING: RefCell<Option<PanicReport>> = RefCell::new(None);
+    /// The report of the last panic caught by [catch].
+    static CAUGHT: RefCell<Option<PanicReport>> = RefCell::new(None);
+    /// The number of active [catch] calls.
+    static CATCHING: Cell<usize> = Cell::new(0);
+}
+
+/// Sets where panics are logged. Defaults to [Sink::Stderr].
+pub fn set_sink(sink: Sink) {
+    *SINK.lock().unwrap_or_else(PoisonError::into_inner) = sink;
+}
+
+/// Installs the panic hook, which captures a backtrace if `RUST_BACKTRACE`
+/// or `SWC_DEBUG` is set. Calling it again does nothing.
+pub fn install_hook() {
+    static INSTALL: Once = Once::new();
+
+    INSTALL.call_once(set_report_hook);
+}
+
+fn set_report_hook() {
+    let force_backtrace =
+        cfg!(debug_assertions) || env::var("SWC_DEBUG").unwrap_or_default() == "1";
+
+    set_hook(Box::new(move |info| {
+        let backtrace = if force_backtrace {
+            Backtrace::force_capture()
+        } else {
+            Backtrace::capture()
+        };
+
+        let report = PanicReport {
+            message: payload_message(info.payload()),
+            location: info.location().map(|l| l.to_string()),
+            backtrace: (backtrace.status() == BacktraceStatus::Captured)
+                .then(|| backtrace.to_string()),
+            filename: None,
+        };
+
+        // Nothing else reports a panic which is not caught.
+        if CATCHING.try_with(Cell::get).unwrap_or_default() == 0 {
+            log(&report);
+        }
+
+        let _ = PENDING.try_with(|p| *p.borrow_mut() = Some(report));
+    }));
+}
+
+/// Runs `op`, returning the report of its panic.
+pub fn catch<F, Ret>(op: F) -> Result<Ret, PanicReport>
+where
+    F: FnOnce() -> Ret,
+{
+    PENDING.with(|p| p.borrow_mut().take());
+    CATCHING.with(|c| c.set(c.get() + 1));
+
+    let result = catch_unwind(AssertUnwindSafe(op));
+
+    CATCHING.with(|c| c.set(c.get() - 1));
+
+    result.map_err(|payload| {
+        // The hook does not run on this thread for a panic of another thread
+        // which is resumed here.
+        PENDING
+            .with(|p| p.borrow_mut().take())
+            .unwrap_or_else(|| PanicReport {
+                message: payload_message(&*payload),
+                location: None,
+                backtrace: None,
+                filename: None,
+            })
+    })
+}
+
+/// Logs `report`, and keeps it for [take_caught].
+pub fn caught(report: PanicReport) -> anyhow::Error {
+    log(&report);
+
+    let err = match &report.location {
+        Some(location) => anyhow!("internal panic at {}: {}", location, report.message),
+        None => anyhow!("internal panic: {}", report.message),
+    };
+
+    CAUGHT.with(|c| *c.borrow_mut() = Some(report));
+
+    err
+}
+
+/// Takes the report of the last panic caught on this thread.
+pub fn take_caught() -> Option<PanicReport> {
+    CAUGHT.with(|c| c.borrow_mut().take())
+}
+
+impl fmt::Display for PanicReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "internal panic")?;
+        if let Some(location) = &self.location {
+            write!(f, " at {}", location)?;
+        }
+        if let Some(filename) = &self.filename {
+            write!(f, " while processing `{}`", filename)?;
+        }
+        writeln!(f, ": {}", self.message)?;
+        if let Some(backtrace) = &self.backtrace {
+            writeln!(f, "{}", backtrace)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn payload_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else {
+        "unknown panic message".into()
+    }
+}
+
+fn log(report: &PanicReport) {
+    let sink = SINK.lock().unwrap_or_else(PoisonError::into_inner);
+    let text = report.to_string();
+
+    let _ = match &*sink {
+        Sink::Stderr => stderr().write_all(text.as_bytes()),
+        Sink::None => Ok(()),
+        Sink::File(path) => OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut f| f.write_all(text.as_bytes())),
+    };
+}
@@ -0,0 +1,321 @@
+//! JSON Schema of the options, for the autocompletion of editors and to
+//! generate the types of `options.ts`.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    env, fs,
+    path::Path,
+};
+
+use anyhow::Context;
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde_json::{json, Value};
+
+/// Returns the schema of the options `T`, with `unknownOptions`.
+pub fn root_schema<T>() -> anyhow::Result<Value>
+where
+    T: JsonSchema,
+{
+    let schema = SchemaGenerator::default().into_root_schema_for::<T>();
+    let mut schema = serde_json::to_value(schema).context("failed to serialize the schema")?;
+
+    // Handled by `parse_options` instead of the option types.
+    if let Some(properties) = schema
+        .pointer_mut("/properties")
+        .and_then(Value::as_object_mut)
+    {
+        properties.insert(
+            "unknownOptions".into(),
+            json!({
+                "description": "Whether unknown keys in these options fail, or are reported in `warnings` of the output. Defaults to `error`.",
+                "enum": ["error", "warn"],
+                "default": "error",
+            }),
+        );
+    }
+
+    Ok(schema)
+}
+
+pub fn from_json(schema: Value) -> Schema {
+    serde_json::from_value(schema).expect("invalid schema")
+}
+
+/// Returns the schema of a value like `value`, which is the default value of
+/// an option. The properties of an object are listed, but others are allowed,
+/// as they may be omitted from the default value.
+pub fn schema_of_default(value: &Value) -> Value {
+    match value {
+        Value::Bool(..) => json!({ "type": "boolean" }),
+        Value::Number(..) => json!({ "type": "number" }),
+        Value::String(..) => json!({ "type": "string" }),
+        Value::Array(..) => json!({ "type": "array" }),
+        Value::Object(properties) => json!({
+            "type": "object",
+            "properties": properties
+                .iter()
+                .map(|(key, value)| (key.clone(), schema_of_default(value)))
+                .collect::<serde_json::Map<_, _>>(),
+        }),
+        Value::Null => json!({}),
+    }
+}
+
+/// Generates the TypeScript types of `schema`, as returned by the
+/// `options_schema` of a core crate, for `options.ts` of its package.
+///
+/// `types` names the type of the options of each key of `schema`. The
+/// definitions they refer to are declared with their own name.
+pub fn typescript_types(schema: &Value, types: &[(&str, &str)]) -> String {
+    let mut ts = String::from(
+        "// Generated from the JSON Schema of the options by the tests of the core\n// crate. Run \
+         them with `UPDATE_OPTIONS_TS=1` to update it.\n",
+    );
+    let mut definitions = BTreeMap::new();
+
+    for (key, name) in types {
+        ts.push_str(&ts_declaration(name, &schema[key]));
+
+        for (name, definition) in schema[key]["definitions"].as_object().into_iter().flatten() {
+            if let Some(other) = definitions.insert(name, definition) {
+                assert_eq!(other, definition, "`{}` differs between the options", name);
+            }
+        }
+    }
+
+    for (name, definition) in definitions {
+        ts.push_str(&ts_declaration(name, definition));
+    }
+
+    ts
+}
+
+/// Compares `generated` with the file at `path`, or writes it there if
+/// `UPDATE_OPTIONS_TS` is set. Returns the first line which differs.
+pub fn check_generated(path: &Path, generated: &str) -> Result<(), String> {
+    if env::var_os("UPDATE_OPTIONS_TS").is_some() {
+        fs::write(path, generated).map_err(|err| err.to_string())?;
+    }
+
+    let committed = fs::read_to_string(path).map_err(|err| err.to_string())?;
+    let mut committed_lines = committed.lines();
+
+    for (i, line) in generated.lines().enumerate() {
+        match committed_lines.next() {
+            Some(committed) if committed == line => {}
+            committed => {
+                return Err(format!(
+                    "{} is outdated at line {}: expected {:?}, found {:?}. Run the tests with \
+                     `UPDATE_OPTIONS_TS=1` to update it.",
+                    path.display(),
+                    i + 1,
+                    line,
+                    committed.unwrap_or_default(),
+                ))
+            }
+        }
+    }
+
+    match committed_lines.next() {
+        Some(line) => Err(format!(
+            "{} has the extra line {:?}. Run the tests with `UPDATE_OPTIONS_TS=1` to update it.",
+            path.display(),
+            line
+        )),
+        None => Ok(()),
+    }
+}
+
+fn ts_declaration(name: &str, schema: &Value) -> String {
+    format!(
+        "\n{}export type {} = {};\n",
+        ts_doc(schema, 0),
+        name,
+        ts_type(schema, 0)
+    )
+}
+
+/// Returns the description of `schema` as a doc comment, wrapped at 80
+/// columns.
+fn ts_doc(schema: &Value, indent: usize) -> String {
+    let description = match schema["description"].as_str() {
+        Some(description) => description,
+        None => return String::new(),
+    };
+    let pad = " ".repeat(indent);
+    let width = 80 - indent - 3;
+
+    let mut doc = format!("{}/**\n", pad);
+    for (i, paragraph) in description.split("\n\n").enumerate() {
+        if i > 0 {
+            doc.push_str(&format!("{} *\n", pad));
+        }
+
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            if !line.is_empty() && line.len() + 1 + word.len() > width {
+                doc.push_str(&format!("{} * {}\n", pad, line));
+                line.clear();
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+        doc.push_str(&format!("{} * {}\n", pad, line));
+    }
+    doc.push_str(&format!("{} */\n", pad));
+    doc
+}
+
+fn ts_type(schema: &Value, indent: usize) -> String {
+    if let Some(reference) = schema["$ref"].as_str() {
+        return reference.trim_start_matches("#/definitions/").to_string();
+    }
+    if let Some(schemas) = schema["allOf"].as_array() {
+        return ts_types(schemas, " & ", indent);
+    }
+    if let Some(schemas) = schema["anyOf"].as_array().or(schema["oneOf"].as_array()) {
+        return ts_types(schemas, " | ", indent);
+    }
+    if let Some(values) = schema["enum"].as_array() {
+        return values
+            .iter()
+            .map(Value::to_string)
+            .collect::<Vec<_>>()
+            .join(" | ");
+    }
+
+    match &schema["type"] {
+        Value::String(ty) => ts_type_named(ty, schema, indent),
+        Value::Array(types) => types
+            .iter()
+            .map(|ty| ts_type_named(ty.as_str().unwrap_or_default(), schema, indent))
+            .collect::<Vec<_>>()
+            .join(" | "),
+        _ => "unknown".into(),
+    }
+}
+
+fn ts_types(schemas: &[Value], separator: &str, indent: usize) -> String {
+    schemas
+        .iter()
+        .map(|schema| ts_type(schema, indent))
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// Returns the type of `schema` for its JSON type `ty`.
+fn ts_type_named(ty: &str, schema: &Value, indent: usize) -> String {
+    match ty {
+        "string" => "string".into(),
+        "boolean" => "boolean".into(),
+        "integer" | "number" => "number".into(),
+        "null" => "null".into(),
+        "array" => match &schema["items"] {
+            Value::Array(items) => {
+                // The elements after `minItems` may be omitted.
+                let min = schema["minItems"].as_u64().unwrap_or(items.len() as u64);
+                let items = items
+                    .iter()
+                    .enumerate()
+                    .map(|(i, item)| {
+                        let ty = ts_type(item, indent);
+                        if (i as u64) < min {
+                            ty
+                        } else if is_compound(&ty) {
+                            format!("({})?", ty)
+                        } else {
+                            format!("{}?", ty)
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                format!("[{}]", items.join(", "))
+            }
+            Value::Object(..) => {
+                let item = ts_type(&schema["items"], indent);
+                if is_compound(&item) {
+                    format!("({})[]", item)
+                } else {
+                    format!("{}[]", item)
+                }
+            }
+            _ => "unknown[]".into(),
+        },
+        "object" => ts_object(schema, indent),
+        _ => "unknown".into(),
+    }
+}
+
+/// Whether `ty` is a union or an intersection, which needs parentheses in an
+/// array type.
+fn is_compound(ty: &str) -> bool {
+    let mut depth = 0usize;
+
+    for (i, b) in ty.bytes().enumerate() {
+        match b {
+            b'[' | b'{' | b'(' | b'<' => depth += 1,
+            b']' | b'}' | b')' | b'>' => depth = depth.saturating_sub(1),
+            b'|' | b'&' if depth == 0 && ty[..i].ends_with(' ') => return true,
+            _ => {}
+        }
+    }
+
+    false
+}
+
+fn ts_object(schema: &Value, indent: usize) -> String {
+    let additional = match &schema["additionalProperties"] {
+        Value::Bool(false) => None,
+        Value::Object(..) => Some(ts_type(&schema["additionalProperties"], indent + 2)),
+        _ => Some("unknown".to_string()),
+    };
+    let properties = match schema["properties"].as_object() {
+        Some(properties) if !properties.is_empty() => properties,
+        _ => {
+            return match additional {
+                Some(ty) => format!("Record<string, {}>", ty),
+                None => "{}".into(),
+            }
+        }
+    };
+    let required = schema["required"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect::<BTreeSet<_>>();
+    let pad = " ".repeat(indent + 2);
+
+    let mut ts = String::from("{\n");
+    for (key, property) in properties {
+        let is_ident = key.chars().enumerate().all(|(i, c)| {
+            c.is_ascii_alphabetic() || c == '_' || c == '$' || (i > 0 && c.is_ascii_digit())
+        });
+
+        ts.push_str(&ts_doc(property, indent + 2));
+        ts.push_str(&format!(
+            "{}{}{}: {};\n",
+            pad,
+            if is_ident {
+                key.clone()
+            } else {
+                Value::from(key.as_str()).to_string()
+            },
+            if required.contains(key.as_str()) {
+                ""
+            } else {
+                "?"
+            },
+            ts_type(property, indent + 2)
+        ));
+    }
+    // Keys which are not listed are allowed, unless `additionalProperties` is
+    // false.
+    if let Some(ty) = additional {
+        ts.push_str(&format!("{}[key: string]: {};\n", pad, ty));
+    }
+    ts.push_str(&" ".repeat(indent));
+    ts.push('}');
+    ts
+}
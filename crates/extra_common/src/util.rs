@@ -0,0 +1,35 @@
+use anyhow::Error;
+use swc_common::{errors::Handler, sync::Lrc, FileName, FilePathMapping, SourceMap};
+use swc_error_reporters::handler::{try_with_handler, HandlerOpts};
+
+use crate::panic::{catch, caught};
+
+/// Runs `op` with a handler which reports errors like swc, turning a panic
+/// into an error with a [report](crate::panic::PanicReport).
+pub fn try_with<F, Ret>(op: F) -> Result<Ret, Error>
+where
+    F: FnOnce(&Lrc<SourceMap>, &Handler) -> Result<Ret, Error>,
+{
+    let cm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+    try_with_handler(
+        cm.clone(),
+        HandlerOpts {
+            skip_filename: false,
+            ..Default::default()
+        },
+        |handler| {
+            //
+            let mut report = match catch(|| op(&cm, handler)) {
+                Ok(v) => return v,
+                Err(report) => report,
+            };
+
+            report.filename = cm.files().first().and_then(|f| match &f.name {
+                FileName::Anon => None,
+                name => Some(name.to_string()),
+            });
+
+            Err(caught(report))
+        },
+    )
+}
@@ -0,0 +1,275 @@
+use std::{cell::Cell, env, fs, path::PathBuf, thread, time::Duration};
+
+use extra_common::{
+    abort::{self, AbortFlag, Aborted, Ticker},
+    cache::Cache,
+    limits::{exceeded, take_exceeded, Limit, LimitExceeded},
+    options::parse_options,
+    panic::{self, Sink},
+};
+use serde::Deserialize;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Options {
+    #[serde(default)]
+    minify: Option<MinifyOptions>,
+    #[serde(default)]
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MinifyOptions {
+    #[serde(default)]
+    remove_comments: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum Rule {
+    Off,
+    Level(u8),
+}
+
+#[test]
+fn rejects_unknown_keys_with_suggestions() {
+    let err = parse_options::<Options>(r#"{ "minify": { "remove_comments": true } }"#)
+        .unwrap_err()
+        .to_string();
+
+    assert_eq!(
+        err,
+        "unknown option `minify.remove_comments`, did you mean `removeComments`?"
+    );
+}
+
+#[test]
+fn warns_about_unknown_keys() {
+    let (opts, warnings) = parse_options::<Options>(
+        r#"{ "unknownOptions": "warn", "minify": { "removeComments": true, "foo": 1 }, "rules": ["off", { "level": 2 }] }"#,
+    )
+    .unwrap();
+
+    assert!(opts.minify.unwrap().remove_comments);
+    assert!(matches!(opts.rules[..], [Rule::Off, Rule::Level(2)]));
+    assert_eq!(warnings, vec!["unknown option `minify.foo`"]);
+}
+
+#[test]
+fn reports_the_path_of_invalid_values() {
+    let err = parse_options::<Options>(r#"{ "rules": ["off", { "level": "high" }] }"#)
+        .unwrap_err()
+        .to_string();
+
+    assert!(
+        err.starts_with("invalid option `rules[1].level`:"),
+        "{}",
+        err
+    );
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("extra-common-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn cache_returns_stored_results() {
+    let dir = temp_dir("stored");
+    let cache = Cache::new("test@0.0.0");
+    cache.enable(dir.to_str().unwrap(), None).unwrap();
+
+    let calls = Cell::new(0);
+    let compute = || {
+        calls.set(calls.get() + 1);
+        Ok("a{}".to_string())
+    };
+
+    let options = r#"{ "a": 1, "b": 2 }"#;
+    let reordered = r#"{ "b": 2, "a": 1 }"#;
+    let first: String = cache
+        .cached("minify", options, b"a {}", None, compute)
+        .unwrap();
+    let second: String = cache
+        .cached("minify", reordered, b"a {}", None, compute)
+        .unwrap();
+    assert_eq!(first, second);
+    assert_eq!(calls.get(), 1);
+
+    let _: String = cache
+        .cached("minify", options, b"b {}", None, compute)
+        .unwrap();
+    let _: String = cache
+        .cached("format", options, b"a {}", None, compute)
+        .unwrap();
+    assert_eq!(calls.get(), 3);
+
+    cache.clear().unwrap();
+    let _: String = cache
+        .cached("minify", options, b"a {}", None, compute)
+        .unwrap();
+    assert_eq!(calls.get(), 4);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn cache_skips_results_with_stats() {
+    let dir = temp_dir("stats");
+    let cache = Cache::new("test@0.0.0");
+    cache.enable(dir.to_str().unwrap(), None).unwrap();
+
+    let calls = Cell::new(0);
+    for _ in 0..2 {
+        let _: String = cache
+            .cached("minify", r#"{ "stats": true }"#, b"a {}", None, || {
+                calls.set(calls.get() + 1);
+                Ok(String::new())
+            })
+            .unwrap();
+    }
+    assert_eq!(calls.get(), 2);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn cache_is_keyed_by_package() {
+    let dir = temp_dir("package");
+    let old = Cache::new("test@0.0.0");
+    let new = Cache::new("test@0.0.1");
+    old.enable(dir.to_str().unwrap(), None).unwrap();
+    new.enable(dir.to_str().unwrap(), None).unwrap();
+
+    let _: String = old
+        .cached("minify", "{}", b"a {}", None, || Ok("old".into()))
+        .unwrap();
+    let result: String = new
+        .cached("minify", "{}", b"a {}", None, || Ok("new".into()))
+        .unwrap();
+    assert_eq!(result, "new");
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn cache_evicts_over_max_size() {
+    let dir = temp_dir("evict");
+    let cache = Cache::new("test@0.0.0");
+    cache.enable(dir.to_str().unwrap(), Some(100)).unwrap();
+
+    for i in 0..10 {
+        let code = format!("a{} {{}}", i);
+        let _: String = cache
+            .cached("minify", "{}", code.as_bytes(), None, || Ok("x".repeat(30)))
+            .unwrap();
+    }
+
+    let size: u64 = fs::read_dir(&dir)
+        .unwrap()
+        .map(|e| e.unwrap().metadata().unwrap().len())
+        .sum();
+    assert!(size <= 100, "{}", size);
+
+    assert!(cache.enable(dir.to_str().unwrap(), Some(0)).is_err());
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn panics_are_reported_once_caught() {
+    panic::install_hook();
+    panic::install_hook();
+    panic::set_sink(Sink::None);
+
+    let report = panic::catch(|| panic!("boom")).unwrap_err();
+    assert_eq!(report.message, "boom");
+    assert!(report.location.unwrap().contains("api.rs"));
+
+    let report = panic::catch(|| panic!("again")).unwrap_err();
+    let err = panic::caught(report);
+    assert!(err.to_string().ends_with(": again"), "{}", err);
+    assert_eq!(panic::take_caught().unwrap().message, "again");
+    assert!(panic::take_caught().is_none());
+}
+
+#[test]
+fn limits_have_a_code_and_name_their_option() {
+    for (limit, code, option) in [
+        (Limit::InputBytes, "SWC_LIMIT_INPUT_BYTES", "maxInputBytes"),
+        (Limit::Depth, "SWC_LIMIT_DEPTH", "maxDepth"),
+        (Limit::Nodes, "SWC_LIMIT_NODES", "maxNodes"),
+        (Limit::Timeout, "SWC_LIMIT_TIMEOUT", "timeoutMs"),
+    ] {
+        assert_eq!(limit.code(), code);
+
+        let err = exceeded(limit, 8);
+        assert_eq!(
+            err.to_string(),
+            format!("exceeded `limits.{}` of 8", option)
+        );
+        assert_eq!(take_exceeded(), Some(limit));
+        assert_eq!(take_exceeded(), None);
+    }
+}
+
+#[test]
+fn tickers_stop_once_aborted() {
+    let flag = AbortFlag::default();
+
+    abort::scope(Some(&flag), || {
+        let mut ticker = Ticker::default();
+        assert!((0..10_000).all(|_| ticker.tick()));
+
+        flag.abort();
+        assert!((0..10_000).any(|_| !ticker.tick()));
+        assert!(!ticker.tick());
+        assert!(abort::check().unwrap_err().is::<Aborted>());
+    });
+
+    assert!(abort::check().is_ok());
+}
+
+#[test]
+fn tickers_stop_once_the_deadline_has_passed() {
+    {
+        let _deadline = abort::deadline(Some(0));
+        thread::sleep(Duration::from_millis(1));
+
+        let mut ticker = Ticker::default();
+        assert!((0..10_000).any(|_| !ticker.tick()));
+        assert!(abort::check().unwrap_err().is::<LimitExceeded>());
+        assert_eq!(take_exceeded(), Some(Limit::Timeout));
+    }
+
+    assert!(!abort::is_scoped());
+    assert!(abort::check().is_ok());
+}
+
+#[cfg(feature = "node")]
+#[test]
+fn batch_reports_a_panic_with_its_file() {
+    use extra_common::node::batch::{run_batch, File};
+
+    panic::install_hook();
+    panic::set_sink(Sink::None);
+
+    let files = ["a.css", "b.css"].map(|filename| File {
+        code: vec![],
+        filename: Some(filename.into()),
+    });
+
+    let results = run_batch(&files, "{}", |file, _: &Options, _| {
+        if file.filename.as_deref() == Some("b.css") {
+            panic!("boom");
+        }
+        Ok(file.code.len())
+    })
+    .unwrap();
+
+    assert_eq!(results[0], Ok(0));
+    let err = results[1].as_ref().unwrap_err();
+    assert!(err.contains("boom"), "{}", err);
+}
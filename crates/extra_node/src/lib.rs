@@ -0,0 +1,16 @@
+//! One addon with the APIs of `@swc/css`, `@swc/html` and `@swc/linter` as
+//! `css`, `html` and `linter` of its exports, so swc is shipped only once.
+//! Each is a feature, enabled by default.
+//!
+//! The napi functions register themselves, so the parts only need to be
+//! linked.
+//!
+//! The parts share the panic hook of `extra_common`, which is installed once,
+//! so `setPanicSink` of any of them sets where the panics of all are logged.
+
+#[cfg(feature = "css")]
+extern crate css_node;
+#[cfg(feature = "html")]
+extern crate html_node;
+#[cfg(feature = "linter")]
+extern crate linter_node;
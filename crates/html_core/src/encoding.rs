@@ -0,0 +1,347 @@
+//! Decoding of the input and escaping of the output, for documents which are
+//! not UTF-8.
+
+use std::{borrow::Cow, fmt::Write};
+
+use anyhow::Context;
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8, WINDOWS_1252, X_USER_DEFINED};
+
+use crate::diagnostic::Diagnostic;
+
+/// The input decoded to UTF-8.
+pub struct Decoded<'a> {
+    pub code: Cow<'a, str>,
+    encoding: &'static Encoding,
+    /// True if invalid bytes were replaced with U+FFFD.
+    lossy: bool,
+}
+
+impl Decoded<'_> {
+    /// Returns a warning if invalid bytes were replaced.
+    pub fn diagnostic(&self) -> Option<Diagnostic> {
+        if !self.lossy {
+            return None;
+        }
+
+        Some(Diagnostic {
+            level: "warning".into(),
+            message: format!(
+                "the input contains bytes which are invalid in {} and were replaced with U+FFFD",
+                self.encoding.name()
+            ),
+            code: Some("lossy-decoding".into()),
+            span: None,
+            code_frame: None,
+            fatal: false,
+        })
+    }
+}
+
+/// Decodes `bytes` with the encoding of, in order of precedence, the byte
+/// order mark, `label`, the `<meta>` declaring it if `prescan` and UTF-8.
+pub fn decode<'a>(
+    bytes: &'a [u8],
+    label: Option<&str>,
+    prescan: bool,
+) -> anyhow::Result<Decoded<'a>> {
+    let (encoding, bom_len) = match Encoding::for_bom(bytes) {
+        Some(v) => v,
+        None => {
+            let encoding = match label {
+                Some(label) => for_label(label)?,
+                None if prescan => meta_charset(bytes).unwrap_or(UTF_8),
+                None => UTF_8,
+            };
+
+            (encoding, 0)
+        }
+    };
+
+    let (code, lossy) = encoding.decode_without_bom_handling(&bytes[bom_len..]);
+
+    Ok(Decoded {
+        code,
+        encoding,
+        lossy,
+    })
+}
+
+/// Escapes the characters of `code` which cannot be encoded with
+/// `output_encoding`, or all non-ASCII characters if `ascii_only`.
+///
+/// Character references are used, except in `<script>` and `<style>` where
+/// they are not decoded, and the escapes of JS and CSS are used instead.
+pub fn escape_output(
+    code: String,
+    output_encoding: Option<&str>,
+    ascii_only: bool,
+) -> anyhow::Result<String> {
+    let encoding = match output_encoding {
+        Some(label) => for_label(label)?.output_encoding(),
+        None => UTF_8,
+    };
+
+    if code.is_ascii() || (!ascii_only && encoding == UTF_8) {
+        return Ok(code);
+    }
+
+    let mut buf = String::with_capacity(code.len());
+    let mut state = State::Text;
+
+    for (idx, c) in code.char_indices() {
+        let rest = &code[idx..];
+
+        state = match state {
+            State::Text => match raw_text_tag(rest, "<") {
+                Some(tag) => State::StartTag { tag, quote: None },
+                None => State::Text,
+            },
+            State::StartTag { tag, quote } => match (quote, c) {
+                (Some(q), c) if q == c => State::StartTag { tag, quote: None },
+                (None, '"' | '\'') => State::StartTag {
+                    tag,
+                    quote: Some(c),
+                },
+                (None, '>') => State::RawText(tag),
+                _ => state,
+            },
+            State::RawText(tag) => match raw_text_tag(rest, "</") {
+                Some(end) if end == tag => State::Text,
+                _ => state,
+            },
+        };
+
+        if c.is_ascii() || (!ascii_only && can_encode(encoding, c)) {
+            buf.push(c);
+            continue;
+        }
+
+        match state {
+            State::RawText("script") => {
+                for unit in c.encode_utf16(&mut [0; 2]) {
+                    write!(buf, "\\u{:04x}", unit).unwrap();
+                }
+            }
+            // The space ends the escape, and is consumed by it.
+            State::RawText(_) => write!(buf, "\\{:x} ", c as u32).unwrap(),
+            _ => write!(buf, "&#x{:x};", c as u32).unwrap(),
+        }
+    }
+
+    Ok(buf)
+}
+
+#[derive(Clone, Copy)]
+enum State {
+    Text,
+    StartTag {
+        tag: &'static str,
+        quote: Option<char>,
+    },
+    RawText(&'static str),
+}
+
+/// Returns `script` or `style` if `s` starts with `prefix` followed by the
+/// tag.
+fn raw_text_tag(s: &str, prefix: &str) -> Option<&'static str> {
+    let s = s.strip_prefix(prefix)?;
+
+    ["script", "style"].into_iter().find(|tag| {
+        s.get(..tag.len())
+            .map_or(false, |name| name.eq_ignore_ascii_case(tag))
+            && s[tag.len()..].starts_with(|c: char| c.is_ascii_whitespace() || c == '/' || c == '>')
+    })
+}
+
+fn for_label(label: &str) -> anyhow::Result<&'static Encoding> {
+    Encoding::for_label(label.as_bytes()).with_context(|| format!("unknown encoding `{}`", label))
+}
+
+fn can_encode(encoding: &'static Encoding, c: char) -> bool {
+    let mut buf = [0; 4];
+    let (_, _, had_errors) = encoding.encode(c.encode_utf8(&mut buf));
+
+    !had_errors
+}
+
+/// Returns the encoding declared by a `<meta>` in the first 1024 bytes.
+///
+/// Simplified from https://html.spec.whatwg.org/multipage/parsing.html#prescan-a-byte-stream-to-determine-its-encoding
+fn meta_charset(bytes: &[u8]) -> Option<&'static Encoding> {
+    let bytes = bytes[..bytes.len().min(1024)].to_ascii_lowercase();
+    let mut pos = 0;
+
+    while pos < bytes.len() {
+        let rest = &bytes[pos..];
+
+        if rest.starts_with(b"<!--") {
+            // `<!-->` is a complete comment.
+            pos += 2 + find(&rest[2..], b"-->")? + 2;
+        } else if rest.starts_with(b"<meta")
+            && rest
+                .get(5)
+                .map_or(false, |&b| b.is_ascii_whitespace() || b == b'/')
+        {
+            pos += 5;
+
+            let mut got_pragma = false;
+            let mut need_pragma = None;
+            let mut charset = None;
+
+            while let Some((name, value, next)) = attribute(&bytes, pos) {
+                pos = next;
+
+                match &*name {
+                    b"http-equiv" if value == b"content-type" => got_pragma = true,
+                    b"content" if charset.is_none() => {
+                        if let Some(label) = content_charset(&value) {
+                            charset = Encoding::for_label(label);
+                            need_pragma = Some(true);
+                        }
+                    }
+                    b"charset" if charset.is_none() => {
+                        charset = Encoding::for_label(&value);
+                        need_pragma = Some(false);
+                    }
+                    _ => {}
+                }
+            }
+
+            match (need_pragma, charset) {
+                (Some(true), _) if !got_pragma => {}
+                (Some(_), Some(e)) if e == UTF_16BE || e == UTF_16LE => return Some(UTF_8),
+                (Some(_), Some(e)) if e == X_USER_DEFINED => return Some(WINDOWS_1252),
+                (Some(_), Some(e)) => return Some(e),
+                _ => {}
+            }
+        } else if rest[0] == b'<'
+            && (rest.get(1).map_or(false, u8::is_ascii_alphabetic)
+                || (rest.starts_with(b"</") && rest.get(2).map_or(false, u8::is_ascii_alphabetic)))
+        {
+            // Skip the attributes of other tags, which may contain `<meta`.
+            pos += rest
+                .iter()
+                .position(|&b| b.is_ascii_whitespace() || b == b'>')?;
+
+            while let Some((_, _, next)) = attribute(&bytes, pos) {
+                pos = next;
+            }
+        } else if rest.starts_with(b"<!") || rest.starts_with(b"</") || rest.starts_with(b"<?") {
+            pos += find(rest, b">")?;
+        }
+
+        pos += 1;
+    }
+
+    None
+}
+
+/// Returns the name and the value of the attribute at `pos`, and the position
+/// after it. Returns [None] at the end of the tag.
+fn attribute(bytes: &[u8], mut pos: usize) -> Option<(Vec<u8>, Vec<u8>, usize)> {
+    let skip_whitespace = |pos: &mut usize| {
+        while bytes.get(*pos).map_or(false, u8::is_ascii_whitespace) {
+            *pos += 1;
+        }
+    };
+
+    while bytes
+        .get(pos)
+        .map_or(false, |&b| b.is_ascii_whitespace() || b == b'/')
+    {
+        pos += 1;
+    }
+
+    if bytes.get(pos).map_or(true, |&b| b == b'>') {
+        return None;
+    }
+
+    let mut name = vec![];
+
+    while let Some(&b) = bytes.get(pos) {
+        if (b == b'=' && !name.is_empty()) || b.is_ascii_whitespace() || b == b'/' || b == b'>' {
+            break;
+        }
+        name.push(b);
+        pos += 1;
+    }
+
+    skip_whitespace(&mut pos);
+
+    if bytes.get(pos) != Some(&b'=') {
+        return Some((name, vec![], pos));
+    }
+
+    pos += 1;
+    skip_whitespace(&mut pos);
+
+    let mut value = vec![];
+
+    match bytes.get(pos) {
+        Some(&quote @ (b'"' | b'\'')) => {
+            pos += 1;
+
+            while let Some(&b) = bytes.get(pos) {
+                pos += 1;
+
+                if b == quote {
+                    break;
+                }
+                value.push(b);
+            }
+        }
+        _ => {
+            while let Some(&b) = bytes.get(pos) {
+                if b.is_ascii_whitespace() || b == b'>' {
+                    break;
+                }
+                value.push(b);
+                pos += 1;
+            }
+        }
+    }
+
+    Some((name, value, pos))
+}
+
+/// Extracts the label from a `content` like `text/html; charset=utf-8`.
+fn content_charset(content: &[u8]) -> Option<&[u8]> {
+    let mut rest = content;
+
+    loop {
+        rest = &rest[find(rest, b"charset")? + 7..];
+
+        if let Some(value) = trim_start(rest).strip_prefix(b"=") {
+            let value = trim_start(value);
+
+            return match value.first() {
+                Some(&quote @ (b'"' | b'\'')) => {
+                    let end = value[1..].iter().position(|&b| b == quote)?;
+
+                    Some(&value[1..end + 1])
+                }
+                _ => {
+                    let end = value
+                        .iter()
+                        .position(|&b| b.is_ascii_whitespace() || b == b';')
+                        .unwrap_or(value.len());
+
+                    Some(&value[..end]).filter(|v| !v.is_empty())
+                }
+            };
+        }
+    }
+}
+
+fn trim_start(bytes: &[u8]) -> &[u8] {
+    let start = bytes
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or(bytes.len());
+
+    &bytes[start..]
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
@@ -0,0 +1,738 @@
+//! The HTML minifier of `@swc/html`, without the bindings for Node.js.
+//!
+//! Each function takes the input as bytes, which are decoded with the
+//! `encoding` of the options, and returns the output with the recoverable
+//! errors. Options can be deserialized from JSON with [parse_options].
+
+use std::iter::once;
+
+use anyhow::{bail, Context};
+/// An aborted call stops while parsing, between the phases or while walking
+/// the tree.
+pub use extra_common::abort;
+pub use extra_common::{
+    diagnostic,
+    options::{parse_options, parse_options_value},
+    panic,
+};
+use extra_common::{options::deserialize_untagged, util::try_with};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use swc_atoms::js_word;
+use swc_cached::regex::CachedRegex;
+use swc_common::{errors::Handler, input::StringInput, sync::Lrc, FileName, SourceMap, DUMMY_SP};
+use swc_html::{
+    ast::{DocumentMode, Namespace},
+    codegen::{
+        writer::basic::{BasicHtmlWriter, BasicHtmlWriterConfig},
+        CodeGenerator, CodegenConfig, Emit,
+    },
+    parser::{
+        lexer::Lexer,
+        parser::{Parser, ParserConfig},
+    },
+};
+use swc_html_ast::{Document, DocumentFragment};
+use swc_html_minifier::{
+    minify_document, minify_document_fragment,
+    option::{
+        CollapseWhitespaces, MinifierType, MinifyCssOption, MinifyJsOption, MinifyJsonOption,
+        RemoveRedundantAttributes,
+    },
+};
+use tracing::{info_span, instrument};
+
+use crate::{
+    diagnostic::{to_diagnostic, Diagnostic},
+    encoding::{decode, escape_output, Decoded},
+    limits::{Limiter, Limits},
+    stats::{Phase, Stats, StatsRecorder},
+};
+
+mod encoding;
+pub mod limits;
+pub mod schema;
+pub mod stats;
+
+#[cfg_attr(
+    all(feature = "napi", not(feature = "namespace")),
+    napi_derive::napi(object)
+)]
+#[cfg_attr(
+    all(feature = "napi", feature = "namespace"),
+    napi_derive::napi(object, namespace = "html")
+)]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransformOutput {
+    /// The input as-is if it could not be parsed with `recover`.
+    pub code: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errors: Option<Vec<Diagnostic>>,
+    /// Unknown keys of the options, if `unknownOptions` is `"warn"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warnings: Option<Vec<String>>,
+    /// If `stats` is requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stats: Option<Stats>,
+}
+
+impl TransformOutput {
+    /// Reports the unknown keys of the options, returned by [parse_options].
+    pub fn with_warnings(mut self, warnings: &[String]) -> Self {
+        if !warnings.is_empty() {
+            self.warnings = Some(warnings.to_vec());
+        }
+        self
+    }
+
+    /// Reports the lossy decoding of `input`, and escapes `code` for the
+    /// output encoding.
+    fn encoded(
+        mut self,
+        input: &Decoded,
+        output_encoding: Option<&str>,
+        ascii_only: bool,
+    ) -> anyhow::Result<Self> {
+        if let Some(d) = input.diagnostic() {
+            self.errors.get_or_insert_with(Vec::new).push(d);
+        }
+        self.code = escape_output(self.code, output_encoding, ascii_only)?;
+        if let Some(stats) = &mut self.stats {
+            stats.output_size = self.code.len() as u32;
+        }
+        Ok(self)
+    }
+}
+
+#[cfg_attr(
+    all(feature = "napi", not(feature = "namespace")),
+    napi_derive::napi(object)
+)]
+#[cfg_attr(
+    all(feature = "napi", feature = "namespace"),
+    napi_derive::napi(object, namespace = "html")
+)]
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[schemars(deny_unknown_fields)]
+pub struct Attribute {
+    #[serde(default)]
+    pub namespace: Option<String>,
+    #[serde(default)]
+    pub prefix: Option<String>,
+    pub name: String,
+    #[serde(default)]
+    pub value: Option<String>,
+}
+
+#[cfg_attr(
+    all(feature = "napi", not(feature = "namespace")),
+    napi_derive::napi(object)
+)]
+#[cfg_attr(
+    all(feature = "napi", feature = "namespace"),
+    napi_derive::napi(object, namespace = "html")
+)]
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[schemars(deny_unknown_fields)]
+/// The context element or the form element of a fragment.
+pub struct Element {
+    pub tag_name: String,
+    pub namespace: String,
+    pub attributes: Vec<Attribute>,
+    pub is_self_closing: bool,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[schemars(deny_unknown_fields)]
+pub struct MinifyOptions {
+    #[serde(default)]
+    pub filename: Option<String>,
+
+    /// The encoding of the input, if it has no byte order mark, like
+    /// `shift_jis`. Defaults to the one declared by `<meta>` for documents, or
+    /// UTF-8. Invalid bytes are replaced and reported as a `lossy-decoding`
+    /// warning in `errors`.
+    #[serde(default)]
+    pub encoding: Option<String>,
+
+    /// Characters which cannot be encoded with this encoding are escaped. The
+    /// output is still returned as a string.
+    #[serde(default)]
+    pub output_encoding: Option<String>,
+
+    /// Escape all non-ASCII characters.
+    #[serde(default)]
+    pub ascii_only: bool,
+
+    /// Return `stats` in the output, with the size of the input and the output,
+    /// the duration of each phase and the number of nodes before and after.
+    #[serde(default)]
+    pub stats: bool,
+
+    /// Return the input as-is with the errors instead of throwing, if the
+    /// input cannot be parsed. The recoverable errors are limited by
+    /// `maxErrors`.
+    #[serde(default)]
+    pub recover: bool,
+
+    /// Fail if the parser reports any recoverable error.
+    #[serde(default)]
+    pub strict: bool,
+
+    /// The maximum number of recoverable errors to report.
+    #[serde(default)]
+    pub max_errors: Option<usize>,
+
+    /// Return `codeFrame` in the diagnostics. Defaults to true, but rendering
+    /// them is slow for many diagnostics.
+    #[serde(default = "true_by_default")]
+    pub code_frame: bool,
+
+    /// Fail on untrusted input which is too large or too deep.
+    #[serde(default)]
+    pub limits: Limits,
+
+    // Parser options
+    #[serde(default)]
+    pub iframe_srcdoc: bool,
+    #[serde(default)]
+    pub scripting_enabled: bool,
+    /// Used only for Document Fragment
+    /// Default: NoQuirks
+    #[serde(default)]
+    #[schemars(schema_with = "schema::document_mode")]
+    pub mode: Option<DocumentMode>,
+    /// Used only for Document Fragment
+    /// Default: `template` in HTML namespace
+    #[serde(default)]
+    pub context_element: Option<Element>,
+    /// Used only for Document Fragment
+    /// Default: None
+    #[serde(default)]
+    pub form_element: Option<Element>,
+
+    // Minification options
+    #[serde(default)]
+    pub force_set_html5_doctype: bool,
+    #[serde(default = "default_collapse_whitespaces")]
+    #[schemars(schema_with = "schema::collapse_whitespaces")]
+    pub collapse_whitespaces: CollapseWhitespaces,
+    // Remove safe empty elements with metadata content, i.e. the `script` and `style` element
+    // without content and attributes, `meta` and `link` elements without attributes and etc
+    #[serde(default = "true_by_default")]
+    pub remove_empty_metadata_elements: bool,
+    #[serde(default = "true_by_default")]
+    pub remove_comments: bool,
+    #[serde(default = "default_preserve_comments")]
+    #[schemars(schema_with = "schema::regexes")]
+    pub preserve_comments: Option<Vec<CachedRegex>>,
+    #[serde(default = "true_by_default")]
+    pub minify_conditional_comments: bool,
+    #[serde(default = "true_by_default")]
+    pub remove_empty_attributes: bool,
+    #[serde(default)]
+    #[schemars(schema_with = "schema::remove_redundant_attributes")]
+    pub remove_redundant_attributes: RemoveRedundantAttributes,
+    #[serde(default = "true_by_default")]
+    pub collapse_boolean_attributes: bool,
+    #[serde(default = "true_by_default")]
+    pub normalize_attributes: bool,
+    #[serde(default = "minify_json_by_default", deserialize_with = "minify_json")]
+    #[schemars(schema_with = "schema::minify_json")]
+    pub minify_json: MinifyJsonOption,
+    #[serde(default = "minify_js_by_default", deserialize_with = "minify_js")]
+    #[schemars(schema_with = "schema::minify_js")]
+    pub minify_js: MinifyJsOption,
+    #[serde(default = "minify_css_by_default", deserialize_with = "minify_css")]
+    #[schemars(schema_with = "schema::minify_css")]
+    pub minify_css: MinifyCssOption,
+    #[serde(default)]
+    #[schemars(schema_with = "schema::additional_minifiers")]
+    pub minify_additional_scripts_content: Option<Vec<(CachedRegex, MinifierType)>>,
+    #[serde(default)]
+    #[schemars(schema_with = "schema::additional_minifiers")]
+    pub minify_additional_attributes: Option<Vec<(CachedRegex, MinifierType)>>,
+    #[serde(default = "true_by_default")]
+    pub sort_space_separated_attribute_values: bool,
+    #[serde(default)]
+    pub sort_attributes: bool,
+    /// Merge consecutive metadata elements of the same kind, like `style`
+    /// elements, into one. Defaults to true.
+    #[serde(default = "true_by_default")]
+    pub merge_metadata_elements: bool,
+
+    // Codegen options
+    #[serde(default)]
+    pub tag_omission: Option<bool>,
+    #[serde(default)]
+    pub self_closing_void_elements: Option<bool>,
+    #[serde(default)]
+    pub quotes: Option<bool>,
+}
+
+impl Default for MinifyOptions {
+    /// The options of an empty object, so the defaults of the fields match
+    /// the deserialized ones.
+    fn default() -> Self {
+        parse_options("{}")
+            .expect("the options of an empty object")
+            .0
+    }
+}
+
+const fn true_by_default() -> bool {
+    true
+}
+
+fn minify_json<'de, D>(deserializer: D) -> Result<MinifyJsonOption, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    deserialize_untagged(
+        deserializer,
+        (MinifyJsonOption::Bool, MinifyJsonOption::Options),
+    )
+}
+
+fn minify_js<'de, D>(deserializer: D) -> Result<MinifyJsOption, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    deserialize_untagged(
+        deserializer,
+        (MinifyJsOption::Bool, MinifyJsOption::Options),
+    )
+}
+
+fn minify_css<'de, D>(deserializer: D) -> Result<MinifyCssOption, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    deserialize_untagged(
+        deserializer,
+        (MinifyCssOption::Bool, MinifyCssOption::Options),
+    )
+}
+
+const fn minify_json_by_default() -> MinifyJsonOption {
+    MinifyJsonOption::Bool(true)
+}
+
+const fn minify_js_by_default() -> MinifyJsOption {
+    MinifyJsOption::Bool(true)
+}
+
+const fn minify_css_by_default() -> MinifyCssOption {
+    MinifyCssOption::Bool(true)
+}
+
+fn default_preserve_comments() -> Option<Vec<CachedRegex>> {
+    Some(vec![
+        // License comments
+        CachedRegex::new("@preserve").unwrap(),
+        CachedRegex::new("@copyright").unwrap(),
+        CachedRegex::new("@lic").unwrap(),
+        CachedRegex::new("@cc_on").unwrap(),
+        // Allow to keep custom comments
+        CachedRegex::new("^!").unwrap(),
+        // Server-side comments
+        CachedRegex::new("^\\s*#").unwrap(),
+        // Conditional IE comments
+        CachedRegex::new("^\\[if\\s[^\\]+]").unwrap(),
+        CachedRegex::new("\\[endif]").unwrap(),
+    ])
+}
+
+const fn default_collapse_whitespaces() -> CollapseWhitespaces {
+    CollapseWhitespaces::OnlyMetadata
+}
+
+/// The parsed input of [minify] or [minify_fragment].
+pub enum DocumentOrDocumentFragment {
+    Document(Document),
+    DocumentFragment(DocumentFragment),
+}
+
+fn create_namespace(namespace: &str) -> anyhow::Result<Namespace> {
+    match &*namespace.to_lowercase() {
+        "http://www.w3.org/1999/xhtml" => Ok(Namespace::HTML),
+        "http://www.w3.org/1998/math/mathml" => Ok(Namespace::MATHML),
+        "http://www.w3.org/2000/svg" => Ok(Namespace::SVG),
+        "http://www.w3.org/1999/xlink" => Ok(Namespace::XLINK),
+        "http://www.w3.org/xml/1998/namespace" => Ok(Namespace::XML),
+        "http://www.w3.org/2000/xmlns/" => Ok(Namespace::XMLNS),
+        _ => {
+            bail!("failed to parse namespace of context element")
+        }
+    }
+}
+
+fn create_element(context_element: &Element) -> anyhow::Result<swc_html_ast::Element> {
+    let mut attributes = Vec::with_capacity(context_element.attributes.len());
+
+    for attribute in context_element.attributes.iter() {
+        let namespace = match &attribute.namespace {
+            Some(namespace) => Some(create_namespace(namespace)?),
+            _ => None,
+        };
+
+        attributes.push(swc_html_ast::Attribute {
+            span: DUMMY_SP,
+            namespace,
+            prefix: attribute.prefix.as_deref().map(|value| value.into()),
+            name: (&*attribute.name).into(),
+            raw_name: None,
+            value: attribute.value.as_deref().map(|value| value.into()),
+            raw_value: None,
+        })
+    }
+
+    Ok(swc_html_ast::Element {
+        span: DUMMY_SP,
+        tag_name: (&*context_element.tag_name).into(),
+        namespace: create_namespace(&context_element.namespace)?,
+        attributes,
+        children: vec![],
+        content: None,
+        is_self_closing: context_element.is_self_closing,
+    })
+}
+
+/// Applies `strict` and `maxErrors` to the recoverable errors of the parser.
+fn check_recoverable_errors(
+    handler: &Handler,
+    errors: &mut Vec<swc_html::parser::error::Error>,
+    strict: bool,
+    max_errors: Option<usize>,
+) -> anyhow::Result<()> {
+    // `strict` fails on any error, even if none is reported.
+    let fails = strict && !errors.is_empty();
+
+    if let Some(max_errors) = max_errors {
+        errors.truncate(max_errors);
+    }
+
+    if fails {
+        for err in errors.drain(..) {
+            err.to_diagnostics(handler).emit();
+        }
+
+        bail!("found recoverable errors in strict mode")
+    }
+
+    Ok(())
+}
+
+/// Used for `recover: true`, when the parser failed. The output is the input
+/// as-is, as there is no tree to print.
+fn recovered_output(
+    cm: &Lrc<SourceMap>,
+    handler: &Handler,
+    code: &str,
+    err: swc_html::parser::error::Error,
+    mut errors: Vec<swc_html::parser::error::Error>,
+    max_errors: Option<usize>,
+    code_frame: bool,
+) -> TransformOutput {
+    if let Some(max_errors) = max_errors {
+        errors.truncate(max_errors);
+    }
+
+    let mut diagnostics = vec![];
+
+    for (err, fatal) in once((err, true)).chain(errors.into_iter().map(|err| (err, false))) {
+        let mut buf = vec![];
+
+        err.to_diagnostics(handler).buffer(&mut buf);
+
+        for i in buf {
+            let mut d = to_diagnostic(cm, &i, code_frame);
+            d.fatal = fatal;
+            diagnostics.push(d);
+        }
+    }
+
+    TransformOutput {
+        code: code.into(),
+        errors: Some(diagnostics),
+        warnings: None,
+        stats: None,
+    }
+}
+
+/// Minifies a document.
+pub fn minify(
+    code: &[u8],
+    filename: Option<&str>,
+    opts: &MinifyOptions,
+) -> anyhow::Result<TransformOutput> {
+    minify_inner(code, filename, opts, false)
+}
+
+/// Minifies a document fragment, parsed in `opts.context_element`.
+pub fn minify_fragment(
+    code: &[u8],
+    filename: Option<&str>,
+    opts: &MinifyOptions,
+) -> anyhow::Result<TransformOutput> {
+    minify_inner(code, filename, opts, true)
+}
+
+#[instrument(skip_all)]
+fn minify_inner(
+    code: &[u8],
+    filename: Option<&str>,
+    opts: &MinifyOptions,
+    is_fragment: bool,
+) -> anyhow::Result<TransformOutput> {
+    let limiter = Limiter::new(&opts.limits, code.len())?;
+    let mut stats = StatsRecorder::new(opts.stats, code.len());
+
+    let input = decode(code, opts.encoding.as_deref(), !is_fragment)?;
+    let code = &*input.code;
+
+    let output = swc_common::GLOBALS.set(&swc_common::Globals::new(), || {
+        try_with(|cm, handler| {
+            let filename = match filename.or(opts.filename.as_deref()) {
+                Some(v) => FileName::Real(v.into()),
+                None => FileName::Anon,
+            };
+
+            let fm = cm.new_source_file(filename, code.into());
+
+            let scripting_enabled = opts.scripting_enabled;
+            let mut errors = vec![];
+
+            let (mut document_or_document_fragment, context_element) = if is_fragment {
+                let context_element = match &opts.context_element {
+                    Some(context_element) => create_element(context_element)?,
+                    _ => swc_html_ast::Element {
+                        span: DUMMY_SP,
+                        tag_name: js_word!("template"),
+                        namespace: Namespace::HTML,
+                        attributes: vec![],
+                        children: vec![],
+                        content: None,
+                        is_self_closing: false,
+                    },
+                };
+                let mode = match opts.mode {
+                    Some(mode) => mode,
+                    _ => DocumentMode::NoQuirks,
+                };
+                let form_element = match &opts.form_element {
+                    Some(form_element) => Some(create_element(form_element)?),
+                    _ => None,
+                };
+                let document_fragment = info_span!("parse").in_scope(|| {
+                    let mut parser = Parser::new(
+                        limiter.checked(Lexer::new(StringInput::from(&*fm))),
+                        ParserConfig {
+                            scripting_enabled,
+                            iframe_srcdoc: opts.iframe_srcdoc,
+                            ..Default::default()
+                        },
+                    );
+                    let result =
+                        parser.parse_document_fragment(context_element.clone(), mode, form_element);
+
+                    errors.extend(parser.take_errors());
+
+                    result
+                });
+                limiter.checkpoint()?;
+
+                let document_fragment = match document_fragment {
+                    Ok(v) => v,
+                    Err(err) if opts.recover => {
+                        return Ok(recovered_output(
+                            cm,
+                            handler,
+                            code,
+                            err,
+                            errors,
+                            opts.max_errors,
+                            opts.code_frame,
+                        ));
+                    }
+                    Err(err) => {
+                        err.to_diagnostics(handler).emit();
+
+                        for err in errors {
+                            err.to_diagnostics(handler).emit();
+                        }
+
+                        bail!("failed to parse input as document fragment")
+                    }
+                };
+
+                (
+                    DocumentOrDocumentFragment::DocumentFragment(document_fragment),
+                    Some(context_element),
+                )
+            } else {
+                let document = info_span!("parse").in_scope(|| {
+                    let mut parser = Parser::new(
+                        limiter.checked(Lexer::new(StringInput::from(&*fm))),
+                        ParserConfig {
+                            scripting_enabled,
+                            iframe_srcdoc: opts.iframe_srcdoc,
+                            ..Default::default()
+                        },
+                    );
+                    let result = parser.parse_document();
+
+                    errors.extend(parser.take_errors());
+
+                    result
+                });
+                limiter.checkpoint()?;
+
+                let document = match document {
+                    Ok(v) => v,
+                    Err(err) if opts.recover => {
+                        return Ok(recovered_output(
+                            cm,
+                            handler,
+                            code,
+                            err,
+                            errors,
+                            opts.max_errors,
+                            opts.code_frame,
+                        ));
+                    }
+                    Err(err) => {
+                        err.to_diagnostics(handler).emit();
+
+                        for err in errors {
+                            err.to_diagnostics(handler).emit();
+                        }
+
+                        bail!("failed to parse input as document")
+                    }
+                };
+
+                (DocumentOrDocumentFragment::Document(document), None)
+            };
+
+            stats.lap(Phase::Parse);
+            limiter.check_tree(&document_or_document_fragment)?;
+            stats.count_before(&document_or_document_fragment);
+
+            check_recoverable_errors(handler, &mut errors, opts.strict, opts.max_errors)?;
+
+            let mut returned_errors = None;
+
+            if !errors.is_empty() {
+                returned_errors = Some(Vec::with_capacity(errors.len()));
+
+                for err in errors {
+                    let mut buf = vec![];
+
+                    err.to_diagnostics(handler).buffer(&mut buf);
+
+                    for i in buf {
+                        returned_errors.as_mut().unwrap().push(to_diagnostic(
+                            cm,
+                            &i,
+                            opts.code_frame,
+                        ));
+                    }
+                }
+            }
+
+            let options = swc_html_minifier::option::MinifyOptions {
+                force_set_html5_doctype: opts.force_set_html5_doctype,
+                collapse_whitespaces: opts.collapse_whitespaces.clone(),
+                remove_empty_metadata_elements: opts.remove_empty_metadata_elements,
+                remove_comments: opts.remove_comments,
+                preserve_comments: opts.preserve_comments.clone(),
+                minify_conditional_comments: opts.minify_conditional_comments,
+                remove_empty_attributes: opts.remove_empty_attributes,
+                remove_redundant_attributes: opts.remove_redundant_attributes.clone(),
+                collapse_boolean_attributes: opts.collapse_boolean_attributes,
+                normalize_attributes: opts.normalize_attributes,
+                minify_json: opts.minify_json.clone(),
+                minify_js: opts.minify_js.clone(),
+                minify_css: opts.minify_css.clone(),
+                minify_additional_scripts_content: opts.minify_additional_scripts_content.clone(),
+                minify_additional_attributes: opts.minify_additional_attributes.clone(),
+                sort_space_separated_attribute_values: opts.sort_space_separated_attribute_values,
+                sort_attributes: opts.sort_attributes,
+                merge_metadata_elements: opts.merge_metadata_elements,
+            };
+
+            limiter.checkpoint()?;
+
+            info_span!("minify").in_scope(|| match document_or_document_fragment {
+                DocumentOrDocumentFragment::Document(ref mut document) => {
+                    minify_document(document, &options);
+                }
+                DocumentOrDocumentFragment::DocumentFragment(ref mut document_fragment) => {
+                    minify_document_fragment(
+                        document_fragment,
+                        context_element.as_ref().unwrap(),
+                        &options,
+                    );
+                }
+            });
+
+            stats.lap(Phase::Minify);
+            stats.count_after(&document_or_document_fragment);
+            limiter.checkpoint()?;
+
+            let _span = info_span!("codegen").entered();
+
+            let code = {
+                let mut buf = String::new();
+
+                {
+                    let mut wr = BasicHtmlWriter::new(
+                        &mut buf,
+                        None,
+                        BasicHtmlWriterConfig {
+                            ..Default::default()
+                        },
+                    );
+                    let mut gen = CodeGenerator::new(
+                        &mut wr,
+                        CodegenConfig {
+                            minify: true,
+                            scripting_enabled,
+                            context_element: context_element.as_ref(),
+                            tag_omission: opts.tag_omission,
+                            self_closing_void_elements: opts.self_closing_void_elements,
+                            quotes: opts.quotes,
+                        },
+                    );
+
+                    match document_or_document_fragment {
+                        DocumentOrDocumentFragment::Document(document) => {
+                            gen.emit(&document).context("failed to emit")?;
+                        }
+                        DocumentOrDocumentFragment::DocumentFragment(document_fragment) => {
+                            gen.emit(&document_fragment).context("failed to emit")?;
+                        }
+                    }
+                }
+
+                buf
+            };
+
+            stats.lap(Phase::Codegen);
+
+            Ok(TransformOutput {
+                code,
+                errors: returned_errors,
+                warnings: None,
+                stats: stats.finish(),
+            })
+        })
+    })?;
+
+    output.encoded(&input, opts.output_encoding.as_deref(), opts.ascii_only)
+}
@@ -0,0 +1,311 @@
+//! `limits` of the options, for untrusted input.
+//!
+//! An exceeded limit fails with a [LimitExceeded] error, whose [Limit] is
+//! kept until it's taken with [take_exceeded], like the reports of
+//! [panic](crate::panic).
+
+use std::cell::Cell;
+
+use extra_common::limits::exceeded;
+pub use extra_common::limits::{take_exceeded, Limit, LimitExceeded};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use swc_atoms::JsWord;
+use swc_common::BytePos;
+use swc_html::{
+    ast::{Child, Element, Token, TokenAndSpan},
+    parser::{error::Error, lexer::State, parser::input::ParserInput},
+    visit::{Visit, VisitWith},
+};
+
+use crate::{
+    abort::{self, Ticker},
+    DocumentOrDocumentFragment,
+};
+
+#[derive(Debug, Default, Clone, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[schemars(deny_unknown_fields)]
+pub struct Limits {
+    /// The maximum size of the input, in bytes.
+    #[serde(default)]
+    pub max_input_bytes: Option<usize>,
+
+    /// The maximum nesting of elements, as the minifier and the printer
+    /// recurse into them.
+    ///
+    /// It's checked on the tags while parsing, before building a deep tree,
+    /// and again on the parsed tree. While parsing, an element whose end tag
+    /// is omitted counts until the end tag of its parent, unless it's a void
+    /// element or one closed by its next sibling like `<li>` or `<p>`.
+    #[serde(default)]
+    pub max_depth: Option<usize>,
+
+    /// The maximum number of elements, text nodes and comments of the parsed
+    /// tree.
+    #[serde(default)]
+    pub max_nodes: Option<usize>,
+
+    /// Milliseconds after which processing fails.
+    ///
+    /// It's checked while parsing and walking the tree, but the minifier of
+    /// swc runs to its end once started, so a call can take longer.
+    /// `maxInputBytes`, `maxDepth` and `maxNodes` bound its work.
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+}
+
+/// How many nodes are counted between [checkpoint](Limiter::checkpoint)s.
+const NODES_PER_CHECK: usize = 1024;
+
+/// Elements which have no end tag.
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "keygen", "link", "meta", "param",
+    "source", "track", "wbr",
+];
+
+/// Elements whose end tag may be omitted before the start tag of a sibling
+/// with the same name.
+const SIBLING_CLOSED_ELEMENTS: &[&str] = &[
+    "dd", "dt", "li", "optgroup", "option", "p", "rb", "rp", "rt", "rtc", "tbody", "td", "tfoot",
+    "th", "thead", "tr",
+];
+
+/// Checks the [Limits] of a call.
+pub struct Limiter<'a> {
+    limits: &'a Limits,
+    _deadline: abort::Deadline,
+    /// Set if the tags nested deeper than `maxDepth` while parsing.
+    too_deep: Cell<bool>,
+}
+
+impl<'a> Limiter<'a> {
+    /// Starts the clock of `timeoutMs`, and checks the size of the input and
+    /// whether the call was aborted before it started.
+    pub fn new(limits: &'a Limits, input_size: usize) -> anyhow::Result<Self> {
+        abort::check()?;
+
+        if let Some(max) = limits.max_input_bytes {
+            if input_size > max {
+                return Err(exceeded(Limit::InputBytes, max as u64));
+            }
+        }
+
+        Ok(Limiter {
+            limits,
+            _deadline: abort::deadline(limits.timeout_ms),
+            too_deep: Cell::new(false),
+        })
+    }
+
+    /// Checks `timeoutMs`, whether the call was [aborted](crate::abort), and
+    /// whether the tags read by a [Checked] lexer nested deeper than
+    /// `maxDepth`.
+    pub fn checkpoint(&self) -> anyhow::Result<()> {
+        abort::check()?;
+
+        match self.limits.max_depth {
+            Some(max) if self.too_deep.get() => Err(exceeded(Limit::Depth, max as u64)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Wraps `input` to check the limits while parsing.
+    pub fn checked<I>(&self, input: I) -> Checked<'_, I> {
+        Checked {
+            input,
+            limiter: self,
+            ticker: Ticker::default(),
+            open: vec![],
+        }
+    }
+
+    /// Checks `maxDepth` and `maxNodes` on the parsed tree, and
+    /// [checkpoint]s periodically while walking it.
+    ///
+    /// [checkpoint]: Limiter::checkpoint
+    pub fn check_tree(&self, n: &DocumentOrDocumentFragment) -> anyhow::Result<()> {
+        self.checkpoint()?;
+
+        if self.limits.max_depth.is_none() && self.limits.max_nodes.is_none() && !abort::is_scoped()
+        {
+            return Ok(());
+        }
+
+        let mut v = NodeCounter {
+            limiter: self,
+            nodes: 0,
+            depth: 0,
+            result: Ok(()),
+        };
+        match n {
+            DocumentOrDocumentFragment::Document(n) => n.visit_with(&mut v),
+            DocumentOrDocumentFragment::DocumentFragment(n) => n.visit_with(&mut v),
+        }
+
+        v.result
+    }
+}
+
+struct NodeCounter<'a, 'b> {
+    limiter: &'a Limiter<'b>,
+    nodes: usize,
+    depth: usize,
+    result: anyhow::Result<()>,
+}
+
+impl NodeCounter<'_, '_> {
+    /// Returns false to stop the visit.
+    fn count(&mut self) -> bool {
+        if self.result.is_err() {
+            return false;
+        }
+
+        self.nodes += 1;
+
+        if let Some(max) = self.limiter.limits.max_nodes {
+            if self.nodes > max {
+                self.result = Err(exceeded(Limit::Nodes, max as u64));
+                return false;
+            }
+        }
+
+        if self.nodes % NODES_PER_CHECK == 0 {
+            self.result = self.limiter.checkpoint();
+        }
+
+        self.result.is_ok()
+    }
+}
+
+impl Visit for NodeCounter<'_, '_> {
+    fn visit_child(&mut self, n: &Child) {
+        if self.count() {
+            n.visit_children_with(self);
+        }
+    }
+
+    fn visit_element(&mut self, n: &Element) {
+        self.depth += 1;
+
+        match self.limiter.limits.max_depth {
+            Some(max) if self.depth > max => {
+                if self.result.is_ok() {
+                    self.result = Err(exceeded(Limit::Depth, max as u64));
+                }
+            }
+            _ => n.visit_children_with(self),
+        }
+
+        self.depth -= 1;
+    }
+}
+
+/// The tokens of a lexer, which end early once the call must stop or the
+/// tags nest deeper than `maxDepth`, so the limits are checked while parsing.
+///
+/// The tree parsed from truncated tokens must be discarded, by
+/// [checking](Limiter::checkpoint) again after parsing.
+pub struct Checked<'a, I> {
+    input: I,
+    limiter: &'a Limiter<'a>,
+    ticker: Ticker,
+    /// The names of the elements which are open, as far as the tags tell.
+    open: Vec<JsWord>,
+}
+
+impl<I> Checked<'_, I> {
+    /// Tracks the nesting of `token`, and returns false if it's deeper than
+    /// `maxDepth`.
+    fn enter(&mut self, token: &Token) -> bool {
+        let max = match self.limiter.limits.max_depth {
+            Some(max) => max,
+            None => return true,
+        };
+
+        match token {
+            Token::StartTag {
+                tag_name,
+                is_self_closing,
+                ..
+            } => {
+                if VOID_ELEMENTS.contains(&&**tag_name) {
+                    return true;
+                }
+
+                // `/>` only closes the elements of svg and MathML.
+                if *is_self_closing && self.is_in_foreign_content() {
+                    return true;
+                }
+
+                if SIBLING_CLOSED_ELEMENTS.contains(&&**tag_name)
+                    && self.open.last() == Some(tag_name)
+                {
+                    return true;
+                }
+
+                self.open.push(tag_name.clone());
+
+                self.open.len() <= max
+            }
+            Token::EndTag { tag_name, .. } => {
+                if let Some(i) = self.open.iter().rposition(|name| name == tag_name) {
+                    self.open.truncate(i);
+                }
+
+                true
+            }
+            _ => true,
+        }
+    }
+
+    fn is_in_foreign_content(&self) -> bool {
+        self.open.iter().any(|name| name == "svg" || name == "math")
+    }
+}
+
+impl<I: ParserInput> Iterator for Checked<'_, I> {
+    type Item = TokenAndSpan;
+
+    fn next(&mut self) -> Option<TokenAndSpan> {
+        if !self.ticker.tick() || self.limiter.too_deep.get() {
+            return None;
+        }
+
+        let token = self.input.next()?;
+
+        if !self.enter(&token.token) {
+            self.limiter.too_deep.set(true);
+            return None;
+        }
+
+        Some(token)
+    }
+}
+
+impl<I: ParserInput> ParserInput for Checked<'_, I> {
+    fn start_pos(&mut self) -> BytePos {
+        self.input.start_pos()
+    }
+
+    fn last_pos(&mut self) -> BytePos {
+        self.input.last_pos()
+    }
+
+    fn take_errors(&mut self) -> Vec<Error> {
+        self.input.take_errors()
+    }
+
+    fn set_last_start_tag_name(&mut self, tag_name: &JsWord) {
+        self.input.set_last_start_tag_name(tag_name)
+    }
+
+    fn set_input_state(&mut self, state: State) {
+        self.input.set_input_state(state)
+    }
+
+    fn set_adjusted_current_node_to_html_namespace(&mut self, value: bool) {
+        self.input
+            .set_adjusted_current_node_to_html_namespace(value)
+    }
+}
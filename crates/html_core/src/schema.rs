@@ -0,0 +1,139 @@
+//! JSON Schema of the options, for the autocompletion of editors and to check
+//! the types of `index.ts`.
+
+use extra_common::schema::{from_json, root_schema, schema_of_default};
+use schemars::{gen::SchemaGenerator, schema::Schema};
+use serde::Serialize;
+use serde_json::{json, Value};
+use swc_html_minifier::option::{CssOptions, JsOptions};
+
+use crate::MinifyOptions;
+
+/// Returns the schemas of the options, keyed by the function using them.
+pub fn options_schema() -> anyhow::Result<Value> {
+    let fragment = root_schema::<MinifyOptions>()?;
+
+    // Only used for fragments.
+    let mut minify = fragment.clone();
+    if let Some(properties) = minify
+        .pointer_mut("/properties")
+        .and_then(Value::as_object_mut)
+    {
+        for key in ["mode", "contextElement", "formElement"] {
+            properties.remove(key);
+        }
+    }
+
+    Ok(json!({
+        "minify": minify,
+        "minifyFragment": fragment,
+    }))
+}
+
+/// [swc_html::ast::DocumentMode]
+pub fn document_mode(_: &mut SchemaGenerator) -> Schema {
+    from_json(json!({
+        "enum": ["no-quirks", "limited-quirks", "quirks"],
+    }))
+}
+
+/// [swc_html_minifier::option::CollapseWhitespaces]
+pub fn collapse_whitespaces(_: &mut SchemaGenerator) -> Schema {
+    from_json(json!({
+        "enum": [
+            "none",
+            "all",
+            "smart",
+            "conservative",
+            "advanced-conservative",
+            "only-metadata",
+        ],
+    }))
+}
+
+/// [swc_html_minifier::option::RemoveRedundantAttributes]
+pub fn remove_redundant_attributes(_: &mut SchemaGenerator) -> Schema {
+    from_json(json!({
+        "enum": ["none", "all", "smart"],
+    }))
+}
+
+/// [swc_html_minifier::option::MinifyJsonOption]
+pub fn minify_json(_: &mut SchemaGenerator) -> Schema {
+    from_json(json!({
+        "anyOf": [
+            { "type": "boolean" },
+            {
+                "type": "object",
+                "properties": {
+                    "pretty": { "type": "boolean" },
+                },
+                "required": ["pretty"],
+                "additionalProperties": false,
+            },
+        ],
+    }))
+}
+
+/// [swc_html_minifier::option::MinifyJsOption]
+///
+/// The options of the parser, the minifier and the code generator are the
+/// ones of `@swc/core`, whose keys are taken from their serialized defaults.
+pub fn minify_js(_: &mut SchemaGenerator) -> Schema {
+    bool_or_options(&JsOptions {
+        parser: Default::default(),
+        minifier: Default::default(),
+        codegen: Default::default(),
+    })
+}
+
+/// [swc_html_minifier::option::MinifyCssOption]
+///
+/// The options of the parser, the minifier and the code generator are the
+/// ones of `@swc/css`, whose keys are taken from their serialized defaults.
+pub fn minify_css(_: &mut SchemaGenerator) -> Schema {
+    bool_or_options(&CssOptions {
+        parser: Default::default(),
+        minifier: Default::default(),
+        codegen: Default::default(),
+    })
+}
+
+fn bool_or_options(default: &impl Serialize) -> Schema {
+    let default = serde_json::to_value(default).expect("failed to serialize the default options");
+    let mut options = schema_of_default(&default);
+    // Only `parser`, `minifier` and `codegen` are accepted.
+    options["additionalProperties"] = false.into();
+
+    from_json(json!({
+        "anyOf": [
+            { "type": "boolean" },
+            options,
+        ],
+    }))
+}
+
+/// [swc_cached::regex::CachedRegex]
+pub fn regexes(_: &mut SchemaGenerator) -> Schema {
+    from_json(json!({
+        "type": "array",
+        "items": { "type": "string", "format": "regex" },
+    }))
+}
+
+/// Pairs of [swc_cached::regex::CachedRegex] and
+/// [swc_html_minifier::option::MinifierType].
+pub fn additional_minifiers(_: &mut SchemaGenerator) -> Schema {
+    from_json(json!({
+        "type": "array",
+        "items": {
+            "type": "array",
+            "items": [
+                { "type": "string", "format": "regex" },
+                { "enum": ["js-module", "js-script", "json", "css", "html"] },
+            ],
+            "minItems": 2,
+            "maxItems": 2,
+        },
+    }))
+}
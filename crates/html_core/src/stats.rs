@@ -0,0 +1,168 @@
+//! `stats` of the output, if requested with the `stats` option.
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+use swc_html::{
+    ast::{Attribute, Comment, Element, Text},
+    visit::{Visit, VisitWith},
+};
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
+use crate::DocumentOrDocumentFragment;
+
+#[cfg_attr(
+    all(feature = "napi", not(feature = "namespace")),
+    napi_derive::napi(object)
+)]
+#[cfg_attr(
+    all(feature = "napi", feature = "namespace"),
+    napi_derive::napi(object, namespace = "html")
+)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Stats {
+    /// Bytes of the input.
+    pub input_size: u32,
+    /// Bytes of `code`.
+    pub output_size: u32,
+    /// Milliseconds spent in each phase.
+    pub durations: Durations,
+    /// Counted after parsing.
+    pub before: Counts,
+    /// Counted after minifying, so the difference with `before` is what was
+    /// removed or merged.
+    pub after: Counts,
+}
+
+#[cfg_attr(
+    all(feature = "napi", not(feature = "namespace")),
+    napi_derive::napi(object)
+)]
+#[cfg_attr(
+    all(feature = "napi", feature = "namespace"),
+    napi_derive::napi(object, namespace = "html")
+)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Durations {
+    pub parse: f64,
+    /// Includes minifying the CSS and JS of the document.
+    pub minify: f64,
+    pub codegen: f64,
+}
+
+#[cfg_attr(
+    all(feature = "napi", not(feature = "namespace")),
+    napi_derive::napi(object)
+)]
+#[cfg_attr(
+    all(feature = "napi", feature = "namespace"),
+    napi_derive::napi(object, namespace = "html")
+)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Counts {
+    pub elements: u32,
+    pub attributes: u32,
+    pub text_nodes: u32,
+    pub comments: u32,
+}
+
+#[derive(Clone, Copy)]
+pub enum Phase {
+    Parse,
+    Minify,
+    Codegen,
+}
+
+/// Measures the phases, as laps of a stopwatch.
+pub struct StatsRecorder {
+    stats: Option<Stats>,
+    lap: Instant,
+}
+
+impl StatsRecorder {
+    /// Starts the first lap. Nothing is recorded unless `enabled`.
+    pub fn new(enabled: bool, input_size: usize) -> Self {
+        StatsRecorder {
+            stats: enabled.then(|| Stats {
+                input_size: input_size as u32,
+                ..Default::default()
+            }),
+            lap: Instant::now(),
+        }
+    }
+
+    /// Adds the time since the previous lap to `phase`.
+    pub fn lap(&mut self, phase: Phase) {
+        let now = Instant::now();
+
+        if let Some(stats) = &mut self.stats {
+            let d = &mut stats.durations;
+            let duration = match phase {
+                Phase::Parse => &mut d.parse,
+                Phase::Minify => &mut d.minify,
+                Phase::Codegen => &mut d.codegen,
+            };
+
+            *duration += (now - self.lap).as_secs_f64() * 1000.0;
+        }
+
+        self.lap = now;
+    }
+
+    pub fn count_before(&mut self, n: &DocumentOrDocumentFragment) {
+        if let Some(stats) = &mut self.stats {
+            stats.before = Counts::of(n);
+        }
+
+        // Counting is not part of the next phase.
+        self.lap = Instant::now();
+    }
+
+    pub fn count_after(&mut self, n: &DocumentOrDocumentFragment) {
+        if let Some(stats) = &mut self.stats {
+            stats.after = Counts::of(n);
+        }
+
+        self.lap = Instant::now();
+    }
+
+    /// `output_size` is set with the final code.
+    pub fn finish(self) -> Option<Stats> {
+        self.stats
+    }
+}
+
+impl Counts {
+    fn of(n: &DocumentOrDocumentFragment) -> Self {
+        let mut v = Counts::default();
+        match n {
+            DocumentOrDocumentFragment::Document(n) => n.visit_with(&mut v),
+            DocumentOrDocumentFragment::DocumentFragment(n) => n.visit_with(&mut v),
+        }
+        v
+    }
+}
+
+impl Visit for Counts {
+    fn visit_element(&mut self, n: &Element) {
+        self.elements += 1;
+        n.visit_children_with(self);
+    }
+
+    fn visit_attribute(&mut self, _: &Attribute) {
+        self.attributes += 1;
+    }
+
+    fn visit_text(&mut self, _: &Text) {
+        self.text_nodes += 1;
+    }
+
+    fn visit_comment(&mut self, _: &Comment) {
+        self.comments += 1;
+    }
+}
@@ -0,0 +1,280 @@
+use std::path::Path;
+
+use html_core::{
+    abort::{self, AbortFlag, Aborted},
+    limits::{take_exceeded, Limit, Limits},
+    parse_options, Attribute, Element, MinifyOptions,
+};
+
+#[test]
+fn minify_document() {
+    let output = html_core::minify(
+        b"<!doctype html><html><head><title>a</title></head><body>  <p>b</p>  </body></html>",
+        None,
+        &Default::default(),
+    )
+    .unwrap();
+
+    assert!(output.code.starts_with("<!doctype html>"));
+    assert!(output.code.contains("<title>a</title>"));
+    assert!(!output.code.contains("  "));
+}
+
+#[test]
+fn minify_removes_comments() {
+    let output = html_core::minify(
+        b"<!doctype html><p>a<!-- b --></p>",
+        None,
+        &Default::default(),
+    )
+    .unwrap();
+
+    assert!(!output.code.contains("<!--"));
+}
+
+#[test]
+fn minify_keeps_comments_if_disabled() {
+    let opts = MinifyOptions {
+        remove_comments: false,
+        ..Default::default()
+    };
+    let output = html_core::minify(b"<!doctype html><p>a<!-- b --></p>", None, &opts).unwrap();
+
+    assert!(output.code.contains("<!-- b -->"));
+}
+
+#[test]
+fn minify_fragment_in_template() {
+    let output = html_core::minify_fragment(b"<td>a</td>", None, &Default::default()).unwrap();
+
+    assert!(output.code.contains("<td>a"));
+    assert!(!output.code.contains("<html"));
+}
+
+#[test]
+fn minify_fragment_with_context_element() {
+    let opts = MinifyOptions {
+        context_element: Some(Element {
+            tag_name: "svg".into(),
+            namespace: "http://www.w3.org/2000/svg".into(),
+            attributes: vec![Attribute {
+                namespace: None,
+                prefix: None,
+                name: "viewBox".into(),
+                value: Some("0 0 10 10".into()),
+            }],
+            is_self_closing: false,
+        }),
+        ..Default::default()
+    };
+    let output = html_core::minify_fragment(b"<circle r=\"5\"></circle>", None, &opts).unwrap();
+
+    assert!(output.code.contains("<circle"));
+}
+
+#[test]
+fn minify_fragment_rejects_unknown_namespace() {
+    let opts = MinifyOptions {
+        context_element: Some(Element {
+            tag_name: "div".into(),
+            namespace: "urn:unknown".into(),
+            attributes: vec![],
+            is_self_closing: false,
+        }),
+        ..Default::default()
+    };
+
+    assert!(html_core::minify_fragment(b"a", None, &opts).is_err());
+}
+
+#[test]
+fn minify_fails_on_recoverable_errors_in_strict_mode() {
+    let opts = MinifyOptions {
+        strict: true,
+        ..Default::default()
+    };
+
+    assert!(html_core::minify(b"<p>a</b>", None, &opts).is_err());
+}
+
+#[test]
+fn strict_mode_fails_even_if_no_error_is_reported() {
+    let opts = MinifyOptions {
+        strict: true,
+        max_errors: Some(0),
+        ..Default::default()
+    };
+
+    assert!(html_core::minify(b"<p>a</b>", None, &opts).is_err());
+}
+
+#[test]
+fn parse_options_reports_unknown_keys() {
+    let (opts, warnings): (MinifyOptions, _) =
+        parse_options(r#"{ "unknownOptions": "warn", "removeComments": false, "foo": 1 }"#)
+            .unwrap();
+
+    assert!(!opts.remove_comments);
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].contains("`foo`"));
+}
+
+#[test]
+fn parse_options_reports_unknown_keys_in_inline_options() {
+    let err = |json| {
+        parse_options::<MinifyOptions>(json)
+            .map(|_| ())
+            .unwrap_err()
+            .to_string()
+    };
+
+    assert_eq!(
+        err(r#"{"minifyCss":{"parser":{"foo":1}}}"#),
+        "unknown option `minifyCss.parser.foo`"
+    );
+    assert_eq!(
+        err(r#"{"minifyCss":{"parserr":{}}}"#),
+        "unknown option `minifyCss.parserr`, did you mean `parser`?"
+    );
+    assert_eq!(
+        err(r#"{"minifyJs":{"minifier":{"compresss":true}}}"#),
+        "unknown option `minifyJs.minifier.compresss`, did you mean `compress`?"
+    );
+    assert!(
+        err(r#"{"minifyJson":{"pretty":1}}"#).starts_with("invalid option `minifyJson.pretty`:")
+    );
+    parse_options::<MinifyOptions>(
+        r#"{"minifyCss":{"parser":{}},"minifyJs":{"minifier":{}},"minifyJson":false}"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn stats_count_elements() {
+    let opts = MinifyOptions {
+        stats: true,
+        ..Default::default()
+    };
+    let stats = html_core::minify_fragment(b"<p class=\"a\">b</p>", None, &opts)
+        .unwrap()
+        .stats
+        .unwrap();
+
+    assert_eq!(stats.before.elements, 1);
+    assert_eq!(stats.before.attributes, 1);
+}
+
+#[test]
+fn limits_fail_on_large_input() {
+    let opts = MinifyOptions {
+        limits: Limits {
+            max_input_bytes: Some(8),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let err = html_core::minify_fragment(b"<p>abcdefgh</p>", None, &opts).unwrap_err();
+
+    assert!(err.to_string().contains("limits.maxInputBytes"));
+    assert_eq!(take_exceeded(), Some(Limit::InputBytes));
+    assert_eq!(Limit::InputBytes.code(), "SWC_LIMIT_INPUT_BYTES");
+    assert_eq!(take_exceeded(), None);
+}
+
+#[test]
+fn limits_fail_on_deep_input() {
+    let opts = MinifyOptions {
+        limits: Limits {
+            max_depth: Some(2),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert!(html_core::minify_fragment(b"<div><p>a</p></div>", None, &opts).is_ok());
+    assert!(html_core::minify_fragment(b"<div><div><p>a</p></div></div>", None, &opts).is_err());
+    assert_eq!(take_exceeded(), Some(Limit::Depth));
+    assert_eq!(Limit::Depth.code(), "SWC_LIMIT_DEPTH");
+}
+
+#[test]
+fn limits_fail_on_deep_input_while_parsing() {
+    let opts = MinifyOptions {
+        limits: Limits {
+            max_depth: Some(64),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    // Building this tree would take minutes.
+    let code = "<div>".repeat(1_000_000);
+    assert!(html_core::minify_fragment(code.as_bytes(), None, &opts).is_err());
+    assert_eq!(take_exceeded(), Some(Limit::Depth));
+
+    // Elements whose end tag is omitted before their next sibling.
+    let code = format!("<ul>{}</ul><p>a<p>b", "<li>a".repeat(100));
+    assert!(html_core::minify_fragment(code.as_bytes(), None, &opts).is_ok());
+    let code = format!("<svg>{}</svg>", "<path/>".repeat(100));
+    assert!(html_core::minify_fragment(code.as_bytes(), None, &opts).is_ok());
+}
+
+#[test]
+fn limits_fail_on_many_nodes() {
+    let opts = MinifyOptions {
+        limits: Limits {
+            max_nodes: Some(4),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    assert!(html_core::minify_fragment(b"<p>a</p>", None, &opts).is_ok());
+    assert!(html_core::minify_fragment(b"<p>a</p><p>b</p><p>c</p>", None, &opts).is_err());
+    assert_eq!(take_exceeded(), Some(Limit::Nodes));
+    assert_eq!(Limit::Nodes.code(), "SWC_LIMIT_NODES");
+}
+
+#[test]
+fn limits_fail_after_timeout() {
+    let opts = MinifyOptions {
+        limits: Limits {
+            timeout_ms: Some(0),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let err = html_core::minify_fragment(b"<p>a</p>", None, &opts).unwrap_err();
+
+    assert!(err.to_string().contains("limits.timeoutMs"));
+    assert_eq!(take_exceeded().map(Limit::code), Some("SWC_LIMIT_TIMEOUT"));
+}
+
+#[test]
+fn aborted_calls_fail() {
+    let flag = AbortFlag::default();
+    let minify = || {
+        abort::scope(Some(&flag), || {
+            html_core::minify_fragment(b"<p>a</p>", None, &Default::default())
+        })
+    };
+
+    assert!(minify().is_ok());
+
+    flag.abort();
+    assert!(minify().unwrap_err().is::<Aborted>());
+}
+
+#[test]
+fn options_ts_is_generated_from_the_schema() {
+    let schema = html_core::schema::options_schema().unwrap();
+    let generated = extra_common::schema::typescript_types(
+        &schema,
+        &[("minify", "Options"), ("minifyFragment", "FragmentOptions")],
+    );
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../packages/html/options.ts");
+
+    if let Err(err) = extra_common::schema::check_generated(&path, &generated) {
+        panic!("{}", err);
+    }
+}
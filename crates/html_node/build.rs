@@ -1,6 +1,6 @@
 use std::{
     env,
-    fs::File,
+    fs::{self, File},
     io::{BufWriter, Write},
     path::Path,
 };
@@ -19,5 +19,21 @@ fn main() {
     )
     .expect("Failed to write target triple text");
 
+    // The name and the version of the npm package, which key the cache.
+    let package_json = Path::new("../../packages/html/package.json");
+    println!("cargo:rerun-if-changed={}", package_json.display());
+    let package: serde_json::Value =
+        serde_json::from_slice(&fs::read(package_json).expect("Failed to read package.json"))
+            .expect("Failed to parse package.json");
+    println!(
+        "cargo:rustc-env=NPM_PACKAGE={}@{}",
+        package["name"]
+            .as_str()
+            .expect("package.json should have a name"),
+        package["version"]
+            .as_str()
+            .expect("package.json should have a version")
+    );
+
     napi_build::setup();
 }
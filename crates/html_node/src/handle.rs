@@ -0,0 +1,178 @@
+//! Handles which deserialize options once, so they can be reused for many
+//! inputs.
+//!
+//! The warnings about unknown options are reported in the output of every
+//! run.
+
+use anyhow::Context;
+use extra_common::node::{
+    abort::{abortable, AbortSignal},
+    handle::Prepared,
+};
+use html_core::{MinifyOptions, TransformOutput};
+use napi::{bindgen_prelude::*, Env, Task};
+use swc_nodejs_common::MapErr;
+
+use crate::{
+    cache::cached,
+    cache_kind, minify_inner,
+    panic::{ReportPanic, Reported},
+};
+
+/// Named apart from the `Minifier` of `css_node`, as napi looks classes up by
+/// name when both are linked into `extra_node`.
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "html"))]
+pub struct HtmlMinifier {
+    prepared: Prepared<MinifyOptions>,
+}
+
+pub struct RunMinifierTask {
+    code: Vec<u8>,
+    filename: Option<String>,
+    prepared: Prepared<MinifyOptions>,
+    is_fragment: bool,
+}
+
+#[napi]
+impl Task for RunMinifierTask {
+    type JsValue = TransformOutput;
+    type Output = TransformOutput;
+
+    fn compute(&mut self) -> napi::Result<Self::Output> {
+        let filename = self.filename.as_deref();
+
+        cached(
+            cache_kind(self.is_fragment),
+            &self.prepared.json,
+            &self.code,
+            filename,
+            || {
+                minify_inner(
+                    &self.code,
+                    filename,
+                    &self.prepared.options,
+                    self.is_fragment,
+                )
+                .map(|output| output.with_warnings(&self.prepared.warnings))
+            },
+        )
+        .convert_err()
+    }
+
+    fn resolve(&mut self, _env: napi::Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+        Ok(output)
+    }
+}
+
+impl HtmlMinifier {
+    fn task(
+        &self,
+        code: Buffer,
+        filename: Option<String>,
+        signal: Option<AbortSignal>,
+        is_fragment: bool,
+    ) -> AsyncTask<Reported<RunMinifierTask>> {
+        let code = code.to_vec();
+
+        let task = RunMinifierTask {
+            code,
+            filename,
+            prepared: self.prepared.clone(),
+            is_fragment,
+        };
+
+        abortable(task, signal)
+    }
+}
+
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "html"))]
+impl HtmlMinifier {
+    /// Minifies `code` as a document. `filename` overrides the one of the
+    /// options.
+    ///
+    /// `signal` aborts it while parsing, between phases and while walking
+    /// the tree; the minifier of swc is not interrupted once started.
+    #[napi]
+    pub fn run(
+        &self,
+        code: Buffer,
+        filename: Option<String>,
+        signal: Option<AbortSignal>,
+    ) -> AsyncTask<Reported<RunMinifierTask>> {
+        self.task(code, filename, signal, false)
+    }
+
+    /// Minifies `code` as a document fragment. `filename` overrides the one
+    /// of the options.
+    ///
+    /// `signal` aborts it while parsing, between phases and while walking
+    /// the tree; the minifier of swc is not interrupted once started.
+    #[napi]
+    pub fn run_fragment(
+        &self,
+        code: Buffer,
+        filename: Option<String>,
+        signal: Option<AbortSignal>,
+    ) -> AsyncTask<Reported<RunMinifierTask>> {
+        self.task(code, filename, signal, true)
+    }
+
+    #[napi]
+    pub fn run_sync(
+        &self,
+        env: Env,
+        code: Buffer,
+        filename: Option<String>,
+    ) -> napi::Result<TransformOutput> {
+        let filename = filename.as_deref();
+
+        cached(
+            cache_kind(false),
+            &self.prepared.json,
+            &code,
+            filename,
+            || {
+                minify_inner(&code, filename, &self.prepared.options, false)
+                    .map(|output| output.with_warnings(&self.prepared.warnings))
+            },
+        )
+        .convert_err()
+        .report_panic(&env)
+    }
+
+    #[napi]
+    pub fn run_fragment_sync(
+        &self,
+        env: Env,
+        code: Buffer,
+        filename: Option<String>,
+    ) -> napi::Result<TransformOutput> {
+        let filename = filename.as_deref();
+
+        cached(
+            cache_kind(true),
+            &self.prepared.json,
+            &code,
+            filename,
+            || {
+                minify_inner(&code, filename, &self.prepared.options, true)
+                    .map(|output| output.with_warnings(&self.prepared.warnings))
+            },
+        )
+        .convert_err()
+        .report_panic(&env)
+    }
+}
+
+#[allow(unused)]
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "html"))]
+pub fn create_minifier(opts: Buffer) -> napi::Result<HtmlMinifier> {
+    let prepared = Prepared::parse(&opts)
+        .context("failed to deserialize minifier options")
+        .convert_err()?;
+
+    Ok(HtmlMinifier { prepared })
+}
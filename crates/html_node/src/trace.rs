@@ -0,0 +1,40 @@
+//! Opt-in tracing of the phases of each function, like `parse` and
+//! `codegen`, to find the slow one for an input.
+
+use anyhow::Context;
+use extra_common::node::trace::{init, Config};
+use napi::Env;
+use swc_nodejs_common::MapErr;
+
+#[cfg_attr(not(feature = "namespace"), napi_derive::napi(object))]
+#[cfg_attr(feature = "namespace", napi_derive::napi(object, namespace = "html"))]
+#[derive(Default)]
+pub struct TracingOptions {
+    /// Like `RUST_LOG`. Defaults to `info`, which includes the phases.
+    pub filter: Option<String>,
+    /// `"chrome"`, `"json"` or `"pretty"`. Defaults to `"pretty"`.
+    pub format: Option<String>,
+    /// The file to write to. Defaults to stderr, or to `trace-{timestamp}.json`
+    /// in the working directory for `"chrome"`.
+    pub path: Option<String>,
+}
+
+/// Installs the subscriber which records the phases. It can be called only
+/// once.
+#[allow(unused)]
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "html"))]
+pub fn init_tracing(env: Env, options: Option<TracingOptions>) -> napi::Result<()> {
+    let options = options.unwrap_or_default();
+
+    init(
+        env,
+        Config {
+            filter: options.filter.as_deref(),
+            format: options.format.as_deref(),
+            path: options.path.as_deref(),
+        },
+    )
+    .context("failed to initialize tracing")
+    .convert_err()
+}
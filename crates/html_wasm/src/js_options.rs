@@ -0,0 +1,158 @@
+//! Options passed as JS objects, converted like the ones of the napi binding
+//! before they are deserialized by [parse_options_value]:
+//!
+//! - A `RegExp` is rejected, as its syntax differs from the one of the
+//!   patterns, which is the one of the `regex` crate.
+//! - A typed array becomes an array of numbers.
+//! - A property which is `undefined` is omitted.
+//! - A function is rejected, as no option accepts one.
+
+use anyhow::{anyhow, bail};
+use html_core::parse_options_value;
+use js_sys::{Array, ArrayBuffer, Function, Object, Reflect, RegExp};
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Number, Value};
+use wasm_bindgen::{JsCast, JsValue};
+
+/// Objects nested deeper than this are rejected, which also rejects cycles.
+const MAX_DEPTH: usize = 64;
+
+/// Options converted from a JS object.
+pub struct JsOptions {
+    value: Value,
+    functions: Vec<(String, Function)>,
+}
+
+impl JsOptions {
+    /// `undefined` and `null` are empty options.
+    pub fn from_value(value: &JsValue) -> anyhow::Result<Self> {
+        let mut converter = Converter { functions: vec![] };
+
+        let value = if value.is_undefined() || value.is_null() {
+            Value::Object(Map::new())
+        } else {
+            converter
+                .convert(value, String::new(), 0)?
+                .unwrap_or_default()
+        };
+
+        Ok(JsOptions {
+            value,
+            functions: converter.functions,
+        })
+    }
+
+    /// Returns the options as JSON, failing if there is a function.
+    pub fn finish(self) -> anyhow::Result<Value> {
+        if let Some((path, _)) = self.functions.first() {
+            bail!("option `{}` does not accept a function", path)
+        }
+
+        Ok(self.value)
+    }
+}
+
+/// Deserializes options from `value`, which may not contain functions.
+pub fn parse_js_options<T>(value: &JsValue) -> anyhow::Result<(T, Vec<String>)>
+where
+    T: DeserializeOwned,
+{
+    parse_options_value(JsOptions::from_value(value)?.finish()?)
+}
+
+struct Converter {
+    functions: Vec<(String, Function)>,
+}
+
+impl Converter {
+    /// Returns [None] if `value` should be omitted.
+    fn convert(
+        &mut self,
+        value: &JsValue,
+        path: String,
+        depth: usize,
+    ) -> anyhow::Result<Option<Value>> {
+        if depth > MAX_DEPTH {
+            bail!("option `{}` is nested too deeply", path)
+        }
+
+        if value.is_undefined() {
+            return Ok(None);
+        }
+        if value.is_null() {
+            return Ok(Some(Value::Null));
+        }
+        if let Some(v) = value.as_bool() {
+            return Ok(Some(Value::Bool(v)));
+        }
+        if let Some(v) = value.as_f64() {
+            return Ok(Some(number(v)));
+        }
+        if let Some(v) = value.as_string() {
+            return Ok(Some(Value::String(v)));
+        }
+        if let Some(f) = value.dyn_ref::<Function>() {
+            self.functions.push((path, f.clone()));
+            return Ok(None);
+        }
+        if value.is_instance_of::<RegExp>() {
+            bail!(
+                "option `{}` is a RegExp, which is not supported: pass its source as a string, in \
+                 the syntax of the Rust `regex` crate",
+                path
+            )
+        }
+
+        if Array::is_array(value) || ArrayBuffer::is_view(value) {
+            let array = Array::from(value);
+            let mut items = Vec::with_capacity(array.length() as usize);
+
+            for (i, item) in array.iter().enumerate() {
+                // Like `JSON.stringify`, `undefined` and functions are kept as
+                // `null` to preserve the indices.
+                items.push(
+                    self.convert(&item, format!("{}[{}]", path, i), depth + 1)?
+                        .unwrap_or(Value::Null),
+                );
+            }
+
+            return Ok(Some(Value::Array(items)));
+        }
+
+        if !value.is_object() {
+            bail!("option `{}` has an unsupported type", path)
+        }
+
+        let mut map = Map::new();
+
+        for key in Object::keys(value.unchecked_ref()).iter() {
+            let key = key.as_string().unwrap_or_default();
+            let value = Reflect::get(value, &JsValue::from_str(&key))
+                .map_err(|_| anyhow!("failed to read option `{}`", key))?;
+
+            let path = if path.is_empty() {
+                key.clone()
+            } else {
+                format!("{}.{}", path, key)
+            };
+
+            if let Some(value) = self.convert(&value, path, depth + 1)? {
+                map.insert(key, value);
+            }
+        }
+
+        Ok(Some(Value::Object(map)))
+    }
+}
+
+/// Integers are kept as integers so they can be deserialized as `usize`.
+fn number(v: f64) -> Value {
+    if v.fract() == 0.0 && v.abs() < 9_007_199_254_740_992.0 {
+        Value::from(v as i64)
+    } else {
+        // `NaN` and infinities become `null`, like `JSON.stringify`.
+        Number::from_f64(v)
+            .map(Value::Number)
+            .unwrap_or(Value::Null)
+    }
+}
@@ -0,0 +1,129 @@
+//! WebAssembly binding of `@swc/html`, for runtimes which cannot load the
+//! native one.
+//!
+//! The functions take options as objects, like `minifyObjectSync` of the
+//! native binding, and return the same outputs. They are synchronous.
+
+use anyhow::Context;
+use html_core::MinifyOptions;
+use js_sys::Uint8Array;
+use serde::Serialize;
+use wasm_bindgen::{prelude::*, JsCast};
+
+use crate::js_options::parse_js_options;
+
+mod js_options;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TYPES: &str = r#"
+export interface Diagnostic {
+  level: string
+  message: string
+  /** The id of the lint rule or the error code, if any. */
+  code?: string
+  span?: DiagnosticSpan
+  /** The source code around `span`, rendered like thrown errors. */
+  codeFrame?: string
+  /** True if this error prevented the input from being processed. */
+  fatal: boolean
+}
+export interface DiagnosticSpan {
+  filename: string
+  /** Byte offset from the start of the file. */
+  start: number
+  /** Byte offset from the start of the file. */
+  end: number
+  /** 1-based. */
+  startLine: number
+  /** 1-based, in characters. */
+  startColumn: number
+  /** 1-based. */
+  endLine: number
+  /** 1-based, in characters. */
+  endColumn: number
+}
+export interface TransformOutput {
+  /** The input as-is if it could not be parsed with `recover`. */
+  code: string
+  errors?: Array<Diagnostic>
+  /** Unknown keys of the options, if `unknownOptions` is `"warn"`. */
+  warnings?: Array<string>
+  /** If `stats` is requested. */
+  stats?: Stats
+}
+export interface Stats {
+  inputSize: number
+  outputSize: number
+  durations: Durations
+  before: Counts
+  after: Counts
+}
+export interface Durations {
+  parse: number
+  minify: number
+  codegen: number
+}
+export interface Counts {
+  elements: number
+  attributes: number
+  textNodes: number
+  comments: number
+}
+export function minify(code: string | Uint8Array, opts?: object): TransformOutput
+/** Parsed in `contextElement` of the options. */
+export function minifyFragment(code: string | Uint8Array, opts?: object): TransformOutput
+"#;
+
+#[wasm_bindgen(skip_typescript)]
+pub fn minify(code: JsValue, opts: JsValue) -> Result<JsValue, JsError> {
+    let code = input(&code)?;
+    let (opts, warnings) = parse_js_options::<MinifyOptions>(&opts)
+        .context("failed to deserialize minifier options")
+        .convert_err()?;
+
+    let output = html_core::minify(&code, None, &opts).convert_err()?;
+
+    to_js(&output.with_warnings(&warnings))
+}
+
+#[wasm_bindgen(js_name = "minifyFragment", skip_typescript)]
+pub fn minify_fragment(code: JsValue, opts: JsValue) -> Result<JsValue, JsError> {
+    let code = input(&code)?;
+    let (opts, warnings) = parse_js_options::<MinifyOptions>(&opts)
+        .context("failed to deserialize minifier options")
+        .convert_err()?;
+
+    let output = html_core::minify_fragment(&code, None, &opts).convert_err()?;
+
+    to_js(&output.with_warnings(&warnings))
+}
+
+/// A string is encoded as UTF-8, like a `Buffer` created from it.
+fn input(code: &JsValue) -> Result<Vec<u8>, JsError> {
+    if let Some(code) = code.as_string() {
+        return Ok(code.into_bytes());
+    }
+
+    match code.dyn_ref::<Uint8Array>() {
+        Some(code) => Ok(code.to_vec()),
+        None => Err(JsError::new("`code` must be a string or an Uint8Array")),
+    }
+}
+
+/// Like the objects of the native binding, so `None` is omitted.
+fn to_js<T: Serialize>(output: &T) -> Result<JsValue, JsError> {
+    output
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(JsError::from)
+}
+
+/// Like `MapErr` of `swc_nodejs_common`, which keeps the causes.
+trait MapErr<T> {
+    fn convert_err(self) -> Result<T, JsError>;
+}
+
+impl<T> MapErr<T> for anyhow::Result<T> {
+    fn convert_err(self) -> Result<T, JsError> {
+        self.map_err(|err| JsError::new(&format!("{:?}", err)))
+    }
+}
@@ -0,0 +1,72 @@
+//! Run with `wasm-pack test --node crates/html_wasm`.
+
+#![cfg(target_arch = "wasm32")]
+
+use js_sys::{Reflect, Uint8Array, JSON};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn options(json: &str) -> JsValue {
+    JSON::parse(json).unwrap()
+}
+
+fn get(value: &JsValue, key: &str) -> JsValue {
+    Reflect::get(value, &JsValue::from_str(key)).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn minify_string() {
+    let output = html_wasm::minify(
+        "<!doctype html><p>a<!-- b --></p>".into(),
+        JsValue::UNDEFINED,
+    )
+    .map_err(JsValue::from)
+    .unwrap();
+    let code = get(&output, "code").as_string().unwrap();
+
+    assert!(code.starts_with("<!doctype html>"));
+    assert!(!code.contains("<!--"));
+}
+
+#[wasm_bindgen_test]
+fn minify_bytes() {
+    let code = Uint8Array::from(&b"<!doctype html><p>a<!-- b --></p>"[..]);
+    let output = html_wasm::minify(code.into(), options(r#"{ "removeComments": false }"#))
+        .map_err(JsValue::from)
+        .unwrap();
+
+    assert!(get(&output, "code")
+        .as_string()
+        .unwrap()
+        .contains("<!-- b -->"));
+}
+
+#[wasm_bindgen_test]
+fn minify_rejects_unknown_options() {
+    assert!(html_wasm::minify("<p>a</p>".into(), options(r#"{ "foo": 1 }"#)).is_err());
+}
+
+#[wasm_bindgen_test]
+fn minify_fragment_has_no_document() {
+    let output = html_wasm::minify_fragment("<td>a</td>".into(), JsValue::NULL)
+        .map_err(JsValue::from)
+        .unwrap();
+    let code = get(&output, "code").as_string().unwrap();
+
+    assert!(code.contains("<td>a"));
+    assert!(!code.contains("<html"));
+}
+
+#[wasm_bindgen_test]
+fn stats_are_camel_case() {
+    let output = html_wasm::minify_fragment(
+        "<p class=\"a\">b</p>".into(),
+        options(r#"{ "stats": true }"#),
+    )
+    .map_err(JsValue::from)
+    .unwrap();
+    let before = get(&get(&output, "stats"), "before");
+
+    assert_eq!(get(&before, "elements").as_f64(), Some(1.0));
+    assert_eq!(get(&before, "textNodes").as_f64(), Some(1.0));
+}
@@ -0,0 +1,129 @@
+//! Checks of the [abort flag](crate::abort) while parsing and linting.
+
+use swc_common::BytePos;
+use swc_ecma_ast::{EsVersion, Module, Script};
+use swc_ecma_lints::rule::Rule;
+use swc_ecma_parser::{
+    error::Error, lexer::TokenContexts, token::TokenAndSpan, Context, Syntax, Tokens,
+};
+
+use crate::abort::{AbortFlag, Ticker};
+
+/// The tokens of a lexer, which end early once the call is aborted.
+///
+/// The module parsed from truncated tokens must be discarded, by
+/// [checking](crate::abort::check) again after parsing.
+#[derive(Clone)]
+pub struct Checked<I> {
+    input: I,
+    ticker: Ticker,
+}
+
+impl<I> Checked<I> {
+    pub fn new(input: I) -> Self {
+        Checked {
+            input,
+            ticker: Ticker::default(),
+        }
+    }
+}
+
+impl<I: Tokens> Iterator for Checked<I> {
+    type Item = TokenAndSpan;
+
+    fn next(&mut self) -> Option<TokenAndSpan> {
+        if !self.ticker.tick() {
+            return None;
+        }
+
+        self.input.next()
+    }
+}
+
+impl<I: Tokens> Tokens for Checked<I> {
+    fn set_ctx(&mut self, ctx: Context) {
+        self.input.set_ctx(ctx)
+    }
+
+    fn ctx(&self) -> Context {
+        self.input.ctx()
+    }
+
+    fn syntax(&self) -> Syntax {
+        self.input.syntax()
+    }
+
+    fn target(&self) -> EsVersion {
+        self.input.target()
+    }
+
+    fn start_pos(&self) -> BytePos {
+        self.input.start_pos()
+    }
+
+    fn set_expr_allowed(&mut self, allow: bool) {
+        self.input.set_expr_allowed(allow)
+    }
+
+    fn set_next_regexp(&mut self, start: Option<BytePos>) {
+        self.input.set_next_regexp(start)
+    }
+
+    fn token_context(&self) -> &TokenContexts {
+        self.input.token_context()
+    }
+
+    fn token_context_mut(&mut self) -> &mut TokenContexts {
+        self.input.token_context_mut()
+    }
+
+    fn set_token_context(&mut self, c: TokenContexts) {
+        self.input.set_token_context(c)
+    }
+
+    fn add_error(&self, error: Error) {
+        self.input.add_error(error)
+    }
+
+    fn add_module_mode_error(&self, error: Error) {
+        self.input.add_module_mode_error(error)
+    }
+
+    fn take_errors(&mut self) -> Vec<Error> {
+        self.input.take_errors()
+    }
+}
+
+/// A lint rule which is skipped once the call is aborted.
+///
+/// The rules run on the threads of rayon, so the flag is passed to each rule
+/// instead of being read from the [scope](crate::abort::scope).
+#[derive(Debug)]
+pub struct CheckedRule {
+    rule: Box<dyn Rule>,
+    flag: Option<AbortFlag>,
+}
+
+impl CheckedRule {
+    pub fn new(rule: Box<dyn Rule>, flag: Option<AbortFlag>) -> Self {
+        CheckedRule { rule, flag }
+    }
+
+    fn is_aborted(&self) -> bool {
+        self.flag.as_ref().map_or(false, AbortFlag::is_aborted)
+    }
+}
+
+impl Rule for CheckedRule {
+    fn lint_module(&mut self, program: &Module) {
+        if !self.is_aborted() {
+            self.rule.lint_module(program);
+        }
+    }
+
+    fn lint_script(&mut self, program: &Script) {
+        if !self.is_aborted() {
+            self.rule.lint_script(program);
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//! Decoding of the input, for sources which are not UTF-8.
+
+use std::borrow::Cow;
+
+use anyhow::Context;
+use encoding_rs::{Encoding, UTF_8};
+use extra_common::diagnostic::LintDiagnostic;
+
+/// The input decoded to UTF-8.
+pub struct Decoded<'a> {
+    pub code: Cow<'a, str>,
+    encoding: &'static Encoding,
+    /// True if invalid bytes were replaced with U+FFFD.
+    lossy: bool,
+}
+
+impl Decoded<'_> {
+    /// Returns a warning if invalid bytes were replaced.
+    pub fn diagnostic(&self) -> Option<LintDiagnostic> {
+        if !self.lossy {
+            return None;
+        }
+
+        Some(LintDiagnostic {
+            level: "warning".into(),
+            message: format!(
+                "the input contains bytes which are invalid in {} and were replaced with U+FFFD",
+                self.encoding.name()
+            ),
+            code: Some("lossy-decoding".into()),
+            span: None,
+            code_frame: None,
+        })
+    }
+}
+
+/// Decodes `bytes` with the encoding of, in order of precedence, the byte
+/// order mark, `label` and UTF-8.
+pub fn decode<'a>(bytes: &'a [u8], label: Option<&str>) -> anyhow::Result<Decoded<'a>> {
+    let (encoding, bom_len) = match Encoding::for_bom(bytes) {
+        Some(v) => v,
+        None => {
+            let encoding = match label {
+                Some(label) => for_label(label)?,
+                None => UTF_8,
+            };
+
+            (encoding, 0)
+        }
+    };
+
+    let (code, lossy) = encoding.decode_without_bom_handling(&bytes[bom_len..]);
+
+    Ok(Decoded {
+        code,
+        encoding,
+        lossy,
+    })
+}
+
+fn for_label(label: &str) -> anyhow::Result<&'static Encoding> {
+    Encoding::for_label(label.as_bytes()).with_context(|| format!("unknown encoding `{}`", label))
+}
@@ -0,0 +1,245 @@
+//! The JavaScript linter of `@swc/linter`, without the bindings for Node.js.
+//!
+//! [lint] takes the input as bytes, which are decoded with the `encoding` of
+//! the options, and returns the diagnostics of the rules. Options can be
+//! deserialized from JSON with [parse_options].
+
+use anyhow::bail;
+/// An aborted call stops between the phases, while parsing or before each
+/// lint rule.
+pub use extra_common::abort;
+use extra_common::{
+    diagnostic::{to_diagnostic, Collector, LintDiagnostic},
+    util::try_with,
+};
+pub use extra_common::{
+    options::{parse_options, parse_options_value},
+    panic,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use swc_common::{
+    errors::{Handler, HANDLER},
+    FileName, Mark, SyntaxContext,
+};
+use swc_ecma_ast::*;
+use swc_ecma_lints::{config::LintConfig, rule::Rule, rules::LintParams};
+use swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax};
+use swc_ecma_transforms_base::resolver;
+use swc_ecma_visit::VisitMutWith;
+use tracing::{info_span, instrument};
+
+use crate::{
+    checked::{Checked, CheckedRule},
+    encoding::{decode, Decoded},
+};
+
+mod checked;
+mod encoding;
+pub mod schema;
+
+// parse it
+// apply resolver
+// apply lints, maybe in parallel
+// emit diagnostics
+
+/// [LintDiagnostic] by its name in JS, which napi only knows within
+/// `extra_common`, so the types of [TransformOutput] refer to it.
+type Diagnostic = LintDiagnostic;
+
+#[cfg_attr(
+    all(feature = "napi", not(feature = "namespace")),
+    napi_derive::napi(object)
+)]
+#[cfg_attr(
+    all(feature = "napi", feature = "namespace"),
+    napi_derive::napi(object, namespace = "linter")
+)]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransformOutput {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errors: Option<Vec<Diagnostic>>,
+    /// Unknown keys of the options, if `unknownOptions` is `"warn"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warnings: Option<Vec<String>>,
+}
+
+impl TransformOutput {
+    /// Reports the unknown keys of the options, returned by [parse_options].
+    pub fn with_warnings(mut self, warnings: &[String]) -> Self {
+        if !warnings.is_empty() {
+            self.warnings = Some(warnings.to_vec());
+        }
+        self
+    }
+
+    /// Reports the lossy decoding of `input`.
+    fn decoded(mut self, input: &Decoded) -> Self {
+        if let Some(d) = input.diagnostic() {
+            self.errors.get_or_insert_with(Vec::new).push(d);
+        }
+        self
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LintOptions {
+    #[serde(default)]
+    pub filename: Option<String>,
+    /// The encoding of the input, if it has no byte order mark. Defaults to
+    /// UTF-8.
+    #[serde(default)]
+    pub encoding: Option<String>,
+    #[serde(default)]
+    #[schemars(schema_with = "schema::lint_config")]
+    pub rules: LintConfig,
+    #[serde(flatten)]
+    #[schemars(schema_with = "schema::syntax")]
+    pub syntax: Syntax,
+    #[serde(default)]
+    #[schemars(schema_with = "schema::es_version")]
+    pub target: EsVersion,
+    /// Return `codeFrame` in the diagnostics. Defaults to true.
+    #[serde(default = "true_by_default")]
+    pub code_frame: bool,
+}
+
+impl Default for LintOptions {
+    fn default() -> Self {
+        Self {
+            filename: None,
+            encoding: None,
+            rules: Default::default(),
+            syntax: Default::default(),
+            target: Default::default(),
+            code_frame: true,
+        }
+    }
+}
+
+const fn true_by_default() -> bool {
+    true
+}
+
+/// Lints a module with the rules of `opts.rules`.
+pub fn lint(
+    code: &[u8],
+    filename: Option<&str>,
+    opts: &LintOptions,
+) -> anyhow::Result<TransformOutput> {
+    // The marks of the resolver need globals, which the threads of callers
+    // may not have.
+    swc_common::GLOBALS.set(&Default::default(), || lint_inner(code, filename, opts))
+}
+
+#[instrument(skip_all)]
+fn lint_inner(
+    code: &[u8],
+    filename: Option<&str>,
+    opts: &LintOptions,
+) -> anyhow::Result<TransformOutput> {
+    abort::check()?;
+
+    let input = decode(code, opts.encoding.as_deref())?;
+    let code = &*input.code;
+
+    let output = try_with(|cm, handler| {
+        let filename = match filename.or(opts.filename.as_deref()) {
+            Some(v) => FileName::Real(v.into()),
+            None => FileName::Anon,
+        };
+
+        let fm = cm.new_source_file(filename, code.into());
+
+        let mut errors = vec![];
+
+        let module = info_span!("parse").in_scope(|| {
+            let lexer = Lexer::new(
+                Syntax::default(),
+                opts.target,
+                StringInput::from(&*fm),
+                None,
+            );
+            let mut parser = Parser::new_from(Checked::new(lexer));
+            let result = parser.parse_module();
+
+            errors.extend(parser.take_errors());
+
+            result
+        });
+        abort::check()?;
+
+        let mut module = match module {
+            Ok(module) => module,
+            Err(err) => {
+                err.into_diagnostic(handler).emit();
+
+                for err in errors {
+                    err.into_diagnostic(handler).emit();
+                }
+
+                bail!("Failed to parse input as module")
+            }
+        };
+
+        let diagnostics = Collector::default();
+        let collector = Handler::with_emitter(true, false, Box::new(diagnostics.clone()));
+
+        for err in errors {
+            err.into_diagnostic(&collector).emit();
+        }
+
+        abort::check()?;
+
+        let unresolved_mark = Mark::new();
+        let top_level_mark = Mark::new();
+        let unresolved_ctxt = SyntaxContext::empty().apply_mark(unresolved_mark);
+        let top_level_ctxt = SyntaxContext::empty().apply_mark(top_level_mark);
+
+        info_span!("resolver").in_scope(|| {
+            module.visit_mut_with(&mut resolver(unresolved_mark, top_level_mark, false));
+        });
+
+        abort::check()?;
+
+        let flag = abort::current();
+        let mut rules = swc_ecma_lints::rules::all(LintParams {
+            program: &Program::Module(module.clone()),
+            lint_config: &opts.rules,
+            unresolved_ctxt,
+            top_level_ctxt,
+            es_version: opts.target,
+            source_map: cm.clone(),
+        })
+        .into_iter()
+        .map(|rule| CheckedRule::new(rule, flag.clone()))
+        .collect::<Vec<_>>();
+
+        info_span!("lint").in_scope(|| {
+            HANDLER.set(&collector, || {
+                rules.lint_module(&module);
+            });
+        });
+
+        abort::check()?;
+
+        let diagnostics = diagnostics
+            .take()
+            .iter()
+            .map(|d| to_diagnostic(cm, d, opts.code_frame).into())
+            .collect::<Vec<_>>();
+
+        Ok(TransformOutput {
+            errors: if diagnostics.is_empty() {
+                None
+            } else {
+                Some(diagnostics)
+            },
+            warnings: None,
+        })
+    })?;
+
+    Ok(output.decoded(&input))
+}
@@ -0,0 +1,106 @@
+//! JSON Schema of the options, for the autocompletion of editors and to check
+//! the types of `index.ts`.
+
+use extra_common::schema::{from_json, root_schema, schema_of_default};
+use schemars::{gen::SchemaGenerator, schema::Schema};
+use serde_json::{json, Map, Value};
+use swc_ecma_lints::config::LintConfig;
+use swc_ecma_parser::Syntax;
+
+use crate::LintOptions;
+
+/// Returns the schemas of the options, keyed by the function using them.
+pub fn options_schema() -> anyhow::Result<Value> {
+    Ok(json!({
+        "lint": root_schema::<LintOptions>()?,
+    }))
+}
+
+/// [LintConfig]
+///
+/// The rules are taken from the serialized default config, so every rule
+/// compiled in is listed.
+pub fn lint_config(gen: &mut SchemaGenerator) -> Schema {
+    let level = format!("{}LintRuleLevel", gen.settings().definitions_path);
+    gen.definitions_mut()
+        .insert("LintRuleLevel".into(), lint_rule_level());
+
+    let default = serde_json::to_value(LintConfig::default()).expect("failed to serialize rules");
+
+    let properties = default
+        .as_object()
+        .into_iter()
+        .flatten()
+        .map(|(rule, config)| {
+            // `RuleConfig` is serialized as `[level, options]`.
+            let options = config
+                .get(1)
+                .map(schema_of_default)
+                .unwrap_or_else(|| json!({}));
+
+            (
+                rule.clone(),
+                json!({
+                    "type": "array",
+                    "items": [
+                        { "$ref": level },
+                        options,
+                    ],
+                    "minItems": 1,
+                    "maxItems": 2,
+                }),
+            )
+        })
+        .collect::<Map<_, _>>();
+
+    from_json(json!({
+        "type": "object",
+        "properties": properties,
+        "additionalProperties": false,
+    }))
+}
+
+/// `LintRuleLevel` of [swc_ecma_lints::config].
+fn lint_rule_level() -> Schema {
+    from_json(json!({
+        "anyOf": [
+            { "enum": ["off", "warning", "error"] },
+            { "enum": [0, 1, 2] },
+        ],
+    }))
+}
+
+/// [Syntax]
+///
+/// The flags are taken from the serialized default config.
+pub fn syntax(_: &mut SchemaGenerator) -> Schema {
+    let default = serde_json::to_value(Syntax::default()).expect("failed to serialize syntax");
+
+    let mut properties = default
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter(|(key, _)| key.as_str() != "syntax")
+        .map(|(key, value)| (key.clone(), schema_of_default(value)))
+        .collect::<Map<_, _>>();
+
+    properties.insert(
+        "syntax".into(),
+        json!({ "enum": ["ecmascript", "typescript"] }),
+    );
+
+    from_json(json!({
+        "type": "object",
+        "properties": properties,
+    }))
+}
+
+/// [swc_ecma_ast::EsVersion]
+pub fn es_version(_: &mut SchemaGenerator) -> Schema {
+    from_json(json!({
+        "enum": [
+            "es3", "es5", "es2015", "es2016", "es2017", "es2018", "es2019", "es2020", "es2021",
+            "es2022", "esnext",
+        ],
+    }))
+}
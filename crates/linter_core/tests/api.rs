@@ -0,0 +1,67 @@
+use linter_core::{
+    abort::{self, AbortFlag, Aborted},
+    parse_options, LintOptions,
+};
+
+fn options(json: &str) -> LintOptions {
+    let (opts, warnings) = parse_options(json).unwrap();
+    assert!(warnings.is_empty());
+    opts
+}
+
+#[test]
+fn reports_enabled_rules() {
+    let opts = options(r#"{ "syntax": "ecmascript", "rules": { "no-debugger": ["error"] } }"#);
+    let output = linter_core::lint(b"debugger;", Some("a.js"), &opts).unwrap();
+    let errors = output.errors.unwrap();
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].message, "Unexpected 'debugger' statement");
+    assert_eq!(errors[0].span.as_ref().unwrap().filename, "a.js");
+}
+
+#[test]
+fn ignores_disabled_rules() {
+    let output = linter_core::lint(b"debugger;", None, &Default::default()).unwrap();
+
+    assert!(output.errors.is_none());
+}
+
+#[test]
+fn reports_builtin_rules() {
+    let output = linter_core::lint(b"const a = 1; a = 2;", None, &Default::default()).unwrap();
+
+    assert!(output.errors.is_some());
+}
+
+#[test]
+fn fails_on_invalid_input() {
+    assert!(linter_core::lint(b"let = ;", None, &Default::default()).is_err());
+}
+
+#[test]
+fn runs_on_other_threads() {
+    let opts = options(r#"{ "syntax": "ecmascript", "rules": { "no-debugger": ["error"] } }"#);
+
+    std::thread::scope(|s| {
+        s.spawn(|| {
+            let output = linter_core::lint(b"debugger;", None, &opts).unwrap();
+            assert!(output.errors.is_some());
+        });
+    });
+}
+
+#[test]
+fn aborted_calls_fail() {
+    let flag = AbortFlag::default();
+    let lint = || {
+        abort::scope(Some(&flag), || {
+            linter_core::lint(b"debugger;", None, &Default::default())
+        })
+    };
+
+    assert!(lint().is_ok());
+
+    flag.abort();
+    assert!(lint().unwrap_err().is::<Aborted>());
+}
@@ -0,0 +1,58 @@
+//! The napi functions of the [cache](extra_common::cache) of the results on
+//! disk.
+
+use anyhow::Context;
+use extra_common::cache::Cache;
+use serde::{de::DeserializeOwned, Serialize};
+use swc_nodejs_common::MapErr;
+
+static CACHE: Cache = Cache::new(env!("NPM_PACKAGE"));
+
+#[cfg_attr(not(feature = "namespace"), napi_derive::napi(object))]
+#[cfg_attr(feature = "namespace", napi_derive::napi(object, namespace = "linter"))]
+pub struct CacheOptions {
+    /// The directory of the cached results, which is created if needed. It
+    /// can be shared by processes.
+    pub dir: String,
+    /// The size of the cached results in bytes, over which the least recently
+    /// used ones are removed. Defaults to 512 MiB.
+    pub max_size: Option<i64>,
+}
+
+/// Caches the results of `lint` in `options.dir`. Calling it again replaces
+/// the options.
+#[allow(unused)]
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "linter"))]
+pub fn enable_cache(options: CacheOptions) -> napi::Result<()> {
+    CACHE
+        .enable(&options.dir, options.max_size)
+        .context("failed to enable the cache")
+        .convert_err()
+}
+
+/// Removes the cached results. The cache stays enabled.
+#[allow(unused)]
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "linter"))]
+pub fn clear_cache() -> napi::Result<()> {
+    CACHE
+        .clear()
+        .context("failed to clear the cache")
+        .convert_err()
+}
+
+/// See [Cache::cached].
+pub fn cached<T, F>(
+    kind: &str,
+    options: &str,
+    code: &[u8],
+    filename: Option<&str>,
+    compute: F,
+) -> anyhow::Result<T>
+where
+    T: Serialize + DeserializeOwned,
+    F: FnOnce() -> anyhow::Result<T>,
+{
+    CACHE.cached(kind, options, code, filename, compute)
+}
@@ -0,0 +1,106 @@
+//! Handles which deserialize options once, so they can be reused for many
+//! inputs.
+//!
+//! The warnings about unknown options are reported in the output of every
+//! run.
+
+use anyhow::Context;
+use extra_common::node::{
+    abort::{abortable, AbortSignal},
+    handle::Prepared,
+};
+use linter_core::{LintOptions, TransformOutput};
+use napi::{bindgen_prelude::*, Env, Task};
+use swc_nodejs_common::MapErr;
+
+use crate::{
+    cache::cached,
+    panic::{ReportPanic, Reported},
+};
+
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "linter"))]
+pub struct Linter {
+    prepared: Prepared<LintOptions>,
+}
+
+pub struct RunLinterTask {
+    code: Vec<u8>,
+    filename: Option<String>,
+    prepared: Prepared<LintOptions>,
+}
+
+#[napi]
+impl Task for RunLinterTask {
+    type JsValue = TransformOutput;
+    type Output = TransformOutput;
+
+    fn compute(&mut self) -> napi::Result<Self::Output> {
+        let filename = self.filename.as_deref();
+
+        cached("lint", &self.prepared.json, &self.code, filename, || {
+            linter_core::lint(&self.code, filename, &self.prepared.options)
+                .map(|output| output.with_warnings(&self.prepared.warnings))
+        })
+        .convert_err()
+    }
+
+    fn resolve(&mut self, _env: napi::Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+        Ok(output)
+    }
+}
+
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "linter"))]
+impl Linter {
+    /// `filename` overrides the one of the options.
+    ///
+    /// `signal` aborts it while parsing, between phases and before each lint
+    /// rule; a running lint rule is not interrupted.
+    #[napi]
+    pub fn run(
+        &self,
+        code: Buffer,
+        filename: Option<String>,
+        signal: Option<AbortSignal>,
+    ) -> AsyncTask<Reported<RunLinterTask>> {
+        let code = code.to_vec();
+
+        let task = RunLinterTask {
+            code,
+            filename,
+            prepared: self.prepared.clone(),
+        };
+
+        abortable(task, signal)
+    }
+
+    /// `filename` overrides the one of the options.
+    #[napi]
+    pub fn run_sync(
+        &self,
+        env: Env,
+        code: Buffer,
+        filename: Option<String>,
+    ) -> napi::Result<TransformOutput> {
+        let filename = filename.as_deref();
+
+        cached("lint", &self.prepared.json, &code, filename, || {
+            linter_core::lint(&code, filename, &self.prepared.options)
+                .map(|output| output.with_warnings(&self.prepared.warnings))
+        })
+        .convert_err()
+        .report_panic(&env)
+    }
+}
+
+#[allow(unused)]
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "linter"))]
+pub fn create_linter(opts: Buffer) -> napi::Result<Linter> {
+    let prepared = Prepared::parse(&opts)
+        .context("failed to deserialize linter options")
+        .convert_err()?;
+
+    Ok(Linter { prepared })
+}
@@ -1,69 +1,39 @@
 #[macro_use]
 extern crate napi_derive;
 
-mod util;
-
-use std::{backtrace::Backtrace, env, panic::set_hook};
-
-use anyhow::{bail, Context};
-use napi::{bindgen_prelude::*, Task};
-use serde::{Deserialize, Serialize};
-use swc_common::{FileName, Mark, SyntaxContext};
-use swc_ecma_ast::*;
-use swc_ecma_lints::{config::LintConfig, rule::Rule, rules::LintParams};
-use swc_ecma_parser::Syntax;
-use swc_ecma_transforms_base::resolver;
-use swc_ecma_visit::VisitMutWith;
-use swc_nodejs_common::{deserialize_json, get_deserialized, MapErr};
-
-use crate::util::try_with;
-
-// parse it
-// apply resolver
-// apply lints, maybe in parallel
-// emit diagnostics
+mod batch;
+mod cache;
+mod handle;
+mod panic;
+mod trace;
+
+use anyhow::Context;
+use extra_common::node::{
+    abort::{abortable, AbortSignal},
+    js_options::JsOptions,
+};
+use linter_core::{parse_options, TransformOutput};
+use napi::{bindgen_prelude::*, Env, JsObject, Task};
+use swc_nodejs_common::MapErr;
+
+use crate::{
+    batch::{run_batch, File, FileInput, FileOutput},
+    panic::{ReportPanic, Reported},
+};
 
 #[napi::module_init]
 fn init() {
-    if cfg!(debug_assertions) || env::var("SWC_DEBUG").unwrap_or_default() == "1" {
-        set_hook(Box::new(|panic_info| {
-            let backtrace = Backtrace::force_capture();
-            println!("Panic: {:?}\nBacktrace: {:?}", panic_info, backtrace);
-        }));
-    }
-}
-
-#[napi_derive::napi(object)]
-#[derive(Debug, Serialize)]
-pub struct Diagnostic {
-    pub level: String,
-    pub message: String,
-    pub span: serde_json::Value,
-}
-
-#[napi_derive::napi(object)]
-#[derive(Debug, Serialize)]
-pub struct TransformOutput {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub errors: Option<Vec<Diagnostic>>,
+    linter_core::panic::install_hook();
 }
 
 struct LintTask {
-    code: String,
+    code: Vec<u8>,
     options: String,
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct LintOptions {
-    #[serde(default)]
-    filename: Option<String>,
-    #[serde(default)]
-    rules: LintConfig,
-    #[serde(flatten)]
-    pub syntax: Syntax,
-    #[serde(default)]
-    pub target: EsVersion,
+struct LintManyTask {
+    files: Vec<File>,
+    options: String,
 }
 
 #[napi]
@@ -72,11 +42,21 @@ impl Task for LintTask {
     type Output = TransformOutput;
 
     fn compute(&mut self) -> napi::Result<Self::Output> {
-        let opts = deserialize_json(&self.options)
-            .context("failed to deserialize linter options")
-            .convert_err()?;
+        lint_json(&self.code, None, &self.options).convert_err()
+    }
 
-        lint_inner(&self.code, opts).convert_err()
+    fn resolve(&mut self, _env: napi::Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+        Ok(output)
+    }
+}
+
+#[napi]
+impl Task for LintManyTask {
+    type JsValue = Vec<FileOutput>;
+    type Output = Vec<FileOutput>;
+
+    fn compute(&mut self) -> napi::Result<Self::Output> {
+        run_batch("lint", &self.files, &self.options, linter_core::lint).convert_err()
     }
 
     fn resolve(&mut self, _env: napi::Env, output: Self::Output) -> napi::Result<Self::JsValue> {
@@ -84,79 +64,108 @@ impl Task for LintTask {
     }
 }
 
-fn lint_inner(code: &str, opts: LintOptions) -> anyhow::Result<TransformOutput> {
-    try_with(|cm, handler| {
-        let filename = match opts.filename {
-            Some(v) => FileName::Real(v.into()),
-            None => FileName::Anon,
-        };
-
-        let fm = cm.new_source_file(filename, code.into());
-
-        let mut errors = vec![];
-
-        let module = swc_ecma_parser::parse_file_as_module(
-            &fm,
-            Syntax::default(),
-            opts.target,
-            None,
-            &mut errors,
-        );
-
-        let mut module = match module {
-            Ok(module) => module,
-            Err(err) => {
-                err.into_diagnostic(handler).emit();
-
-                for err in errors {
-                    err.into_diagnostic(handler).emit();
-                }
-
-                bail!("Failed to parse input as module")
-            }
-        };
-
-        let unresolved_mark = Mark::new();
-        let top_level_mark = Mark::new();
-        let unresolved_ctxt = SyntaxContext::empty().apply_mark(unresolved_mark);
-        let top_level_ctxt = SyntaxContext::empty().apply_mark(top_level_mark);
-
-        module.visit_mut_with(&mut resolver(unresolved_mark, top_level_mark, false));
-
-        let mut rules = swc_ecma_lints::rules::all(LintParams {
-            program: &Program::Module(module.clone()),
-            lint_config: &opts.rules,
-            unresolved_ctxt,
-            top_level_ctxt,
-            es_version: opts.target,
-            source_map: cm.clone(),
-        });
-
-        rules.lint_module(&module);
-
-        Ok(())
+/// [linter_core::lint] with the options deserialized from `options`, through
+/// the [cache].
+fn lint_json(
+    code: &[u8],
+    filename: Option<&str>,
+    options: &str,
+) -> anyhow::Result<TransformOutput> {
+    cache::cached("lint", options, code, filename, || {
+        let (opts, warnings) =
+            parse_options(options).context("failed to deserialize linter options")?;
+
+        linter_core::lint(code, filename, &opts).map(|output| output.with_warnings(&warnings))
     })
-    .convert_err()?;
+}
 
-    Ok(TransformOutput { errors: None })
+/// `signal` aborts it while parsing, between phases and before each lint
+/// rule; a running lint rule is not interrupted.
+#[allow(unused)]
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "linter"))]
+fn lint(code: Buffer, opts: Buffer, signal: Option<AbortSignal>) -> AsyncTask<Reported<LintTask>> {
+    let code = code.to_vec();
+    let options = String::from_utf8_lossy(opts.as_ref()).to_string();
+
+    let task = LintTask { code, options };
+
+    abortable(task, signal)
 }
 
+/// Lints `files` in parallel, sharing `opts`.
+///
+/// `signal` aborts it while parsing, between phases and before each lint
+/// rule; a running lint rule is not interrupted.
 #[allow(unused)]
-#[napi]
-fn lint(code: Buffer, opts: Buffer, signal: Option<AbortSignal>) -> AsyncTask<LintTask> {
-    let code = String::from_utf8_lossy(code.as_ref()).to_string();
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "linter"))]
+fn lint_many(
+    files: Vec<FileInput>,
+    opts: Buffer,
+    signal: Option<AbortSignal>,
+) -> AsyncTask<Reported<LintManyTask>> {
+    let files = files.into_iter().map(File::from).collect();
     let options = String::from_utf8_lossy(opts.as_ref()).to_string();
 
+    let task = LintManyTask { files, options };
+
+    abortable(task, signal)
+}
+
+#[allow(unused)]
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "linter"))]
+pub fn lint_sync(env: Env, code: Buffer, opts: Buffer) -> napi::Result<TransformOutput> {
+    lint_json(&code, None, &String::from_utf8_lossy(opts.as_ref()))
+        .convert_err()
+        .report_panic(&env)
+}
+
+/// Like [lint], with `opts` as a JS object instead of a JSON buffer.
+///
+/// `signal` aborts it while parsing, between phases and before each lint
+/// rule; a running lint rule is not interrupted.
+#[allow(unused)]
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "linter"))]
+fn lint_object(
+    env: Env,
+    code: Buffer,
+    opts: JsObject,
+    signal: Option<AbortSignal>,
+) -> napi::Result<AsyncTask<Reported<LintTask>>> {
+    let code = code.to_vec();
+    let options = JsOptions::from_object(&env, opts)
+        .and_then(JsOptions::finish)
+        .context("failed to convert linter options")
+        .convert_err()?
+        .to_string();
+
     let task = LintTask { code, options };
 
-    AsyncTask::with_optional_signal(task, signal)
+    Ok(abortable(task, signal))
 }
 
 #[allow(unused)]
-#[napi]
-pub fn lint_sync(code: Buffer, opts: Buffer) -> napi::Result<TransformOutput> {
-    let code = String::from_utf8_lossy(code.as_ref());
-    let opts = get_deserialized(opts)?;
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "linter"))]
+pub fn lint_object_sync(env: Env, code: Buffer, opts: JsObject) -> napi::Result<TransformOutput> {
+    let options = JsOptions::from_object(&env, opts)
+        .and_then(JsOptions::finish)
+        .context("failed to convert linter options")
+        .convert_err()?
+        .to_string();
+
+    lint_json(&code, None, &options)
+        .convert_err()
+        .report_panic(&env)
+}
 
-    lint_inner(&code, opts).convert_err()
+/// Returns the JSON Schema of the options, keyed by the function using them.
+#[allow(unused)]
+#[cfg_attr(not(feature = "namespace"), napi)]
+#[cfg_attr(feature = "namespace", napi(namespace = "linter"))]
+pub fn get_options_schema() -> napi::Result<serde_json::Value> {
+    linter_core::schema::options_schema().convert_err()
 }
@@ -0,0 +1,91 @@
+//! WebAssembly binding of `@swc/linter`, for runtimes which cannot load the
+//! native one.
+//!
+//! [lint] takes options as an object, like `lintObjectSync` of the native
+//! binding, and returns the same output. It is synchronous.
+
+use anyhow::Context;
+use js_sys::Uint8Array;
+use linter_core::LintOptions;
+use serde::Serialize;
+use wasm_bindgen::{prelude::*, JsCast};
+
+use crate::js_options::parse_js_options;
+
+mod js_options;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TYPES: &str = r#"
+export interface Diagnostic {
+  level: string
+  message: string
+  /** The id of the lint rule or the error code, if any. */
+  code?: string
+  span?: DiagnosticSpan
+  /** The source code around `span`, rendered like thrown errors. */
+  codeFrame?: string
+}
+export interface DiagnosticSpan {
+  filename: string
+  /** Byte offset from the start of the file. */
+  start: number
+  /** Byte offset from the start of the file. */
+  end: number
+  /** 1-based. */
+  startLine: number
+  /** 1-based, in characters. */
+  startColumn: number
+  /** 1-based. */
+  endLine: number
+  /** 1-based, in characters. */
+  endColumn: number
+}
+export interface TransformOutput {
+  errors?: Array<Diagnostic>
+  /** Unknown keys of the options, if `unknownOptions` is `"warn"`. */
+  warnings?: Array<string>
+}
+export function lint(code: string | Uint8Array, opts?: object): TransformOutput
+"#;
+
+#[wasm_bindgen(skip_typescript)]
+pub fn lint(code: JsValue, opts: JsValue) -> Result<JsValue, JsError> {
+    let code = input(&code)?;
+    let (opts, warnings) = parse_js_options::<LintOptions>(&opts)
+        .context("failed to deserialize linter options")
+        .convert_err()?;
+
+    let output = linter_core::lint(&code, None, &opts).convert_err()?;
+
+    to_js(&output.with_warnings(&warnings))
+}
+
+/// A string is encoded as UTF-8, like a `Buffer` created from it.
+fn input(code: &JsValue) -> Result<Vec<u8>, JsError> {
+    if let Some(code) = code.as_string() {
+        return Ok(code.into_bytes());
+    }
+
+    match code.dyn_ref::<Uint8Array>() {
+        Some(code) => Ok(code.to_vec()),
+        None => Err(JsError::new("`code` must be a string or an Uint8Array")),
+    }
+}
+
+/// Like the objects of the native binding, so `None` is omitted.
+fn to_js<T: Serialize>(output: &T) -> Result<JsValue, JsError> {
+    output
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(JsError::from)
+}
+
+/// Like `MapErr` of `swc_nodejs_common`, which keeps the causes.
+trait MapErr<T> {
+    fn convert_err(self) -> Result<T, JsError>;
+}
+
+impl<T> MapErr<T> for anyhow::Result<T> {
+    fn convert_err(self) -> Result<T, JsError> {
+        self.map_err(|err| JsError::new(&format!("{:?}", err)))
+    }
+}
@@ -0,0 +1,51 @@
+//! Run with `wasm-pack test --node crates/linter_wasm`.
+
+#![cfg(target_arch = "wasm32")]
+
+use js_sys::{Array, Reflect, Uint8Array, JSON};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn options(json: &str) -> JsValue {
+    JSON::parse(json).unwrap()
+}
+
+fn get(value: &JsValue, key: &str) -> JsValue {
+    Reflect::get(value, &JsValue::from_str(key)).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn reports_enabled_rules() {
+    let output = linter_wasm::lint(
+        "debugger;".into(),
+        options(r#"{ "syntax": "ecmascript", "rules": { "no-debugger": ["error"] } }"#),
+    )
+    .map_err(JsValue::from)
+    .unwrap();
+    let errors = Array::from(&get(&output, "errors"));
+
+    assert_eq!(errors.length(), 1);
+    assert_eq!(
+        get(&errors.get(0), "message").as_string().unwrap(),
+        "Unexpected 'debugger' statement"
+    );
+}
+
+#[wasm_bindgen_test]
+fn ignores_disabled_rules() {
+    let code = Uint8Array::from(&b"debugger;"[..]);
+    let output = linter_wasm::lint(code.into(), options(r#"{ "syntax": "ecmascript" }"#))
+        .map_err(JsValue::from)
+        .unwrap();
+
+    assert!(get(&output, "errors").is_undefined());
+}
+
+#[wasm_bindgen_test]
+fn fails_on_invalid_input() {
+    let err = linter_wasm::lint("let = ;".into(), options(r#"{ "syntax": "ecmascript" }"#))
+        .map_err(JsValue::from)
+        .unwrap_err();
+
+    assert!(!format!("{:?}", err).contains("options"));
+}
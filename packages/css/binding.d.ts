@@ -6,18 +6,226 @@
 export interface Diagnostic {
   level: string
   message: string
-  span: any
+  /** The id of the lint rule or the error code, if any. */
+  code?: string
+  span?: DiagnosticSpan
+  /** The source code around `span`, rendered like thrown errors. */
+  codeFrame?: string
+  /** True if this error prevented the input from being processed. */
+  fatal: boolean
+}
+export interface DiagnosticSpan {
+  filename: string
+  /** Byte offset from the start of the file. */
+  start: number
+  /** Byte offset from the start of the file. */
+  end: number
+  /** 1-based. */
+  startLine: number
+  /** 1-based, in characters. */
+  startColumn: number
+  /** 1-based. */
+  endLine: number
+  /** 1-based, in characters. */
+  endColumn: number
 }
 export interface TransformOutput {
+  /** The input as-is if it could not be parsed with `recover`. */
   code: string
   map?: string
   errors?: Array<Diagnostic>
+  /** Unknown keys of the options, if `unknownOptions` is `"warn"`. */
+  warnings?: Array<string>
   /** JSON string. */
   deps?: string
   /** JSON string. */
   modulesMapping?: string
+  /** If `stats` is requested. */
+  stats?: Stats
+}
+export interface LintOutput {
+  diagnostics: Array<Diagnostic>
+  /** Recoverable errors of the parser. */
+  errors?: Array<Diagnostic>
+  /** Unknown keys of the options, if `unknownOptions` is `"warn"`. */
+  warnings?: Array<string>
+}
+export interface Stats {
+  /** Bytes of the input. */
+  inputSize: number
+  /** Bytes of `code`. */
+  outputSize: number
+  /** Milliseconds spent in each phase. A phase which did not run is `0`. */
+  durations: Durations
+  /** Counted after parsing. */
+  before: Counts
+  /**
+   * Counted before generating the code, so the difference with `before`
+   * is what was removed or merged.
+   */
+  after: Counts
+}
+export interface Durations {
+  parse: number
+  /**
+   * Everything between parsing and minifying, like CSS Modules and
+   * prefixing.
+   */
+  transforms: number
+  minify: number
+  codegen: number
+  sourceMap: number
+}
+export interface Counts {
+  /** Qualified rules and at-rules, including nested ones. */
+  rules: number
+  declarations: number
 }
+export interface FileInput {
+  code: Buffer
+  /** Overrides `filename` of the shared options. */
+  filename?: string
+}
+export interface FileOutput {
+  filename?: string
+  output?: TransformOutput
+  /** The message of the error, if this file failed. */
+  error?: string
+}
+/**
+ * `signal` aborts it between phases; a running phase, like parsing or
+ * minifying, is not interrupted.
+ */
 export function minify(code: Buffer, opts: Buffer, signal?: AbortSignal | undefined | null): Promise<TransformOutput>
 export function minifySync(code: Buffer, opts: Buffer): TransformOutput
+/**
+ * `signal` aborts it between phases; a running phase, like parsing or
+ * minifying, is not interrupted.
+ */
 export function transform(code: Buffer, opts: Buffer, signal?: AbortSignal | undefined | null): Promise<TransformOutput>
 export function transformSync(code: Buffer, opts: Buffer): TransformOutput
+/**
+ * `signal` aborts it between phases; a running phase, like parsing or
+ * minifying, is not interrupted.
+ */
+export function format(code: Buffer, opts: Buffer, signal?: AbortSignal | undefined | null): Promise<TransformOutput>
+export function formatSync(code: Buffer, opts: Buffer): TransformOutput
+/**
+ * `signal` aborts it between phases; a running phase, like parsing or
+ * minifying, is not interrupted.
+ */
+export function lintCss(code: Buffer, opts: Buffer, signal?: AbortSignal | undefined | null): Promise<LintOutput>
+export function lintCssSync(code: Buffer, opts: Buffer): LintOutput
+/**
+ * Transforms `files` in parallel, sharing `opts`.
+ *
+ * `signal` aborts it between phases; a running phase, like parsing or
+ * minifying, is not interrupted.
+ */
+export function transformMany(files: Array<FileInput>, opts: Buffer, signal?: AbortSignal | undefined | null): Promise<Array<FileOutput>>
+/**
+ * Minifies `files` in parallel, sharing `opts`.
+ *
+ * `signal` aborts it between phases; a running phase, like parsing or
+ * minifying, is not interrupted.
+ */
+export function minifyMany(files: Array<FileInput>, opts: Buffer, signal?: AbortSignal | undefined | null): Promise<Array<FileOutput>>
+export function createTransformer(opts: Buffer): Transformer
+export function createMinifier(opts: Buffer): Minifier
+export class Transformer {
+  /**
+   * `filename` overrides the one of the options.
+   *
+   * `signal` aborts it between phases; a running phase, like parsing or
+   * minifying, is not interrupted.
+   */
+  run(code: Buffer, filename?: string | undefined | null, signal?: AbortSignal | undefined | null): Promise<TransformOutput>
+  /** `filename` overrides the one of the options. */
+  runSync(code: Buffer, filename?: string | undefined | null): TransformOutput
+}
+export class Minifier {
+  /**
+   * `filename` overrides the one of the options.
+   *
+   * `signal` aborts it between phases; a running phase, like parsing or
+   * minifying, is not interrupted.
+   */
+  run(code: Buffer, filename?: string | undefined | null, signal?: AbortSignal | undefined | null): Promise<TransformOutput>
+  /** `filename` overrides the one of the options. */
+  runSync(code: Buffer, filename?: string | undefined | null): TransformOutput
+}
+/**
+ * Like [minify], with `opts` as a JS object instead of a JSON buffer.
+ *
+ * `signal` aborts it between phases; a running phase, like parsing or
+ * minifying, is not interrupted.
+ */
+export function minifyObject(code: Buffer, opts: object, signal?: AbortSignal | undefined | null): Promise<TransformOutput>
+export function minifyObjectSync(code: Buffer, opts: object): TransformOutput
+/**
+ * Like [transform], with `opts` as a JS object instead of a JSON buffer,
+ * which may contain `rewriteUrl`.
+ *
+ * `signal` aborts it between phases; a running phase, like parsing or
+ * minifying, is not interrupted.
+ */
+export function transformObject(code: Buffer, opts: object, signal?: AbortSignal | undefined | null): Promise<TransformOutput>
+export function transformObjectSync(code: Buffer, opts: object): TransformOutput
+/**
+ * Like [format], with `opts` as a JS object.
+ *
+ * `signal` aborts it between phases; a running phase, like parsing or
+ * minifying, is not interrupted.
+ */
+export function formatObject(code: Buffer, opts: object, signal?: AbortSignal | undefined | null): Promise<TransformOutput>
+export function formatObjectSync(code: Buffer, opts: object): TransformOutput
+/**
+ * Like [lint_css], with `opts` as a JS object.
+ *
+ * `signal` aborts it between phases; a running phase, like parsing or
+ * minifying, is not interrupted.
+ */
+export function lintCssObject(code: Buffer, opts: object, signal?: AbortSignal | undefined | null): Promise<LintOutput>
+export function lintCssObjectSync(code: Buffer, opts: object): LintOutput
+/** Returns the JSON Schema of the options, keyed by the function using them. */
+export function getOptionsSchema(): any
+/**
+ * Sets where panics are logged: `"stderr"`, which is the default, `"none"`
+ * or the path of a file.
+ */
+export function setPanicSink(sink?: string | undefined | null): void
+export interface TracingOptions {
+  /** Like `RUST_LOG`. Defaults to `info`, which includes the phases. */
+  filter?: string
+  /** `"chrome"`, `"json"` or `"pretty"`. Defaults to `"pretty"`. */
+  format?: string
+  /**
+   * The file to write to. Defaults to stderr, or to `trace-{timestamp}.json`
+   * in the working directory for `"chrome"`.
+   */
+  path?: string
+}
+/**
+ * Installs the subscriber which records the phases. It can be called only
+ * once.
+ */
+export function initTracing(options?: TracingOptions | undefined | null): void
+export interface CacheOptions {
+  /**
+   * The directory of the cached results, which is created if needed. It
+   * can be shared by processes.
+   */
+  dir: string
+  /**
+   * The size of the cached results in bytes, over which the least recently
+   * used ones are removed. Defaults to 512 MiB.
+   */
+  maxSize?: number
+}
+/**
+ * Caches the results of `transform` and `minify` in `options.dir`. Calling it
+ * again replaces the options.
+ */
+export function enableCache(options: CacheOptions): void
+/** Removes the cached results. The cache stays enabled. */
+export function clearCache(): void
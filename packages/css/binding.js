@@ -257,9 +257,32 @@ if (!nativeBinding) {
   throw new Error(`Failed to load native binding`);
 }
 
-const { minify, minifySync, transform, transformSync } = nativeBinding;
+const { minify, minifySync, transform, transformSync, format, formatSync, lintCss, lintCssSync, transformMany, minifyMany, Transformer, Minifier, createTransformer, createMinifier, minifyObject, minifyObjectSync, transformObject, transformObjectSync, formatObject, formatObjectSync, lintCssObject, lintCssObjectSync, getOptionsSchema, setPanicSink, initTracing, enableCache, clearCache } = nativeBinding;
 
 module.exports.minify = minify;
 module.exports.minifySync = minifySync;
 module.exports.transform = transform;
 module.exports.transformSync = transformSync;
+module.exports.format = format;
+module.exports.formatSync = formatSync;
+module.exports.lintCss = lintCss;
+module.exports.lintCssSync = lintCssSync;
+module.exports.transformMany = transformMany;
+module.exports.minifyMany = minifyMany;
+module.exports.Transformer = Transformer;
+module.exports.Minifier = Minifier;
+module.exports.createTransformer = createTransformer;
+module.exports.createMinifier = createMinifier;
+module.exports.minifyObject = minifyObject;
+module.exports.minifyObjectSync = minifyObjectSync;
+module.exports.transformObject = transformObject;
+module.exports.transformObjectSync = transformObjectSync;
+module.exports.formatObject = formatObject;
+module.exports.formatObjectSync = formatObjectSync;
+module.exports.lintCssObject = lintCssObject;
+module.exports.lintCssObjectSync = lintCssObjectSync;
+module.exports.getOptionsSchema = getOptionsSchema;
+module.exports.setPanicSink = setPanicSink;
+module.exports.initTracing = initTracing;
+module.exports.enableCache = enableCache;
+module.exports.clearCache = clearCache;
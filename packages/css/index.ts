@@ -1,51 +1,203 @@
 import * as binding from "./binding";
+import type {
+  CssModulesConfig,
+  FormatOptions,
+  LintOptions,
+  MinifyOptions,
+  TransformOptions as SchemaTransformOptions,
+} from "./options";
 
-export type MinifyOptions = {
-  filename?: string;
-  sourceMap?: boolean;
-};
-
-export type TransformOptions = {
-  filename?: string;
-
-  sourceMap?: boolean
-
-  cssModules?: CssModuleTransformOptions
-
-  minify?: boolean
+export * from "./options";
 
+export type TransformOptions = SchemaTransformOptions & {
   /**
-   * If true, swc will analyze dependencies of css files.
+   * Replaces the URLs of `url()` and `@import`. `deps` contains the URLs
+   * before they are rewritten.
+   *
+   * Only supported by `transform` and `transformSync`.
    */
-  analyzeDependencies?: boolean
-}
+  rewriteUrl?: (url: string) => string;
+};
 
-export type CssModuleTransformOptions = {
-  pattern: String,
-}
+/** @deprecated Use `CssModulesConfig`. */
+export type CssModuleTransformOptions = CssModulesConfig;
+
+export type LintRuleLevel = "off" | "warning" | "error";
 
 export async function minify(
   content: Buffer,
   options: MinifyOptions
 ): Promise<binding.TransformOutput> {
-  return binding.minify(content, toBuffer(options ?? {}));
+  return binding.minifyObject(content, options ?? {});
 }
 
 export function minifySync(content: Buffer, options: MinifyOptions) {
-  return binding.minifySync(content, toBuffer(options ?? {}));
+  return binding.minifyObjectSync(content, options ?? {});
 }
 
 export async function transform(
   content: Buffer,
   options: TransformOptions
 ): Promise<binding.TransformOutput> {
-  return binding.transform(content, toBuffer(options ?? {}));
+  return binding.transformObject(content, options ?? {});
 }
 
 export function transformSync(content: Buffer, options: TransformOptions) {
-  return binding.transformSync(content, toBuffer(options ?? {}));
+  return binding.transformObjectSync(content, options ?? {});
+}
+
+export async function format(
+  content: Buffer,
+  options: FormatOptions
+): Promise<binding.TransformOutput> {
+  return binding.formatObject(content, options ?? {});
+}
+
+export function formatSync(content: Buffer, options: FormatOptions) {
+  return binding.formatObjectSync(content, options ?? {});
+}
+
+export async function lintCss(
+  content: Buffer,
+  options: LintOptions
+): Promise<binding.LintOutput> {
+  return binding.lintCssObject(content, options ?? {});
+}
+
+export function lintCssSync(content: Buffer, options: LintOptions) {
+  return binding.lintCssObjectSync(content, options ?? {});
+}
+
+export async function transformMany(
+  files: binding.FileInput[],
+  options: TransformOptions
+): Promise<binding.FileOutput[]> {
+  return binding.transformMany(files, toBuffer(options ?? {}));
+}
+
+export async function minifyMany(
+  files: binding.FileInput[],
+  options: MinifyOptions
+): Promise<binding.FileOutput[]> {
+  return binding.minifyMany(files, toBuffer(options ?? {}));
+}
+
+/**
+ * Validates `options` once and returns a transformer which can be reused for
+ * many inputs.
+ */
+export function createTransformer(
+  options: TransformOptions
+): binding.Transformer {
+  return binding.createTransformer(toBuffer(options ?? {}));
+}
+
+/**
+ * Returns a minifier which can be reused for many inputs.
+ */
+export function createMinifier(options: MinifyOptions): binding.Minifier {
+  return binding.createMinifier(toBuffer(options ?? {}));
+}
+
+/**
+ * Returns the JSON Schema of the options, keyed by the function using them.
+ */
+export function getOptionsSchema(): Record<string, object> {
+  return binding.getOptionsSchema();
+}
+
+/**
+ * The error thrown for an internal panic, with `code` set to
+ * `"SWC_INTERNAL_PANIC"`.
+ */
+export type InternalPanicError = Error & {
+  code: "SWC_INTERNAL_PANIC";
+  /** Like `src/lib.rs:10:5`. */
+  location?: string;
+  /** Captured if `RUST_BACKTRACE=1` or `SWC_DEBUG=1` is set. */
+  backtrace?: string;
+  /** The input being processed, if it has a filename. */
+  filename?: string;
+};
+
+/**
+ * The error thrown for an exceeded limit of `limits`.
+ */
+export type LimitError = Error & {
+  code:
+    | "SWC_LIMIT_INPUT_BYTES"
+    | "SWC_LIMIT_DEPTH"
+    | "SWC_LIMIT_NODES"
+    | "SWC_LIMIT_TIMEOUT";
+};
+
+/**
+ * Sets where internal panics are logged: `"stderr"`, which is the default,
+ * `"none"` or the path of a file to append to.
+ */
+export function setPanicSink(sink?: "stderr" | "none" | string) {
+  binding.setPanicSink(sink);
+}
+
+/**
+ * Caches the results of `transform` and `minify` on disk, keyed by the input, the
+ * options and the version, so unchanged inputs are not processed again.
+ * Errors are not cached, nor results with `stats`.
+ */
+export function enableCache(options: {
+  /** The directory of the cached results, which can be shared by processes. */
+  dir: string;
+  /**
+   * The size of the cached results in bytes, over which the least recently
+   * used ones are removed. Defaults to 512 MiB.
+   */
+  maxSize?: number;
+}) {
+  binding.enableCache(options);
+}
+
+/**
+ * Removes the cached results. The cache stays enabled.
+ */
+export function clearCache() {
+  binding.clearCache();
+}
+
+/**
+ * Records the phases of each function, like `parse` and `codegen`, as spans.
+ * It can be called only once.
+ */
+export function initTracing(options?: {
+  /** Like `RUST_LOG`. Defaults to `info`, which includes the phases. */
+  filter?: string;
+  /**
+   * `chrome` writes a trace for `chrome://tracing` or Perfetto. Defaults to
+   * `pretty`.
+   */
+  format?: "chrome" | "json" | "pretty";
+  /**
+   * The file to write to. Defaults to stderr, or to `trace-{timestamp}.json`
+   * in the working directory for `chrome`.
+   */
+  path?: string;
+}) {
+  binding.initTracing(options);
 }
 
 function toBuffer(t: any): Buffer {
-  return Buffer.from(JSON.stringify(t));
+  return Buffer.from(JSON.stringify(t, rejectRegExp));
+}
+
+/**
+ * Rejects a `RegExp` like the functions taking the options as an object, as
+ * the patterns use the syntax of the Rust `regex` crate.
+ */
+function rejectRegExp(key: string, value: any): any {
+  if (value instanceof RegExp) {
+    throw new TypeError(
+      `option \`${key}\` is a RegExp, which is not supported: pass its source as a string, in the syntax of the Rust \`regex\` crate`
+    );
+  }
+
+  return value;
 }
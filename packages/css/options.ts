@@ -0,0 +1,304 @@
+// Generated from the JSON Schema of the options by the tests of the core
+// crate. Run them with `UPDATE_OPTIONS_TS=1` to update it.
+
+export type MinifyOptions = {
+  /**
+   * Escape all non-ASCII characters. Cannot be used with `sourceMap`.
+   */
+  asciiOnly?: boolean;
+  /**
+   * Return `codeFrame` in the diagnostics. Defaults to true, but rendering them
+   * is slow for many diagnostics.
+   */
+  codeFrame?: boolean;
+  /**
+   * The encoding of the input, if it has no byte order mark, like
+   * `windows-1252`. Defaults to the one of `@charset`, or UTF-8. Invalid bytes
+   * are replaced and reported as a `lossy-decoding` warning in `errors`.
+   */
+  encoding?: string | null;
+  filename?: string | null;
+  /**
+   * Fail on untrusted input which is too large or too deep.
+   */
+  limits?: Limits;
+  /**
+   * The maximum number of recoverable errors to report.
+   */
+  maxErrors?: number | null;
+  /**
+   * Characters which cannot be encoded with this encoding are escaped. The
+   * output is still returned as a string. Cannot be used with `sourceMap`.
+   */
+  outputEncoding?: string | null;
+  purge?: PurgeOptions | null;
+  /**
+   * Return the input as-is with the errors instead of throwing, if the input
+   * cannot be parsed. The recoverable errors are limited by `maxErrors`.
+   */
+  recover?: boolean;
+  sourceMap?: boolean;
+  /**
+   * Return `stats` in the output, with the size of the input and the output,
+   * the duration of each phase and the number of nodes before and after.
+   */
+  stats?: boolean;
+  /**
+   * Fail if the parser reports any recoverable error.
+   */
+  strict?: boolean;
+  /**
+   * Whether unknown keys in these options fail, or are reported in `warnings`
+   * of the output. Defaults to `error`.
+   */
+  unknownOptions?: "error" | "warn";
+};
+
+export type TransformOptions = {
+  /**
+   * Return the URLs of `@import` and `url()` in `deps` of the output.
+   */
+  analyzeDependencies?: boolean;
+  /**
+   * Escape all non-ASCII characters. Cannot be used with `sourceMap`.
+   */
+  asciiOnly?: boolean;
+  /**
+   * Return `codeFrame` in the diagnostics. Defaults to true, but rendering them
+   * is slow for many diagnostics.
+   */
+  codeFrame?: boolean;
+  /**
+   * Used only if `minify` is false, except for `preserveComments`.
+   */
+  codegen?: CodegenOptions;
+  cssModules?: CssModulesConfig | null;
+  /**
+   * Statically resolve custom properties declared on `:root`. Usages which
+   * cannot be resolved, like cycles or values longer than 64 KiB once resolved,
+   * are reported as warnings in `errors`.
+   */
+  customProperties?: CustomPropertiesOptions | null;
+  /**
+   * The encoding of the input, if it has no byte order mark, like
+   * `windows-1252`. Defaults to the one of `@charset`, or UTF-8. Invalid bytes
+   * are replaced and reported as a `lossy-decoding` warning in `errors`.
+   */
+  encoding?: string | null;
+  filename?: string | null;
+  /**
+   * Fail on untrusted input which is too large or too deep.
+   */
+  limits?: Limits;
+  /**
+   * The maximum number of recoverable errors to report.
+   */
+  maxErrors?: number | null;
+  minify?: boolean;
+  /**
+   * Characters which cannot be encoded with this encoding are escaped. The
+   * output is still returned as a string. Cannot be used with `sourceMap`.
+   */
+  outputEncoding?: string | null;
+  /**
+   * Add vendor prefixes required by `targets` and remove the ones which are not
+   * required anymore.
+   */
+  prefix?: boolean;
+  /**
+   * Remove rules which cannot match the used names.
+   */
+  purge?: PurgeOptions | null;
+  /**
+   * Return the input as-is with the errors instead of throwing, if the input
+   * cannot be parsed. The recoverable errors are limited by `maxErrors`.
+   */
+  recover?: boolean;
+  sourceMap?: boolean;
+  /**
+   * Return `stats` in the output, with the size of the input and the output,
+   * the duration of each phase and the number of nodes before and after.
+   */
+  stats?: boolean;
+  /**
+   * Fail if the parser reports any recoverable error.
+   */
+  strict?: boolean;
+  /**
+   * Browser targets, in the format of browserslist. The syntax they do not
+   * support is lowered, or all of it without targets.
+   */
+  targets?: string | string[] | Record<string, string>;
+  /**
+   * Whether unknown keys in these options fail, or are reported in `warnings`
+   * of the output. Defaults to `error`.
+   */
+  unknownOptions?: "error" | "warn";
+};
+
+export type FormatOptions = {
+  /**
+   * Escape all non-ASCII characters. Cannot be used with `sourceMap`.
+   */
+  asciiOnly?: boolean;
+  /**
+   * Return `codeFrame` in the diagnostics. Defaults to true, but rendering them
+   * is slow for many diagnostics.
+   */
+  codeFrame?: boolean;
+  /**
+   * The encoding of the input, if it has no byte order mark, like
+   * `windows-1252`. Defaults to the one of `@charset`, or UTF-8. Invalid bytes
+   * are replaced and reported as a `lossy-decoding` warning in `errors`.
+   */
+  encoding?: string | null;
+  filename?: string | null;
+  indentType?: IndentKind | null;
+  indentWidth?: number | null;
+  /**
+   * Fail on untrusted input which is too large or too deep.
+   */
+  limits?: Limits;
+  lineFeed?: LineFeedKind | null;
+  /**
+   * Characters which cannot be encoded with this encoding are escaped. The
+   * output is still returned as a string. Cannot be used with `sourceMap`.
+   */
+  outputEncoding?: string | null;
+  /**
+   * Keep comments, which are printed before the token following them.
+   */
+  preserveComments?: boolean;
+  sourceMap?: boolean;
+  /**
+   * Return `stats` in the output, with the size of the input and the output,
+   * the duration of each phase and the number of nodes before and after.
+   */
+  stats?: boolean;
+  /**
+   * Whether unknown keys in these options fail, or are reported in `warnings`
+   * of the output. Defaults to `error`.
+   */
+  unknownOptions?: "error" | "warn";
+};
+
+export type LintOptions = {
+  /**
+   * Return `codeFrame` in the diagnostics. Defaults to true, but rendering them
+   * is slow for many diagnostics.
+   */
+  codeFrame?: boolean;
+  /**
+   * The encoding of the input, if it has no byte order mark. Defaults to the
+   * one of `@charset`, or UTF-8.
+   */
+  encoding?: string | null;
+  filename?: string | null;
+  /**
+   * Fail on untrusted input which is too large or too deep.
+   */
+  limits?: Limits;
+  /**
+   * Maps a rule id like `block-no-empty` to its level, optionally followed by
+   * the options of the rule.
+   */
+  rules?: Record<string, "off" | "warning" | "error" | ["off" | "warning" | "error", unknown?]>;
+  /**
+   * Whether unknown keys in these options fail, or are reported in `warnings`
+   * of the output. Defaults to `error`.
+   */
+  unknownOptions?: "error" | "warn";
+};
+
+export type CodegenOptions = {
+  /**
+   * Defaults to `space`.
+   */
+  indentType?: IndentKind | null;
+  /**
+   * Between 0 and 16. Defaults to 2 for `space` and 1 for `tab`.
+   */
+  indentWidth?: number | null;
+  /**
+   * Defaults to `lf`.
+   */
+  lineFeed?: LineFeedKind | null;
+  /**
+   * Keep comments, including the ones nested in rules and values. A comment is
+   * printed before the token following it.
+   */
+  preserveComments?: boolean;
+};
+
+export type CssModulesConfig = {
+  pattern: string;
+};
+
+export type CustomPropertiesOptions = {
+  /**
+   * Keep the declarations using `var()` after the static fallback. If false,
+   * they are replaced. Defaults to true.
+   */
+  preserve?: boolean;
+};
+
+export type IndentKind = "tab" | "space";
+
+export type Limits = {
+  /**
+   * The maximum nesting of blocks, functions and brackets. It's checked before
+   * parsing, as the parser recurses into them.
+   */
+  maxDepth?: number | null;
+  /**
+   * The maximum size of the input, in bytes.
+   */
+  maxInputBytes?: number | null;
+  /**
+   * The maximum number of rules, declarations and component values of the
+   * parsed stylesheet.
+   */
+  maxNodes?: number | null;
+  /**
+   * Milliseconds after which processing fails.
+   *
+   * It's checked while parsing and walking the stylesheet, but the minifier of
+   * swc runs to its end once started, so a call can take longer.
+   * `maxInputBytes`, `maxDepth` and `maxNodes` bound its work.
+   */
+  timeoutMs?: number | null;
+};
+
+export type LineFeedKind = "lf" | "crlf";
+
+export type PurgeOptions = {
+  /**
+   * A category which is not specified is checked only against `content`, and is
+   * not checked at all if `content` is empty.
+   */
+  attributes?: string[] | null;
+  /**
+   * A category which is not specified is checked only against `content`, and is
+   * not checked at all if `content` is empty.
+   */
+  classes?: string[] | null;
+  /**
+   * Raw HTML or JS sources to scan for used names.
+   */
+  content?: string[];
+  /**
+   * A category which is not specified is checked only against `content`, and is
+   * not checked at all if `content` is empty.
+   */
+  ids?: string[] | null;
+  /**
+   * Regular expressions. Selectors, `@keyframes` and `@font-face` rules with a
+   * name matching one of these are always kept.
+   */
+  safelist?: string[];
+  /**
+   * A category which is not specified is checked only against `content`, and is
+   * not checked at all if `content` is empty.
+   */
+  tags?: string[] | null;
+};
@@ -0,0 +1,505 @@
+/* tslint:disable */
+/* eslint-disable */
+
+/* auto-generated by NAPI-RS */
+
+export interface DiagnosticSpan {
+  filename: string
+  /** Byte offset from the start of the file. */
+  start: number
+  /** Byte offset from the start of the file. */
+  end: number
+  /** 1-based. */
+  startLine: number
+  /** 1-based, in characters. */
+  startColumn: number
+  /** 1-based. */
+  endLine: number
+  /** 1-based, in characters. */
+  endColumn: number
+}
+/** A diagnostic of the css and html tools, which can recover from errors. */
+export interface Diagnostic {
+  level: string
+  message: string
+  /** The id of the lint rule or the error code, if any. */
+  code?: string
+  span?: DiagnosticSpan
+  /** The source code around `span`, rendered like thrown errors. */
+  codeFrame?: string
+  /** True if this error prevented the input from being processed. */
+  fatal: boolean
+}
+export namespace css {
+  export interface Stats {
+    /** Bytes of the input. */
+    inputSize: number
+    /** Bytes of `code`. */
+    outputSize: number
+    /** Milliseconds spent in each phase. A phase which did not run is `0`. */
+    durations: Durations
+    /** Counted after parsing. */
+    before: Counts
+    /**
+     * Counted before generating the code, so the difference with `before`
+     * is what was removed or merged.
+     */
+    after: Counts
+  }
+  export interface Durations {
+    parse: number
+    /**
+     * Everything between parsing and minifying, like CSS Modules and
+     * prefixing.
+     */
+    transforms: number
+    minify: number
+    codegen: number
+    sourceMap: number
+  }
+  export interface Counts {
+    /** Qualified rules and at-rules, including nested ones. */
+    rules: number
+    declarations: number
+  }
+  export interface TransformOutput {
+    /** The input as-is if it could not be parsed with `recover`. */
+    code: string
+    map?: string
+    errors?: Array<Diagnostic>
+    /** Unknown keys of the options, if `unknownOptions` is `"warn"`. */
+    warnings?: Array<string>
+    /** JSON string. */
+    deps?: string
+    /** JSON string. */
+    modulesMapping?: string
+    /** If `stats` is requested. */
+    stats?: Stats
+  }
+  export interface LintOutput {
+    diagnostics: Array<Diagnostic>
+    /** Recoverable errors of the parser. */
+    errors?: Array<Diagnostic>
+    /** Unknown keys of the options, if `unknownOptions` is `"warn"`. */
+    warnings?: Array<string>
+  }
+  export interface FileInput {
+    code: Buffer
+    /** Overrides `filename` of the shared options. */
+    filename?: string
+  }
+  export interface FileOutput {
+    filename?: string
+    output?: TransformOutput
+    /** The message of the error, if this file failed. */
+    error?: string
+  }
+  export interface CacheOptions {
+    /**
+     * The directory of the cached results, which is created if needed. It
+     * can be shared by processes.
+     */
+    dir: string
+    /**
+     * The size of the cached results in bytes, over which the least recently
+     * used ones are removed. Defaults to 512 MiB.
+     */
+    maxSize?: number
+  }
+  /**
+   * Caches the results of `transform` and `minify` in `options.dir`. Calling it
+   * again replaces the options.
+   */
+  export function enableCache(options: CacheOptions): void
+  /** Removes the cached results. The cache stays enabled. */
+  export function clearCache(): void
+  export class Transformer {
+    /**
+     * `filename` overrides the one of the options.
+     *
+     * `signal` aborts it while parsing, between phases and while walking
+     * the tree; a running minifier or lint rule of swc is not interrupted.
+     */
+    run(code: Buffer, filename?: string | undefined | null, signal?: AbortSignal | undefined | null): Promise<unknown>
+    /** `filename` overrides the one of the options. */
+    runSync(code: Buffer, filename?: string | undefined | null): TransformOutput
+  }
+  export class Minifier {
+    /**
+     * `filename` overrides the one of the options.
+     *
+     * `signal` aborts it while parsing, between phases and while walking
+     * the tree; a running minifier or lint rule of swc is not interrupted.
+     */
+    run(code: Buffer, filename?: string | undefined | null, signal?: AbortSignal | undefined | null): Promise<unknown>
+    /** `filename` overrides the one of the options. */
+    runSync(code: Buffer, filename?: string | undefined | null): TransformOutput
+  }
+  export function createTransformer(opts: Buffer): Transformer
+  export function createMinifier(opts: Buffer): Minifier
+  /**
+   * Sets where panics are logged: `"stderr"`, which is the default, `"none"`
+   * or the path of a file.
+   */
+  export function setPanicSink(sink?: string | undefined | null): void
+  export interface TracingOptions {
+    /** Like `RUST_LOG`. Defaults to `info`, which includes the phases. */
+    filter?: string
+    /** `"chrome"`, `"json"` or `"pretty"`. Defaults to `"pretty"`. */
+    format?: string
+    /**
+     * The file to write to. Defaults to stderr, or to `trace-{timestamp}.json`
+     * in the working directory for `"chrome"`.
+     */
+    path?: string
+  }
+  /**
+   * Installs the subscriber which records the phases. It can be called only
+   * once.
+   */
+  export function initTracing(options?: TracingOptions | undefined | null): void
+  /**
+   * `signal` aborts it while parsing, between phases and while walking
+   * the tree; a running minifier or lint rule of swc is not interrupted.
+   */
+  export function minify(code: Buffer, opts: Buffer, signal?: AbortSignal | undefined | null): Promise<unknown>
+  export function minifySync(code: Buffer, opts: Buffer): TransformOutput
+  /**
+   * `signal` aborts it while parsing, between phases and while walking
+   * the tree; a running minifier or lint rule of swc is not interrupted.
+   */
+  export function transform(code: Buffer, opts: Buffer, signal?: AbortSignal | undefined | null): Promise<unknown>
+  export function transformSync(code: Buffer, opts: Buffer): TransformOutput
+  /**
+   * Transforms `files` in parallel, sharing `opts`.
+   *
+   * `signal` aborts it while parsing, between phases and while walking
+   * the tree; a running minifier or lint rule of swc is not interrupted.
+   */
+  export function transformMany(files: Array<FileInput>, opts: Buffer, signal?: AbortSignal | undefined | null): Promise<unknown>
+  /**
+   * Minifies `files` in parallel, sharing `opts`.
+   *
+   * `signal` aborts it while parsing, between phases and while walking
+   * the tree; a running minifier or lint rule of swc is not interrupted.
+   */
+  export function minifyMany(files: Array<FileInput>, opts: Buffer, signal?: AbortSignal | undefined | null): Promise<unknown>
+  /**
+   * `signal` aborts it while parsing, between phases and while walking
+   * the tree; a running minifier or lint rule of swc is not interrupted.
+   */
+  export function format(code: Buffer, opts: Buffer, signal?: AbortSignal | undefined | null): Promise<unknown>
+  export function formatSync(code: Buffer, opts: Buffer): TransformOutput
+  /**
+   * `signal` aborts it while parsing, between phases and while walking
+   * the tree; a running minifier or lint rule of swc is not interrupted.
+   */
+  export function lintCss(code: Buffer, opts: Buffer, signal?: AbortSignal | undefined | null): Promise<unknown>
+  export function lintCssSync(code: Buffer, opts: Buffer): LintOutput
+  /**
+   * Like [minify], with `opts` as a JS object instead of a JSON buffer.
+   *
+   * `signal` aborts it while parsing, between phases and while walking
+   * the tree; a running minifier or lint rule of swc is not interrupted.
+   */
+  export function minifyObject(code: Buffer, opts: object, signal?: AbortSignal | undefined | null): Promise<unknown>
+  export function minifyObjectSync(code: Buffer, opts: object): TransformOutput
+  /**
+   * Like [transform], with `opts` as a JS object instead of a JSON buffer,
+   * which may contain `rewriteUrl`.
+   *
+   * `signal` aborts it while parsing, between phases and while walking
+   * the tree; a running minifier or lint rule of swc is not interrupted.
+   */
+  export function transformObject(code: Buffer, opts: object, signal?: AbortSignal | undefined | null): Promise<unknown>
+  export function transformObjectSync(code: Buffer, opts: object): TransformOutput
+  /**
+   * Like [format], with `opts` as a JS object.
+   *
+   * `signal` aborts it while parsing, between phases and while walking
+   * the tree; a running minifier or lint rule of swc is not interrupted.
+   */
+  export function formatObject(code: Buffer, opts: object, signal?: AbortSignal | undefined | null): Promise<unknown>
+  export function formatObjectSync(code: Buffer, opts: object): TransformOutput
+  /**
+   * Like [lint_css], with `opts` as a JS object.
+   *
+   * `signal` aborts it while parsing, between phases and while walking
+   * the tree; a running minifier or lint rule of swc is not interrupted.
+   */
+  export function lintCssObject(code: Buffer, opts: object, signal?: AbortSignal | undefined | null): Promise<unknown>
+  export function lintCssObjectSync(code: Buffer, opts: object): LintOutput
+  /** Returns the JSON Schema of the options, keyed by the function using them. */
+  export function getOptionsSchema(): any
+}
+export namespace html {
+  export interface Stats {
+    /** Bytes of the input. */
+    inputSize: number
+    /** Bytes of `code`. */
+    outputSize: number
+    /** Milliseconds spent in each phase. */
+    durations: Durations
+    /** Counted after parsing. */
+    before: Counts
+    /**
+     * Counted after minifying, so the difference with `before` is what was
+     * removed or merged.
+     */
+    after: Counts
+  }
+  export interface Durations {
+    parse: number
+    /** Includes minifying the CSS and JS of the document. */
+    minify: number
+    codegen: number
+  }
+  export interface Counts {
+    elements: number
+    attributes: number
+    textNodes: number
+    comments: number
+  }
+  export interface TransformOutput {
+    /** The input as-is if it could not be parsed with `recover`. */
+    code: string
+    errors?: Array<Diagnostic>
+    /** Unknown keys of the options, if `unknownOptions` is `"warn"`. */
+    warnings?: Array<string>
+    /** If `stats` is requested. */
+    stats?: Stats
+  }
+  export interface Attribute {
+    namespace?: string
+    prefix?: string
+    name: string
+    value?: string
+  }
+  /** The context element or the form element of [minify_fragment]. */
+  export interface Element {
+    tagName: string
+    namespace: string
+    attributes: Array<Attribute>
+    isSelfClosing: boolean
+  }
+  export interface FileInput {
+    code: Buffer
+    /** Overrides `filename` of the shared options. */
+    filename?: string
+  }
+  export interface FileOutput {
+    filename?: string
+    output?: TransformOutput
+    /** The message of the error, if this file failed. */
+    error?: string
+  }
+  export interface CacheOptions {
+    /**
+     * The directory of the cached results, which is created if needed. It
+     * can be shared by processes.
+     */
+    dir: string
+    /**
+     * The size of the cached results in bytes, over which the least recently
+     * used ones are removed. Defaults to 512 MiB.
+     */
+    maxSize?: number
+  }
+  /**
+   * Caches the results of `minify` and `minifyFragment` in `options.dir`.
+   * Calling it again replaces the options.
+   */
+  export function enableCache(options: CacheOptions): void
+  /** Removes the cached results. The cache stays enabled. */
+  export function clearCache(): void
+  /**
+   * Named apart from the `Minifier` of `css_node`, as napi looks classes up by
+   * name when both are linked into `extra_node`.
+   */
+  export class HtmlMinifier {
+    /**
+     * Minifies `code` as a document. `filename` overrides the one of the
+     * options.
+     *
+     * `signal` aborts it while parsing, between phases and while walking
+     * the tree; the minifier of swc is not interrupted once started.
+     */
+    run(code: Buffer, filename?: string | undefined | null, signal?: AbortSignal | undefined | null): Promise<unknown>
+    /**
+     * Minifies `code` as a document fragment. `filename` overrides the one
+     * of the options.
+     *
+     * `signal` aborts it while parsing, between phases and while walking
+     * the tree; the minifier of swc is not interrupted once started.
+     */
+    runFragment(code: Buffer, filename?: string | undefined | null, signal?: AbortSignal | undefined | null): Promise<unknown>
+    runSync(code: Buffer, filename?: string | undefined | null): TransformOutput
+    runFragmentSync(code: Buffer, filename?: string | undefined | null): TransformOutput
+  }
+  export function createMinifier(opts: Buffer): HtmlMinifier
+  /**
+   * Sets where panics are logged: `"stderr"`, which is the default, `"none"`
+   * or the path of a file.
+   */
+  export function setPanicSink(sink?: string | undefined | null): void
+  export interface TracingOptions {
+    /** Like `RUST_LOG`. Defaults to `info`, which includes the phases. */
+    filter?: string
+    /** `"chrome"`, `"json"` or `"pretty"`. Defaults to `"pretty"`. */
+    format?: string
+    /**
+     * The file to write to. Defaults to stderr, or to `trace-{timestamp}.json`
+     * in the working directory for `"chrome"`.
+     */
+    path?: string
+  }
+  /**
+   * Installs the subscriber which records the phases. It can be called only
+   * once.
+   */
+  export function initTracing(options?: TracingOptions | undefined | null): void
+  /**
+   * `signal` aborts it while parsing, between phases and while walking
+   * the tree; the minifier of swc is not interrupted once started.
+   */
+  export function minify(code: Buffer, opts: Buffer, signal?: AbortSignal | undefined | null): Promise<unknown>
+  /**
+   * `signal` aborts it while parsing, between phases and while walking
+   * the tree; the minifier of swc is not interrupted once started.
+   */
+  export function minifyFragment(code: Buffer, opts: Buffer, signal?: AbortSignal | undefined | null): Promise<unknown>
+  /**
+   * Minifies `files` as documents in parallel, sharing `opts`.
+   *
+   * `signal` aborts it while parsing, between phases and while walking
+   * the tree; the minifier of swc is not interrupted once started.
+   */
+  export function minifyMany(files: Array<FileInput>, opts: Buffer, signal?: AbortSignal | undefined | null): Promise<unknown>
+  export function minifySync(code: Buffer, opts: Buffer): TransformOutput
+  export function minifyFragmentSync(code: Buffer, opts: Buffer): TransformOutput
+  /**
+   * Like [minify], with `opts` as a JS object instead of a JSON buffer.
+   *
+   * `signal` aborts it while parsing, between phases and while walking
+   * the tree; the minifier of swc is not interrupted once started.
+   */
+  export function minifyObject(code: Buffer, opts: object, signal?: AbortSignal | undefined | null): Promise<unknown>
+  /**
+   * Like [minify_fragment], with `opts` as a JS object instead of a
+   * JSON buffer.
+   *
+   * `signal` aborts it while parsing, between phases and while walking
+   * the tree; the minifier of swc is not interrupted once started.
+   */
+  export function minifyFragmentObject(code: Buffer, opts: object, signal?: AbortSignal | undefined | null): Promise<unknown>
+  export function minifyObjectSync(code: Buffer, opts: object): TransformOutput
+  export function minifyFragmentObjectSync(code: Buffer, opts: object): TransformOutput
+  /** Returns the JSON Schema of the options, keyed by the function using them. */
+  export function getOptionsSchema(): any
+}
+export namespace linter {
+  /**
+   * A diagnostic of the linter, which has no `fatal` as it cannot recover from
+   * errors.
+   */
+  export interface Diagnostic {
+    level: string
+    message: string
+    /** The id of the lint rule or the error code, if any. */
+    code?: string
+    span?: DiagnosticSpan
+    /** The source code around `span`, rendered like thrown errors. */
+    codeFrame?: string
+  }
+  export interface TransformOutput {
+    errors?: Array<Diagnostic>
+    /** Unknown keys of the options, if `unknownOptions` is `"warn"`. */
+    warnings?: Array<string>
+  }
+  export interface FileInput {
+    code: Buffer
+    /** Overrides `filename` of the shared options. */
+    filename?: string
+  }
+  export interface FileOutput {
+    filename?: string
+    output?: TransformOutput
+    /** The message of the error, if this file failed. */
+    error?: string
+  }
+  export interface CacheOptions {
+    /**
+     * The directory of the cached results, which is created if needed. It
+     * can be shared by processes.
+     */
+    dir: string
+    /**
+     * The size of the cached results in bytes, over which the least recently
+     * used ones are removed. Defaults to 512 MiB.
+     */
+    maxSize?: number
+  }
+  /**
+   * Caches the results of `lint` in `options.dir`. Calling it again replaces
+   * the options.
+   */
+  export function enableCache(options: CacheOptions): void
+  /** Removes the cached results. The cache stays enabled. */
+  export function clearCache(): void
+  export class Linter {
+    /**
+     * `filename` overrides the one of the options.
+     *
+     * `signal` aborts it while parsing, between phases and before each lint
+     * rule; a running lint rule is not interrupted.
+     */
+    run(code: Buffer, filename?: string | undefined | null, signal?: AbortSignal | undefined | null): Promise<unknown>
+    /** `filename` overrides the one of the options. */
+    runSync(code: Buffer, filename?: string | undefined | null): TransformOutput
+  }
+  export function createLinter(opts: Buffer): Linter
+  /**
+   * Sets where panics are logged: `"stderr"`, which is the default, `"none"`
+   * or the path of a file.
+   */
+  export function setPanicSink(sink?: string | undefined | null): void
+  export interface TracingOptions {
+    /** Like `RUST_LOG`. Defaults to `info`, which includes the phases. */
+    filter?: string
+    /** `"chrome"`, `"json"` or `"pretty"`. Defaults to `"pretty"`. */
+    format?: string
+    /**
+     * The file to write to. Defaults to stderr, or to `trace-{timestamp}.json`
+     * in the working directory for `"chrome"`.
+     */
+    path?: string
+  }
+  /**
+   * Installs the subscriber which records the phases. It can be called only
+   * once.
+   */
+  export function initTracing(options?: TracingOptions | undefined | null): void
+  /**
+   * `signal` aborts it while parsing, between phases and before each lint
+   * rule; a running lint rule is not interrupted.
+   */
+  export function lint(code: Buffer, opts: Buffer, signal?: AbortSignal | undefined | null): Promise<unknown>
+  /**
+   * Lints `files` in parallel, sharing `opts`.
+   *
+   * `signal` aborts it while parsing, between phases and before each lint
+   * rule; a running lint rule is not interrupted.
+   */
+  export function lintMany(files: Array<FileInput>, opts: Buffer, signal?: AbortSignal | undefined | null): Promise<unknown>
+  export function lintSync(code: Buffer, opts: Buffer): TransformOutput
+  /**
+   * Like [lint], with `opts` as a JS object instead of a JSON buffer.
+   *
+   * `signal` aborts it while parsing, between phases and before each lint
+   * rule; a running lint rule is not interrupted.
+   */
+  export function lintObject(code: Buffer, opts: object, signal?: AbortSignal | undefined | null): Promise<unknown>
+  export function lintObjectSync(code: Buffer, opts: object): TransformOutput
+  /** Returns the JSON Schema of the options, keyed by the function using them. */
+  export function getOptionsSchema(): any
+}
@@ -6,11 +6,64 @@
 export interface Diagnostic {
   level: string;
   message: string;
-  span: any;
+  /** The id of the lint rule or the error code, if any. */
+  code?: string;
+  span?: DiagnosticSpan;
+  /** The source code around `span`, rendered like thrown errors. */
+  codeFrame?: string;
+  /** True if this error prevented the input from being processed. */
+  fatal: boolean;
+}
+export interface DiagnosticSpan {
+  filename: string;
+  /** Byte offset from the start of the file. */
+  start: number;
+  /** Byte offset from the start of the file. */
+  end: number;
+  /** 1-based. */
+  startLine: number;
+  /** 1-based, in characters. */
+  startColumn: number;
+  /** 1-based. */
+  endLine: number;
+  /** 1-based, in characters. */
+  endColumn: number;
 }
 export interface TransformOutput {
+  /** The input as-is if it could not be parsed with `recover`. */
   code: string;
   errors?: Array<Diagnostic>;
+  /** Unknown keys of the options, if `unknownOptions` is `"warn"`. */
+  warnings?: Array<string>;
+  /** If `stats` is requested. */
+  stats?: Stats;
+}
+export interface Stats {
+  /** Bytes of the input. */
+  inputSize: number;
+  /** Bytes of `code`. */
+  outputSize: number;
+  /** Milliseconds spent in each phase. */
+  durations: Durations;
+  /** Counted after parsing. */
+  before: Counts;
+  /**
+   * Counted after minifying, so the difference with `before` is what was
+   * removed or merged.
+   */
+  after: Counts;
+}
+export interface Durations {
+  parse: number;
+  /** Includes minifying the CSS and JS of the document. */
+  minify: number;
+  codegen: number;
+}
+export interface Counts {
+  elements: number;
+  attributes: number;
+  textNodes: number;
+  comments: number;
 }
 export interface Attribute {
   namespace?: string;
@@ -24,11 +77,30 @@ export interface Element {
   attributes: Array<Attribute>;
   isSelfClosing: boolean;
 }
+export interface FileInput {
+  code: Buffer;
+  /** Overrides `filename` of the shared options. */
+  filename?: string;
+}
+export interface FileOutput {
+  filename?: string;
+  output?: TransformOutput;
+  /** The message of the error, if this file failed. */
+  error?: string;
+}
+/**
+ * `signal` aborts it between phases; a running phase, like parsing or
+ * minifying, is not interrupted.
+ */
 export function minify(
   code: Buffer,
   opts: Buffer,
   signal?: AbortSignal | undefined | null
 ): Promise<TransformOutput>;
+/**
+ * `signal` aborts it between phases; a running phase, like parsing or
+ * minifying, is not interrupted.
+ */
 export function minifyFragment(
   code: Buffer,
   opts: Buffer,
@@ -39,11 +111,64 @@ export function minifyFragmentSync(code: Buffer, opts: Buffer): TransformOutput;
 export interface Diagnostic {
   level: string;
   message: string;
-  span: any;
+  /** The id of the lint rule or the error code, if any. */
+  code?: string;
+  span?: DiagnosticSpan;
+  /** The source code around `span`, rendered like thrown errors. */
+  codeFrame?: string;
+  /** True if this error prevented the input from being processed. */
+  fatal: boolean;
+}
+export interface DiagnosticSpan {
+  filename: string;
+  /** Byte offset from the start of the file. */
+  start: number;
+  /** Byte offset from the start of the file. */
+  end: number;
+  /** 1-based. */
+  startLine: number;
+  /** 1-based, in characters. */
+  startColumn: number;
+  /** 1-based. */
+  endLine: number;
+  /** 1-based, in characters. */
+  endColumn: number;
 }
 export interface TransformOutput {
+  /** The input as-is if it could not be parsed with `recover`. */
   code: string;
   errors?: Array<Diagnostic>;
+  /** Unknown keys of the options, if `unknownOptions` is `"warn"`. */
+  warnings?: Array<string>;
+  /** If `stats` is requested. */
+  stats?: Stats;
+}
+export interface Stats {
+  /** Bytes of the input. */
+  inputSize: number;
+  /** Bytes of `code`. */
+  outputSize: number;
+  /** Milliseconds spent in each phase. */
+  durations: Durations;
+  /** Counted after parsing. */
+  before: Counts;
+  /**
+   * Counted after minifying, so the difference with `before` is what was
+   * removed or merged.
+   */
+  after: Counts;
+}
+export interface Durations {
+  parse: number;
+  /** Includes minifying the CSS and JS of the document. */
+  minify: number;
+  codegen: number;
+}
+export interface Counts {
+  elements: number;
+  attributes: number;
+  textNodes: number;
+  comments: number;
 }
 export interface Attribute {
   namespace?: string;
@@ -57,11 +182,19 @@ export interface Element {
   attributes: Array<Attribute>;
   isSelfClosing: boolean;
 }
+/**
+ * `signal` aborts it between phases; a running phase, like parsing or
+ * minifying, is not interrupted.
+ */
 export function minify(
   code: Buffer,
   opts: Buffer,
   signal?: AbortSignal | undefined | null
 ): Promise<TransformOutput>;
+/**
+ * `signal` aborts it between phases; a running phase, like parsing or
+ * minifying, is not interrupted.
+ */
 export function minifyFragment(
   code: Buffer,
   opts: Buffer,
@@ -69,3 +202,116 @@ export function minifyFragment(
 ): Promise<TransformOutput>;
 export function minifySync(code: Buffer, opts: Buffer): TransformOutput;
 export function minifyFragmentSync(code: Buffer, opts: Buffer): TransformOutput;
+/**
+ * Minifies `files` as documents in parallel, sharing `opts`.
+ *
+ * `signal` aborts it between phases; a running phase, like parsing or
+ * minifying, is not interrupted.
+ */
+export function minifyMany(
+  files: Array<FileInput>,
+  opts: Buffer,
+  signal?: AbortSignal | undefined | null
+): Promise<Array<FileOutput>>;
+export function createMinifier(opts: Buffer): HtmlMinifier;
+export class HtmlMinifier {
+  /**
+   * Minifies `code` as a document. `filename` overrides the one of the
+   * options.
+   *
+   * `signal` aborts it between phases; a running phase, like parsing or
+   * minifying, is not interrupted.
+   */
+  run(
+    code: Buffer,
+    filename?: string | undefined | null,
+    signal?: AbortSignal | undefined | null
+  ): Promise<TransformOutput>;
+  /**
+   * Minifies `code` as a document fragment. `filename` overrides the one
+   * of the options.
+   *
+   * `signal` aborts it between phases; a running phase, like parsing or
+   * minifying, is not interrupted.
+   */
+  runFragment(
+    code: Buffer,
+    filename?: string | undefined | null,
+    signal?: AbortSignal | undefined | null
+  ): Promise<TransformOutput>;
+  runSync(code: Buffer, filename?: string | undefined | null): TransformOutput;
+  runFragmentSync(
+    code: Buffer,
+    filename?: string | undefined | null
+  ): TransformOutput;
+}
+/**
+ * Like [minify], with `opts` as a JS object instead of a JSON buffer.
+ *
+ * `signal` aborts it between phases; a running phase, like parsing or
+ * minifying, is not interrupted.
+ */
+export function minifyObject(
+  code: Buffer,
+  opts: object,
+  signal?: AbortSignal | undefined | null
+): Promise<TransformOutput>;
+/**
+ * Like [minify_fragment], with `opts` as a JS object instead of a
+ * JSON buffer.
+ *
+ * `signal` aborts it between phases; a running phase, like parsing or
+ * minifying, is not interrupted.
+ */
+export function minifyFragmentObject(
+  code: Buffer,
+  opts: object,
+  signal?: AbortSignal | undefined | null
+): Promise<TransformOutput>;
+export function minifyObjectSync(code: Buffer, opts: object): TransformOutput;
+export function minifyFragmentObjectSync(
+  code: Buffer,
+  opts: object
+): TransformOutput;
+/** Returns the JSON Schema of the options, keyed by the function using them. */
+export function getOptionsSchema(): any;
+/**
+ * Sets where panics are logged: `"stderr"`, which is the default, `"none"`
+ * or the path of a file.
+ */
+export function setPanicSink(sink?: string | undefined | null): void;
+export interface TracingOptions {
+  /** Like `RUST_LOG`. Defaults to `info`, which includes the phases. */
+  filter?: string;
+  /** `"chrome"`, `"json"` or `"pretty"`. Defaults to `"pretty"`. */
+  format?: string;
+  /**
+   * The file to write to. Defaults to stderr, or to `trace-{timestamp}.json`
+   * in the working directory for `"chrome"`.
+   */
+  path?: string;
+}
+/**
+ * Installs the subscriber which records the phases. It can be called only
+ * once.
+ */
+export function initTracing(options?: TracingOptions | undefined | null): void;
+export interface CacheOptions {
+  /**
+   * The directory of the cached results, which is created if needed. It
+   * can be shared by processes.
+   */
+  dir: string;
+  /**
+   * The size of the cached results in bytes, over which the least recently
+   * used ones are removed. Defaults to 512 MiB.
+   */
+  maxSize?: number;
+}
+/**
+ * Caches the results of `minify` and `minifyFragment` in `options.dir`. Calling
+ * it again replaces the options.
+ */
+export function enableCache(options: CacheOptions): void;
+/** Removes the cached results. The cache stays enabled. */
+export function clearCache(): void;
@@ -270,6 +270,18 @@ const {
   minifyFragment,
   minifySync,
   minifyFragmentSync,
+  minifyMany,
+  HtmlMinifier,
+  createMinifier,
+  minifyObject,
+  minifyFragmentObject,
+  minifyObjectSync,
+  minifyFragmentObjectSync,
+  getOptionsSchema,
+  setPanicSink,
+  initTracing,
+  enableCache,
+  clearCache,
 } = nativeBinding;
 
 module.exports.minify = minify;
@@ -280,3 +292,15 @@ module.exports.minify = minify;
 module.exports.minifyFragment = minifyFragment;
 module.exports.minifySync = minifySync;
 module.exports.minifyFragmentSync = minifyFragmentSync;
+module.exports.minifyMany = minifyMany;
+module.exports.HtmlMinifier = HtmlMinifier;
+module.exports.createMinifier = createMinifier;
+module.exports.minifyObject = minifyObject;
+module.exports.minifyFragmentObject = minifyFragmentObject;
+module.exports.minifyObjectSync = minifyObjectSync;
+module.exports.minifyFragmentObjectSync = minifyFragmentObjectSync;
+module.exports.getOptionsSchema = getOptionsSchema;
+module.exports.setPanicSink = setPanicSink;
+module.exports.initTracing = initTracing;
+module.exports.enableCache = enableCache;
+module.exports.clearCache = clearCache;
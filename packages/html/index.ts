@@ -1,74 +1,154 @@
 import * as binding from "./binding";
+import type { FragmentOptions, Options } from "./options";
 
-export type MinifierType = "js-module" | "js-script" | "json" | "css" | "html";
-
-export type Options = {
-  filename?: string;
-  iframeSrcdoc?: boolean;
-  scriptingEnabled?: boolean;
-  forceSetHtml5Doctype?: boolean;
-  collapseWhitespaces?:
-    | "none"
-    | "all"
-    | "smart"
-    | "conservative"
-    | "advanced-conservative"
-    | "only-metadata";
-  removeEmptyMetadataElements?: boolean;
-  removeComments?: boolean;
-  preserveComments: string[];
-  minifyConditionalComments?: boolean;
-  removeEmptyAttributes?: boolean;
-  removeRedundantAttributes?: boolean;
-  collapseBooleanAttributes?: boolean;
-  normalizeAttributes?: boolean;
-  minifyJson?: boolean | { pretty?: boolean };
-  // TODO improve me after typing `@swc/css`
-  minifyJs?: boolean | { parser?: any; minifier?: any; codegen?: any };
-  minifyCss?: boolean | { parser?: any; minifier?: any; codegen?: any };
-  minifyAdditionalScriptsContent?: [string, MinifierType][];
-  minifyAdditionalAttributes?: [string, MinifierType][];
-  sortSpaceSeparatedAttributeValues?: boolean;
-  sortAttributes?: boolean;
-  tagOmission?: boolean;
-  selfClosingVoidElements?: boolean;
-  quotes?: boolean;
-};
+export * from "./options";
 
-export type FragmentOptions = Options & {
-  mode?: "no-quirks" | "limited-quirks" | "quirks";
-  context_element?: binding.Element;
-  form_element?: binding.Element;
-};
+export type MinifierType = "js-module" | "js-script" | "json" | "css" | "html";
 
 export async function minify(
   content: Buffer,
   options?: Options
 ): Promise<binding.TransformOutput> {
-  return binding.minify(content, toBuffer(options ?? {}));
+  return binding.minifyObject(content, options ?? {});
 }
 
 export async function minifyFragment(
   content: Buffer,
   options?: FragmentOptions
 ): Promise<binding.TransformOutput> {
-  return binding.minifyFragment(content, toBuffer(options ?? {}));
+  return binding.minifyFragmentObject(content, options ?? {});
 }
 
 export function minifySync(
   content: Buffer,
   options?: Options
 ): binding.TransformOutput {
-  return binding.minifySync(content, toBuffer(options ?? {}));
+  return binding.minifyObjectSync(content, options ?? {});
 }
 
 export async function minifyFragmentSync(
   content: Buffer,
   options?: FragmentOptions
 ): Promise<binding.TransformOutput> {
-  return binding.minifyFragmentSync(content, toBuffer(options ?? {}));
+  return binding.minifyFragmentObjectSync(content, options ?? {});
+}
+
+export async function minifyMany(
+  files: binding.FileInput[],
+  options?: Options
+): Promise<binding.FileOutput[]> {
+  return binding.minifyMany(files, toBuffer(options ?? {}));
+}
+
+/**
+ * Returns a minifier which can be reused for many inputs. The options are
+ * used for both documents and fragments.
+ */
+export function createMinifier(
+  options?: FragmentOptions
+): binding.HtmlMinifier {
+  return binding.createMinifier(toBuffer(options ?? {}));
+}
+
+/**
+ * Returns the JSON Schema of the options, keyed by the function using them.
+ */
+export function getOptionsSchema(): Record<string, object> {
+  return binding.getOptionsSchema();
+}
+
+/**
+ * The error thrown for an internal panic, with `code` set to
+ * `"SWC_INTERNAL_PANIC"`.
+ */
+export type InternalPanicError = Error & {
+  code: "SWC_INTERNAL_PANIC";
+  /** Like `src/lib.rs:10:5`. */
+  location?: string;
+  /** Captured if `RUST_BACKTRACE=1` or `SWC_DEBUG=1` is set. */
+  backtrace?: string;
+  /** The input being processed, if it has a filename. */
+  filename?: string;
+};
+
+/**
+ * The error thrown for an exceeded limit of `limits`.
+ */
+export type LimitError = Error & {
+  code:
+    | "SWC_LIMIT_INPUT_BYTES"
+    | "SWC_LIMIT_DEPTH"
+    | "SWC_LIMIT_NODES"
+    | "SWC_LIMIT_TIMEOUT";
+};
+
+/**
+ * Sets where internal panics are logged: `"stderr"`, which is the default,
+ * `"none"` or the path of a file to append to.
+ */
+export function setPanicSink(sink?: "stderr" | "none" | string) {
+  binding.setPanicSink(sink);
+}
+
+/**
+ * Caches the results of `minify` and `minifyFragment` on disk, keyed by the input, the
+ * options and the version, so unchanged inputs are not processed again.
+ * Errors are not cached, nor results with `stats`.
+ */
+export function enableCache(options: {
+  /** The directory of the cached results, which can be shared by processes. */
+  dir: string;
+  /**
+   * The size of the cached results in bytes, over which the least recently
+   * used ones are removed. Defaults to 512 MiB.
+   */
+  maxSize?: number;
+}) {
+  binding.enableCache(options);
+}
+
+/**
+ * Removes the cached results. The cache stays enabled.
+ */
+export function clearCache() {
+  binding.clearCache();
+}
+
+/**
+ * Records the phases of each function, like `parse` and `codegen`, as spans.
+ * It can be called only once.
+ */
+export function initTracing(options?: {
+  /** Like `RUST_LOG`. Defaults to `info`, which includes the phases. */
+  filter?: string;
+  /**
+   * `chrome` writes a trace for `chrome://tracing` or Perfetto. Defaults to
+   * `pretty`.
+   */
+  format?: "chrome" | "json" | "pretty";
+  /**
+   * The file to write to. Defaults to stderr, or to `trace-{timestamp}.json`
+   * in the working directory for `chrome`.
+   */
+  path?: string;
+}) {
+  binding.initTracing(options);
 }
 
 function toBuffer(t: any): Buffer {
-  return Buffer.from(JSON.stringify(t));
+  return Buffer.from(JSON.stringify(t, rejectRegExp));
+}
+
+/**
+ * Rejects a `RegExp` like the functions taking the options as an object, as
+ * the patterns use the syntax of the Rust `regex` crate.
+ */
+function rejectRegExp(key: string, value: any): any {
+  if (value instanceof RegExp) {
+    throw new TypeError(
+      `option \`${key}\` is a RegExp, which is not supported: pass its source as a string, in the syntax of the Rust \`regex\` crate`
+    );
+  }
+
+  return value;
 }
@@ -0,0 +1,314 @@
+// Generated from the JSON Schema of the options by the tests of the core
+// crate. Run them with `UPDATE_OPTIONS_TS=1` to update it.
+
+export type Options = {
+  /**
+   * Escape all non-ASCII characters.
+   */
+  asciiOnly?: boolean;
+  /**
+   * Return `codeFrame` in the diagnostics. Defaults to true, but rendering them
+   * is slow for many diagnostics.
+   */
+  codeFrame?: boolean;
+  collapseBooleanAttributes?: boolean;
+  collapseWhitespaces?: "none" | "all" | "smart" | "conservative" | "advanced-conservative" | "only-metadata";
+  /**
+   * The encoding of the input, if it has no byte order mark, like `shift_jis`.
+   * Defaults to the one declared by `<meta>` for documents, or UTF-8. Invalid
+   * bytes are replaced and reported as a `lossy-decoding` warning in `errors`.
+   */
+  encoding?: string | null;
+  filename?: string | null;
+  forceSetHtml5Doctype?: boolean;
+  iframeSrcdoc?: boolean;
+  /**
+   * Fail on untrusted input which is too large or too deep.
+   */
+  limits?: Limits;
+  /**
+   * The maximum number of recoverable errors to report.
+   */
+  maxErrors?: number | null;
+  /**
+   * Merge consecutive metadata elements of the same kind, like `style`
+   * elements, into one. Defaults to true.
+   */
+  mergeMetadataElements?: boolean;
+  minifyAdditionalAttributes?: [string, "js-module" | "js-script" | "json" | "css" | "html"][];
+  minifyAdditionalScriptsContent?: [string, "js-module" | "js-script" | "json" | "css" | "html"][];
+  minifyConditionalComments?: boolean;
+  minifyCss?: boolean | {
+    codegen?: {
+      minify?: boolean;
+      [key: string]: unknown;
+    };
+    minifier?: Record<string, unknown>;
+    parser?: {
+      allowWrongLineComments?: boolean;
+      cssModules?: boolean;
+      legacyIe?: boolean;
+      legacyNesting?: boolean;
+      [key: string]: unknown;
+    };
+  };
+  minifyJs?: boolean | {
+    codegen?: {
+      asciiOnly?: boolean;
+      emitAssertForImportAttributes?: boolean;
+      inlineScript?: boolean;
+      minify?: boolean;
+      omitLastSemi?: boolean;
+      target?: string;
+      [key: string]: unknown;
+    };
+    minifier?: {
+      compress?: unknown;
+      enclose?: boolean;
+      mangle?: unknown;
+      rename?: boolean;
+      wrap?: boolean;
+      [key: string]: unknown;
+    };
+    parser?: {
+      allowReturnOutsideFunction?: boolean;
+      allowSuperOutsideMethod?: boolean;
+      autoAccessors?: boolean;
+      comments?: boolean;
+      decorators?: boolean;
+      decoratorsBeforeExport?: boolean;
+      explicitResourceManagement?: boolean;
+      exportDefaultFrom?: boolean;
+      functionBind?: boolean;
+      importAttributes?: boolean;
+      jsx?: boolean;
+      syntax?: string;
+      target?: string;
+      [key: string]: unknown;
+    };
+  };
+  minifyJson?: boolean | {
+    pretty: boolean;
+  };
+  normalizeAttributes?: boolean;
+  /**
+   * Characters which cannot be encoded with this encoding are escaped. The
+   * output is still returned as a string.
+   */
+  outputEncoding?: string | null;
+  preserveComments?: string[];
+  quotes?: boolean | null;
+  /**
+   * Return the input as-is with the errors instead of throwing, if the input
+   * cannot be parsed. The recoverable errors are limited by `maxErrors`.
+   */
+  recover?: boolean;
+  removeComments?: boolean;
+  removeEmptyAttributes?: boolean;
+  removeEmptyMetadataElements?: boolean;
+  removeRedundantAttributes?: "none" | "all" | "smart";
+  scriptingEnabled?: boolean;
+  selfClosingVoidElements?: boolean | null;
+  sortAttributes?: boolean;
+  sortSpaceSeparatedAttributeValues?: boolean;
+  /**
+   * Return `stats` in the output, with the size of the input and the output,
+   * the duration of each phase and the number of nodes before and after.
+   */
+  stats?: boolean;
+  /**
+   * Fail if the parser reports any recoverable error.
+   */
+  strict?: boolean;
+  tagOmission?: boolean | null;
+  /**
+   * Whether unknown keys in these options fail, or are reported in `warnings`
+   * of the output. Defaults to `error`.
+   */
+  unknownOptions?: "error" | "warn";
+};
+
+export type FragmentOptions = {
+  /**
+   * Escape all non-ASCII characters.
+   */
+  asciiOnly?: boolean;
+  /**
+   * Return `codeFrame` in the diagnostics. Defaults to true, but rendering them
+   * is slow for many diagnostics.
+   */
+  codeFrame?: boolean;
+  collapseBooleanAttributes?: boolean;
+  collapseWhitespaces?: "none" | "all" | "smart" | "conservative" | "advanced-conservative" | "only-metadata";
+  /**
+   * Used only for Document Fragment Default: `template` in HTML namespace
+   */
+  contextElement?: Element | null;
+  /**
+   * The encoding of the input, if it has no byte order mark, like `shift_jis`.
+   * Defaults to the one declared by `<meta>` for documents, or UTF-8. Invalid
+   * bytes are replaced and reported as a `lossy-decoding` warning in `errors`.
+   */
+  encoding?: string | null;
+  filename?: string | null;
+  forceSetHtml5Doctype?: boolean;
+  /**
+   * Used only for Document Fragment Default: None
+   */
+  formElement?: Element | null;
+  iframeSrcdoc?: boolean;
+  /**
+   * Fail on untrusted input which is too large or too deep.
+   */
+  limits?: Limits;
+  /**
+   * The maximum number of recoverable errors to report.
+   */
+  maxErrors?: number | null;
+  /**
+   * Merge consecutive metadata elements of the same kind, like `style`
+   * elements, into one. Defaults to true.
+   */
+  mergeMetadataElements?: boolean;
+  minifyAdditionalAttributes?: [string, "js-module" | "js-script" | "json" | "css" | "html"][];
+  minifyAdditionalScriptsContent?: [string, "js-module" | "js-script" | "json" | "css" | "html"][];
+  minifyConditionalComments?: boolean;
+  minifyCss?: boolean | {
+    codegen?: {
+      minify?: boolean;
+      [key: string]: unknown;
+    };
+    minifier?: Record<string, unknown>;
+    parser?: {
+      allowWrongLineComments?: boolean;
+      cssModules?: boolean;
+      legacyIe?: boolean;
+      legacyNesting?: boolean;
+      [key: string]: unknown;
+    };
+  };
+  minifyJs?: boolean | {
+    codegen?: {
+      asciiOnly?: boolean;
+      emitAssertForImportAttributes?: boolean;
+      inlineScript?: boolean;
+      minify?: boolean;
+      omitLastSemi?: boolean;
+      target?: string;
+      [key: string]: unknown;
+    };
+    minifier?: {
+      compress?: unknown;
+      enclose?: boolean;
+      mangle?: unknown;
+      rename?: boolean;
+      wrap?: boolean;
+      [key: string]: unknown;
+    };
+    parser?: {
+      allowReturnOutsideFunction?: boolean;
+      allowSuperOutsideMethod?: boolean;
+      autoAccessors?: boolean;
+      comments?: boolean;
+      decorators?: boolean;
+      decoratorsBeforeExport?: boolean;
+      explicitResourceManagement?: boolean;
+      exportDefaultFrom?: boolean;
+      functionBind?: boolean;
+      importAttributes?: boolean;
+      jsx?: boolean;
+      syntax?: string;
+      target?: string;
+      [key: string]: unknown;
+    };
+  };
+  minifyJson?: boolean | {
+    pretty: boolean;
+  };
+  /**
+   * Used only for Document Fragment Default: NoQuirks
+   */
+  mode?: "no-quirks" | "limited-quirks" | "quirks";
+  normalizeAttributes?: boolean;
+  /**
+   * Characters which cannot be encoded with this encoding are escaped. The
+   * output is still returned as a string.
+   */
+  outputEncoding?: string | null;
+  preserveComments?: string[];
+  quotes?: boolean | null;
+  /**
+   * Return the input as-is with the errors instead of throwing, if the input
+   * cannot be parsed. The recoverable errors are limited by `maxErrors`.
+   */
+  recover?: boolean;
+  removeComments?: boolean;
+  removeEmptyAttributes?: boolean;
+  removeEmptyMetadataElements?: boolean;
+  removeRedundantAttributes?: "none" | "all" | "smart";
+  scriptingEnabled?: boolean;
+  selfClosingVoidElements?: boolean | null;
+  sortAttributes?: boolean;
+  sortSpaceSeparatedAttributeValues?: boolean;
+  /**
+   * Return `stats` in the output, with the size of the input and the output,
+   * the duration of each phase and the number of nodes before and after.
+   */
+  stats?: boolean;
+  /**
+   * Fail if the parser reports any recoverable error.
+   */
+  strict?: boolean;
+  tagOmission?: boolean | null;
+  /**
+   * Whether unknown keys in these options fail, or are reported in `warnings`
+   * of the output. Defaults to `error`.
+   */
+  unknownOptions?: "error" | "warn";
+};
+
+export type Attribute = {
+  name: string;
+  namespace?: string | null;
+  prefix?: string | null;
+  value?: string | null;
+};
+
+/**
+ * The context element or the form element of a fragment.
+ */
+export type Element = {
+  attributes: Attribute[];
+  isSelfClosing: boolean;
+  namespace: string;
+  tagName: string;
+};
+
+export type Limits = {
+  /**
+   * The maximum nesting of elements, as the minifier and the printer recurse
+   * into them.
+   *
+   * It's checked on the tags while parsing, before building a deep tree, and
+   * again on the parsed tree. While parsing, an element whose end tag is
+   * omitted counts until the end tag of its parent, unless it's a void element
+   * or one closed by its next sibling like `<li>` or `<p>`.
+   */
+  maxDepth?: number | null;
+  /**
+   * The maximum size of the input, in bytes.
+   */
+  maxInputBytes?: number | null;
+  /**
+   * The maximum number of elements, text nodes and comments of the parsed tree.
+   */
+  maxNodes?: number | null;
+  /**
+   * Milliseconds after which processing fails.
+   *
+   * It's checked while parsing and walking the tree, but the minifier of swc
+   * runs to its end once started, so a call can take longer. `maxInputBytes`,
+   * `maxDepth` and `maxNodes` bound its work.
+   */
+  timeoutMs?: number | null;
+};
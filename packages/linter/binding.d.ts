@@ -6,10 +6,115 @@
 export interface Diagnostic {
   level: string
   message: string
-  span: any
+  /** The id of the lint rule or the error code, if any. */
+  code?: string
+  span?: DiagnosticSpan
+  /** The source code around `span`, rendered like thrown errors. */
+  codeFrame?: string
+}
+export interface DiagnosticSpan {
+  filename: string
+  /** Byte offset from the start of the file. */
+  start: number
+  /** Byte offset from the start of the file. */
+  end: number
+  /** 1-based. */
+  startLine: number
+  /** 1-based, in characters. */
+  startColumn: number
+  /** 1-based. */
+  endLine: number
+  /** 1-based, in characters. */
+  endColumn: number
 }
 export interface TransformOutput {
   errors?: Array<Diagnostic>
+  /** Unknown keys of the options, if `unknownOptions` is `"warn"`. */
+  warnings?: Array<string>
+}
+export interface FileInput {
+  code: Buffer
+  /** Overrides `filename` of the shared options. */
+  filename?: string
+}
+export interface FileOutput {
+  filename?: string
+  output?: TransformOutput
+  /** The message of the error, if this file failed. */
+  error?: string
 }
+/**
+ * `signal` aborts it between phases; a running phase, like parsing or a lint
+ * rule, is not interrupted.
+ */
 export function lint(code: Buffer, opts: Buffer, signal?: AbortSignal | undefined | null): Promise<TransformOutput>
 export function lintSync(code: Buffer, opts: Buffer): TransformOutput
+/**
+ * Lints `files` in parallel, sharing `opts`.
+ *
+ * `signal` aborts it between phases; a running phase, like parsing or a lint
+ * rule, is not interrupted.
+ */
+export function lintMany(files: Array<FileInput>, opts: Buffer, signal?: AbortSignal | undefined | null): Promise<Array<FileOutput>>
+export function createLinter(opts: Buffer): Linter
+export class Linter {
+  /**
+   * `filename` overrides the one of the options.
+   *
+   * `signal` aborts it between phases; a running phase, like parsing or a lint
+   * rule, is not interrupted.
+   */
+  run(code: Buffer, filename?: string | undefined | null, signal?: AbortSignal | undefined | null): Promise<TransformOutput>
+  /** `filename` overrides the one of the options. */
+  runSync(code: Buffer, filename?: string | undefined | null): TransformOutput
+}
+/**
+ * Like [lint], with `opts` as a JS object instead of a JSON buffer.
+ *
+ * `signal` aborts it between phases; a running phase, like parsing or a lint
+ * rule, is not interrupted.
+ */
+export function lintObject(code: Buffer, opts: object, signal?: AbortSignal | undefined | null): Promise<TransformOutput>
+export function lintObjectSync(code: Buffer, opts: object): TransformOutput
+/** Returns the JSON Schema of the options, keyed by the function using them. */
+export function getOptionsSchema(): any
+/**
+ * Sets where panics are logged: `"stderr"`, which is the default, `"none"`
+ * or the path of a file.
+ */
+export function setPanicSink(sink?: string | undefined | null): void
+export interface TracingOptions {
+  /** Like `RUST_LOG`. Defaults to `info`, which includes the phases. */
+  filter?: string
+  /** `"chrome"`, `"json"` or `"pretty"`. Defaults to `"pretty"`. */
+  format?: string
+  /**
+   * The file to write to. Defaults to stderr, or to `trace-{timestamp}.json`
+   * in the working directory for `"chrome"`.
+   */
+  path?: string
+}
+/**
+ * Installs the subscriber which records the phases. It can be called only
+ * once.
+ */
+export function initTracing(options?: TracingOptions | undefined | null): void
+export interface CacheOptions {
+  /**
+   * The directory of the cached results, which is created if needed. It
+   * can be shared by processes.
+   */
+  dir: string
+  /**
+   * The size of the cached results in bytes, over which the least recently
+   * used ones are removed. Defaults to 512 MiB.
+   */
+  maxSize?: number
+}
+/**
+ * Caches the results of `lint` in `options.dir`. Calling it again replaces the
+ * options.
+ */
+export function enableCache(options: CacheOptions): void
+/** Removes the cached results. The cache stays enabled. */
+export function clearCache(): void
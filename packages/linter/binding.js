@@ -252,7 +252,17 @@ if (!nativeBinding) {
   throw new Error(`Failed to load native binding`)
 }
 
-const { lint, lintSync } = nativeBinding
+const { lint, lintSync, lintMany, Linter, createLinter, lintObject, lintObjectSync, getOptionsSchema, setPanicSink, initTracing, enableCache, clearCache } = nativeBinding
 
 module.exports.lint = lint
 module.exports.lintSync = lintSync
+module.exports.lintMany = lintMany
+module.exports.Linter = Linter
+module.exports.createLinter = createLinter
+module.exports.lintObject = lintObject
+module.exports.lintObjectSync = lintObjectSync
+module.exports.getOptionsSchema = getOptionsSchema
+module.exports.setPanicSink = setPanicSink
+module.exports.initTracing = initTracing
+module.exports.enableCache = enableCache
+module.exports.clearCache = clearCache